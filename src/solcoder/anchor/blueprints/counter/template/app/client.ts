@@ -0,0 +1,171 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+type PublicKey = anchor.web3.PublicKey;
+type Amount = number | anchor.BN;
+
+export const I64_MIN = new anchor.BN("-9223372036854775808");
+export const I64_MAX = new anchor.BN("9223372036854775807");
+
+export type CounterBounds = {
+  min?: Amount;
+  max?: Amount;
+  saturating?: boolean;
+  /** Seconds a proposed authority waits before it can accept; defaults to a day. */
+  transferDelay?: Amount;
+};
+export type HistoryEntry = { timestamp: anchor.BN; delta: anchor.BN; newCount: anchor.BN };
+
+/** Mirrors `CounterHistory::LEN`. */
+export const HISTORY_LEN = 16;
+
+export const DEFAULT_TRANSFER_DELAY = 24 * 60 * 60;
+
+/** Logged by `preview`; `projectedCount` is what `increment(delta)` would set. */
+export type CountPreview = {
+  counter: PublicKey;
+  count: anchor.BN;
+  projectedCount: anchor.BN;
+  delta: anchor.BN;
+};
+
+function toBN(value: Amount): anchor.BN {
+  return anchor.BN.isBN(value) ? value : new anchor.BN(value);
+}
+
+/** Counter PDA for `[b"counter", authority, label]`. */
+export function counterAddress(
+  programId: PublicKey,
+  authority: PublicKey,
+  label: string
+): PublicKey {
+  const [address] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), authority.toBuffer(), Buffer.from(label)],
+    programId
+  );
+  return address;
+}
+
+/** History PDA for `[b"history", counter]`. */
+export function historyAddress(programId: PublicKey, counter: PublicKey): PublicKey {
+  const [address] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("history"), counter.toBuffer()],
+    programId
+  );
+  return address;
+}
+
+/**
+ * Typed helpers for every {{PROGRAM_NAME_SNAKE}} instruction. Each returns the
+ * Anchor method builder, so callers finish with `.rpc()`, `.instruction()` or
+ * add `.signers([...])` when a delegate or new authority signs. With
+ * `{ history: true }` every update also writes the counter's history, which
+ * must exist (see `initHistory`).
+ */
+export function {{PROGRAM_NAME_CAMEL}}Client(
+  program: Program<{{PROGRAM_NAME_PASCAL}}>,
+  authority: PublicKey,
+  options: { history?: boolean } = {}
+) {
+  const counter = (label: string) => counterAddress(program.programId, authority, label);
+  const history = (address: PublicKey) => historyAddress(program.programId, address);
+  const update = (address: PublicKey, signer: PublicKey) => ({
+    counter: address,
+    authority: signer,
+    history: options.history ? history(address) : null,
+  });
+
+  return {
+    program,
+    authority,
+    counterAddress: counter,
+
+    fetch: (address: PublicKey) => program.account.counter.fetch(address),
+
+    historyAddress: history,
+
+    /** History entries from oldest to newest. */
+    fetchHistory: async (address: PublicKey): Promise<HistoryEntry[]> => {
+      const account = await program.account.counterHistory.fetch(history(address));
+      return Array.from(
+        { length: account.len },
+        (_, i) => account.entries[(account.head + HISTORY_LEN - account.len + i) % HISTORY_LEN]
+      );
+    },
+
+    initHistory: (address: PublicKey) =>
+      program.methods.initHistory().accountsPartial({
+        counter: address,
+        history: history(address),
+        authority,
+      }),
+
+    initialize: (label: string, bounds: CounterBounds = {}) =>
+      program.methods
+        .initialize(
+          label,
+          toBN(bounds.min ?? I64_MIN),
+          toBN(bounds.max ?? I64_MAX),
+          bounds.saturating ?? false,
+          toBN(bounds.transferDelay ?? DEFAULT_TRANSFER_DELAY)
+        )
+        .accountsPartial({ counter: counter(label), authority }),
+
+    increment: (address: PublicKey, amount: Amount, signer: PublicKey = authority) =>
+      program.methods.increment(toBN(amount)).accountsPartial(update(address, signer)),
+
+    /** Read-only: finish with `.simulate()` rather than `.rpc()`; see `simulatePreview`. */
+    preview: (address: PublicKey, delta: Amount) =>
+      program.methods.preview(toBN(delta)).accountsPartial({ counter: address }),
+
+    /** Simulates `preview` and returns its event; nothing is sent or written. */
+    simulatePreview: async (address: PublicKey, delta: Amount): Promise<CountPreview> => {
+      const { events } = await program.methods
+        .preview(toBN(delta))
+        .accountsPartial({ counter: address })
+        .simulate();
+      const event = events.find((item) => item.name === "countPreviewed");
+      if (!event) {
+        throw new Error("preview did not emit CountPreviewed");
+      }
+      return event.data as CountPreview;
+    },
+
+    incrementMany: (address: PublicKey, amounts: Amount[], signer: PublicKey = authority) =>
+      program.methods
+        .incrementMany(amounts.map(toBN))
+        .accountsPartial(update(address, signer)),
+
+    decrement: (address: PublicKey, amount: Amount, signer: PublicKey = authority) =>
+      program.methods.decrement(toBN(amount)).accountsPartial(update(address, signer)),
+
+    reset: (address: PublicKey) =>
+      program.methods.reset().accountsPartial(update(address, authority)),
+
+    setCount: (address: PublicKey, value: Amount) =>
+      program.methods.setCount(toBN(value)).accountsPartial(update(address, authority)),
+
+    setPaused: (address: PublicKey, paused: boolean) =>
+      program.methods.setPaused(paused).accountsPartial(update(address, authority)),
+
+    proposeAuthority: (address: PublicKey, newAuthority: PublicKey) =>
+      program.methods.proposeAuthority(newAuthority).accountsPartial(update(address, authority)),
+
+    /** Signed by `pendingAuthority`; add `.signers([...])` unless it is the wallet. */
+    acceptAuthority: (address: PublicKey, pendingAuthority: PublicKey) =>
+      program.methods.acceptAuthority().accountsPartial(update(address, pendingAuthority)),
+
+    cancelPendingAuthority: (address: PublicKey) =>
+      program.methods.cancelPendingAuthority().accountsPartial(update(address, authority)),
+
+    addDelegate: (address: PublicKey, delegate: PublicKey) =>
+      program.methods.addDelegate(delegate).accountsPartial(update(address, authority)),
+
+    removeDelegate: (address: PublicKey, delegate: PublicKey) =>
+      program.methods.removeDelegate(delegate).accountsPartial(update(address, authority)),
+
+    closeCounter: (address: PublicKey) =>
+      program.methods.closeCounter().accountsPartial(update(address, authority)),
+  };
+}
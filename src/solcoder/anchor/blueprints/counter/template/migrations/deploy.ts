@@ -0,0 +1,30 @@
+// Run by `anchor migrate` with the provider configured in Anchor.toml. It creates
+// the wallet's `default` counter on the first run and leaves it alone afterwards,
+// so it is safe to run any number of times.
+
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+import { {{PROGRAM_NAME_CAMEL}}Client } from "../app/client";
+
+const PROGRAM_ID = new anchor.web3.PublicKey("{{PROGRAM_ID}}");
+const DEFAULT_LABEL = "default";
+
+module.exports = async function (provider: anchor.AnchorProvider) {
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  if (!program.programId.equals(PROGRAM_ID)) {
+    throw new Error(
+      `Workspace program id ${program.programId.toBase58()} is not ${PROGRAM_ID.toBase58()}.`
+    );
+  }
+  const client = {{PROGRAM_NAME_CAMEL}}Client(program, provider.wallet.publicKey);
+  const counter = client.counterAddress(DEFAULT_LABEL);
+  if (await provider.connection.getAccountInfo(counter)) {
+    console.log(`Counter "${DEFAULT_LABEL}" already exists at ${counter.toBase58()}.`);
+    return;
+  }
+  await client.initialize(DEFAULT_LABEL).rpc();
+  console.log(`Initialized counter "${DEFAULT_LABEL}" at ${counter.toBase58()}.`);
+};
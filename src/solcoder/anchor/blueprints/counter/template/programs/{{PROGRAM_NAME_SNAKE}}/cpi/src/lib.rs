@@ -0,0 +1,271 @@
+//! Instruction builders for `{{PROGRAM_NAME_SNAKE}}`, for programs that call it
+//! via CPI and for off-chain Rust clients. Each function returns a plain
+//! `Instruction`; on-chain callers pass it to `invoke` or `invoke_signed` together
+//! with the account infos listed in its `accounts`.
+//!
+//! The crate does not depend on the program itself, so linking it never pulls in
+//! a second entrypoint, and it only needs `alloc`.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::{pubkey, system_program};
+use anchor_lang::AnchorSerialize;
+
+/// Program id of `{{PROGRAM_NAME_SNAKE}}`; keep in sync with its `declare_id!`.
+pub const ID: Pubkey = pubkey!("{{PROGRAM_ID}}");
+
+/// Counter PDA for `[b"counter", authority, label]`.
+pub fn counter_address(authority: &Pubkey, label: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"counter", authority.as_ref(), label.as_bytes()], &ID)
+}
+
+/// History PDA for `[b"history", counter]`.
+pub fn history_address(counter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"history", counter.as_ref()], &ID)
+}
+
+/// Creates the counter PDA for `(authority, label)`; `authority` pays and signs.
+pub fn initialize(
+    authority: &Pubkey,
+    label: &str,
+    min: i64,
+    max: i64,
+    saturating: bool,
+    transfer_delay: i64,
+) -> Instruction {
+    let (counter, _) = counter_address(authority, label);
+    instruction(
+        "initialize",
+        (String::from(label), min, max, saturating, transfer_delay),
+        vec![
+            AccountMeta::new(counter, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn increment(
+    counter: &Pubkey,
+    signer: &Pubkey,
+    history: Option<&Pubkey>,
+    amount: i64,
+) -> Instruction {
+    instruction(
+        "increment",
+        amount,
+        update_accounts(counter, signer, history),
+    )
+}
+
+/// Read-only and unsigned; meant for `simulateTransaction`, which returns the
+/// `CountPreviewed` event in its logs.
+pub fn preview(counter: &Pubkey, delta: i64) -> Instruction {
+    instruction(
+        "preview",
+        delta,
+        vec![AccountMeta::new_readonly(*counter, false)],
+    )
+}
+
+pub fn increment_many(
+    counter: &Pubkey,
+    signer: &Pubkey,
+    history: Option<&Pubkey>,
+    amounts: &[i64],
+) -> Instruction {
+    instruction(
+        "increment_many",
+        amounts.to_vec(),
+        update_accounts(counter, signer, history),
+    )
+}
+
+pub fn decrement(
+    counter: &Pubkey,
+    signer: &Pubkey,
+    history: Option<&Pubkey>,
+    amount: i64,
+) -> Instruction {
+    instruction(
+        "decrement",
+        amount,
+        update_accounts(counter, signer, history),
+    )
+}
+
+pub fn reset(counter: &Pubkey, authority: &Pubkey, history: Option<&Pubkey>) -> Instruction {
+    instruction("reset", (), update_accounts(counter, authority, history))
+}
+
+pub fn set_count(
+    counter: &Pubkey,
+    authority: &Pubkey,
+    history: Option<&Pubkey>,
+    value: i64,
+) -> Instruction {
+    instruction(
+        "set_count",
+        value,
+        update_accounts(counter, authority, history),
+    )
+}
+
+pub fn init_history(counter: &Pubkey, authority: &Pubkey) -> Instruction {
+    instruction(
+        "init_history",
+        (),
+        vec![
+            AccountMeta::new_readonly(*counter, false),
+            AccountMeta::new(history_address(counter).0, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn set_paused(counter: &Pubkey, authority: &Pubkey, paused: bool) -> Instruction {
+    instruction(
+        "set_paused",
+        paused,
+        update_accounts(counter, authority, None),
+    )
+}
+
+pub fn propose_authority(
+    counter: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    instruction(
+        "propose_authority",
+        *new_authority,
+        update_accounts(counter, authority, None),
+    )
+}
+
+/// Signed by the pending authority once the counter's transfer delay has passed.
+pub fn accept_authority(counter: &Pubkey, pending_authority: &Pubkey) -> Instruction {
+    instruction(
+        "accept_authority",
+        (),
+        update_accounts(counter, pending_authority, None),
+    )
+}
+
+pub fn cancel_pending_authority(counter: &Pubkey, authority: &Pubkey) -> Instruction {
+    instruction(
+        "cancel_pending_authority",
+        (),
+        update_accounts(counter, authority, None),
+    )
+}
+
+pub fn add_delegate(counter: &Pubkey, authority: &Pubkey, delegate: &Pubkey) -> Instruction {
+    instruction(
+        "add_delegate",
+        *delegate,
+        update_accounts(counter, authority, None),
+    )
+}
+
+pub fn remove_delegate(counter: &Pubkey, authority: &Pubkey, delegate: &Pubkey) -> Instruction {
+    instruction(
+        "remove_delegate",
+        *delegate,
+        update_accounts(counter, authority, None),
+    )
+}
+
+/// Closes the counter, and its history when given, refunding rent to `authority`.
+pub fn close_counter(
+    counter: &Pubkey,
+    authority: &Pubkey,
+    history: Option<&Pubkey>,
+) -> Instruction {
+    instruction(
+        "close_counter",
+        (),
+        vec![
+            AccountMeta::new(*counter, false),
+            AccountMeta::new(*authority, true),
+            optional_account(history),
+        ],
+    )
+}
+
+/// Accounts of `UpdateCounter`: `signer` is the authority or one of its delegates.
+fn update_accounts(
+    counter: &Pubkey,
+    signer: &Pubkey,
+    history: Option<&Pubkey>,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*counter, false),
+        AccountMeta::new_readonly(*signer, true),
+        optional_account(history),
+    ]
+}
+
+/// Anchor reads an optional account passed as the program id as `None`.
+fn optional_account(account: Option<&Pubkey>) -> AccountMeta {
+    match account {
+        Some(address) => AccountMeta::new(*address, false),
+        None => AccountMeta::new_readonly(ID, false),
+    }
+}
+
+/// Anchor instruction data: `sha256("global:<name>")[..8]` followed by the Borsh-encoded arguments.
+fn instruction(name: &str, args: impl AnchorSerialize, accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = hashv(&[b"global:", name.as_bytes()]).to_bytes()[..8].to_vec();
+    args.serialize(&mut data)
+        .expect("serializing into a Vec cannot fail");
+    Instruction {
+        program_id: ID,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_targets_the_program_and_its_accounts() {
+        let authority = Pubkey::new_unique();
+        let (counter, _) = counter_address(&authority, "default");
+
+        let ix = increment(&counter, &authority, None, 5);
+
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(counter, false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(ID, false),
+            ]
+        );
+        assert_eq!(ix.data[..8], [11, 18, 104, 9, 104, 174, 59, 33]);
+        assert_eq!(ix.data[8..], 5i64.to_le_bytes());
+    }
+
+    #[test]
+    fn increment_writes_the_history_when_given() {
+        let authority = Pubkey::new_unique();
+        let (counter, _) = counter_address(&authority, "default");
+        let (history, _) = history_address(&counter);
+
+        let ix = increment(&counter, &authority, Some(&history), 1);
+
+        assert_eq!(ix.accounts[2], AccountMeta::new(history, false));
+    }
+}
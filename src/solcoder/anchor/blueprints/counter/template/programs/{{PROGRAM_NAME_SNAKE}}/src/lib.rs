@@ -6,56 +6,341 @@ declare_id!("{{PROGRAM_ID}}");
 pub mod {{PROGRAM_NAME_SNAKE}} {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    /// Pass `i64::MIN`/`i64::MAX` as the bounds for an unrestricted counter.
+    /// `transfer_delay` is how many seconds a proposed authority must wait
+    /// before `accept_authority`; it is fixed for the life of the counter.
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        label: String,
+        min: i64,
+        max: i64,
+        saturating: bool,
+        transfer_delay: i64,
+    ) -> Result<()> {
+        require!(
+            label.len() <= Counter::MAX_LABEL,
+            CounterError::LabelTooLong
+        );
+        require!(min <= max, CounterError::InvalidBounds);
+        require!(transfer_delay >= 0, CounterError::InvalidTransferDelay);
         let counter = &mut ctx.accounts.counter;
         counter.authority = ctx.accounts.authority.key();
-        counter.count = 0;
+        counter.label = label;
+        counter.bump = ctx.bumps.counter;
+        counter.min = min;
+        counter.max = max;
+        counter.saturating = saturating;
+        counter.paused = false;
+        counter.count = counter.start_value();
+        counter.last_updated = Clock::get()?.unix_timestamp;
+        counter.transfer_delay = transfer_delay;
+        counter.pending_authority = None;
+        counter.transfer_eligible_at = 0;
         Ok(())
     }
 
     pub fn increment(ctx: Context<UpdateCounter>, amount: i64) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
-        require_keys_eq!(
-            counter.authority,
-            ctx.accounts.authority.key(),
+        require!(
+            counter.can_update(&ctx.accounts.authority.key()),
             CounterError::Unauthorized
         );
-        counter.count = counter
+        require!(!counter.paused, CounterError::Paused);
+        let old_count = counter.count;
+        let new_count = counter
             .count
             .checked_add(amount)
             .ok_or(CounterError::Overflow)?;
+        counter.count = counter.check_bounds(new_count)?;
+        counter.last_updated = Clock::get()?.unix_timestamp;
+        if let Some(history) = ctx.accounts.history.as_mut() {
+            history.push(counter.last_updated, amount, counter.count);
+        }
+        emit!(CountChanged {
+            counter: counter.key(),
+            authority: counter.authority,
+            old_count,
+            new_count: counter.count,
+            delta: amount,
+        });
+        Ok(())
+    }
+
+    /// Emits `CountPreviewed` with the count `increment(delta)` would produce, failing
+    /// the same way on overflow or out-of-bounds results, but writes nothing: the
+    /// counter is read-only and no one signs, so clients can run it through
+    /// `simulateTransaction` and read the event from the simulated logs.
+    pub fn preview(ctx: Context<ReadCounter>, delta: i64) -> Result<()> {
+        let counter = &ctx.accounts.counter;
+        let projected = counter
+            .count
+            .checked_add(delta)
+            .ok_or(CounterError::Overflow)?;
+        emit!(CountPreviewed {
+            counter: counter.key(),
+            count: counter.count,
+            projected_count: counter.check_bounds(projected)?,
+            delta,
+        });
         Ok(())
     }
 
+    /// Applies every delta in `amounts` with `checked_add`; any overflow aborts
+    /// the whole batch, leaving the counter untouched.
+    pub fn increment_many(ctx: Context<UpdateCounter>, amounts: Vec<i64>) -> Result<()> {
+        require!(
+            amounts.len() <= Counter::MAX_BATCH,
+            CounterError::BatchTooLarge
+        );
+        let counter = &mut ctx.accounts.counter;
+        require!(
+            counter.can_update(&ctx.accounts.authority.key()),
+            CounterError::Unauthorized
+        );
+        require!(!counter.paused, CounterError::Paused);
+        let old_count = counter.count;
+        let new_count = amounts
+            .iter()
+            .try_fold(old_count, |acc, amount| acc.checked_add(*amount))
+            .ok_or(CounterError::Overflow)?;
+        counter.count = counter.check_bounds(new_count)?;
+        counter.last_updated = Clock::get()?.unix_timestamp;
+        let delta = counter.count.saturating_sub(old_count);
+        if let Some(history) = ctx.accounts.history.as_mut() {
+            history.push(counter.last_updated, delta, counter.count);
+        }
+        emit!(CountChanged {
+            counter: counter.key(),
+            authority: counter.authority,
+            old_count,
+            new_count: counter.count,
+            delta,
+        });
+        Ok(())
+    }
+
+    /// In saturating mode the result is clamped at the counter's floor instead of
+    /// failing; otherwise underflow and out-of-bounds results are rejected.
     pub fn decrement(ctx: Context<UpdateCounter>, amount: i64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        require!(
+            counter.can_update(&ctx.accounts.authority.key()),
+            CounterError::Unauthorized
+        );
+        require!(!counter.paused, CounterError::Paused);
+        let old_count = counter.count;
+        let new_count = if counter.saturating {
+            // Never clamp upward if the count already sits below the floor.
+            let floor = counter.floor().min(counter.count);
+            counter.count.saturating_sub(amount).max(floor)
+        } else {
+            counter
+                .count
+                .checked_sub(amount)
+                .ok_or(CounterError::Overflow)?
+        };
+        counter.count = counter.check_bounds(new_count)?;
+        counter.last_updated = Clock::get()?.unix_timestamp;
+        let delta = counter.count.saturating_sub(old_count);
+        if let Some(history) = ctx.accounts.history.as_mut() {
+            history.push(counter.last_updated, delta, counter.count);
+        }
+        emit!(CountChanged {
+            counter: counter.key(),
+            authority: counter.authority,
+            old_count,
+            new_count: counter.count,
+            delta,
+        });
+        Ok(())
+    }
+
+    pub fn reset(ctx: Context<UpdateCounter>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
         require_keys_eq!(
             counter.authority,
             ctx.accounts.authority.key(),
             CounterError::Unauthorized
         );
-        counter.count = counter
-            .count
-            .checked_sub(amount)
+        require!(!counter.paused, CounterError::Paused);
+        let old_count = counter.count;
+        counter.count = counter.start_value();
+        counter.last_updated = Clock::get()?.unix_timestamp;
+        if let Some(history) = ctx.accounts.history.as_mut() {
+            history.push(
+                counter.last_updated,
+                counter.count.saturating_sub(old_count),
+                counter.count,
+            );
+        }
+        Ok(())
+    }
+
+    /// Assigns `value` directly. This is a plain assignment, so it bypasses the
+    /// `checked_add`/`checked_sub` guards used by `increment` and `decrement`;
+    /// the `[min, max]` bounds still apply.
+    pub fn set_count(ctx: Context<UpdateCounter>, value: i64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        require_keys_eq!(
+            counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
+        require!(!counter.paused, CounterError::Paused);
+        let old_count = counter.count;
+        counter.count = counter.check_bounds(value)?;
+        counter.last_updated = Clock::get()?.unix_timestamp;
+        if let Some(history) = ctx.accounts.history.as_mut() {
+            history.push(
+                counter.last_updated,
+                counter.count.saturating_sub(old_count),
+                counter.count,
+            );
+        }
+        Ok(())
+    }
+
+    /// Creates the optional `CounterHistory` for this counter. Once it exists, pass
+    /// it as `history` to record every count change in its ring buffer.
+    pub fn init_history(ctx: Context<InitHistory>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
+        ctx.accounts.history.counter = ctx.accounts.counter.key();
+        Ok(())
+    }
+
+    /// While paused, every instruction that changes `count` fails with `Paused`;
+    /// authority transfers, delegate management and closing keep working.
+    pub fn set_paused(ctx: Context<UpdateCounter>, paused: bool) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        require_keys_eq!(
+            counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
+        counter.paused = paused;
+        Ok(())
+    }
+
+    /// First step of an authority transfer: `new_authority` may take over with
+    /// `accept_authority` once `transfer_delay` seconds have passed. Proposing
+    /// again replaces the pending authority and restarts the delay.
+    pub fn propose_authority(ctx: Context<UpdateCounter>, new_authority: Pubkey) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        require_keys_eq!(
+            counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
+        // The default pubkey has no private key, so handing it authority would brick the counter.
+        require_keys_neq!(
+            new_authority,
+            Pubkey::default(),
+            CounterError::InvalidAuthority
+        );
+        counter.pending_authority = Some(new_authority);
+        counter.transfer_eligible_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(counter.transfer_delay)
             .ok_or(CounterError::Overflow)?;
         Ok(())
     }
 
-    pub fn reset(ctx: Context<UpdateCounter>) -> Result<()> {
+    /// Signed by the pending authority; fails with `TransferNotReady` until
+    /// `transfer_eligible_at`.
+    pub fn accept_authority(ctx: Context<UpdateCounter>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        let signer = ctx.accounts.authority.key();
+        require!(
+            counter.pending_authority == Some(signer),
+            CounterError::Unauthorized
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= counter.transfer_eligible_at,
+            CounterError::TransferNotReady
+        );
+        counter.authority = signer;
+        counter.pending_authority = None;
+        counter.transfer_eligible_at = 0;
+        Ok(())
+    }
+
+    pub fn cancel_pending_authority(ctx: Context<UpdateCounter>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
         require_keys_eq!(
             counter.authority,
             ctx.accounts.authority.key(),
             CounterError::Unauthorized
         );
-        counter.count = 0;
+        require!(
+            counter.pending_authority.is_some(),
+            CounterError::NoPendingAuthority
+        );
+        counter.pending_authority = None;
+        counter.transfer_eligible_at = 0;
+        Ok(())
+    }
+
+    pub fn add_delegate(ctx: Context<UpdateCounter>, delegate: Pubkey) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        require_keys_eq!(
+            counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
+        require!(
+            !counter.delegates.contains(&delegate),
+            CounterError::DelegateExists
+        );
+        require!(
+            counter.delegates.len() < Counter::MAX_DELEGATES,
+            CounterError::TooManyDelegates
+        );
+        counter.delegates.push(delegate);
+        Ok(())
+    }
+
+    pub fn remove_delegate(ctx: Context<UpdateCounter>, delegate: Pubkey) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        require_keys_eq!(
+            counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
+        let index = counter
+            .delegates
+            .iter()
+            .position(|key| key == &delegate)
+            .ok_or(CounterError::DelegateNotFound)?;
+        counter.delegates.remove(index);
+        Ok(())
+    }
+
+    /// Closes the counter, returning its rent lamports to the authority. Pass the
+    /// counter's history, if any, to close it in the same call.
+    pub fn close_counter(ctx: Context<CloseCounter>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.counter.authority,
+            ctx.accounts.authority.key(),
+            CounterError::Unauthorized
+        );
         Ok(())
     }
 }
 
 #[derive(Accounts)]
+#[instruction(label: String)]
 pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = 8 + Counter::SIZE)]
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Counter::SIZE,
+        seeds = [b"counter", authority.key().as_ref(), label.as_bytes()],
+        bump
+    )]
     pub counter: Account<'info, Counter>,
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -67,22 +352,203 @@ pub struct UpdateCounter<'info> {
     #[account(mut)]
     pub counter: Account<'info, Counter>,
     pub authority: Signer<'info>,
+    /// When present, count changes are appended to this counter's history.
+    #[account(mut, has_one = counter @ CounterError::HistoryMismatch)]
+    pub history: Option<Account<'info, CounterHistory>>,
+}
+
+/// Read-only view of a counter for `preview`; nothing is writable or signs.
+#[derive(Accounts)]
+pub struct ReadCounter<'info> {
+    pub counter: Account<'info, Counter>,
+}
+
+#[derive(Accounts)]
+pub struct InitHistory<'info> {
+    pub counter: Account<'info, Counter>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CounterHistory::SIZE,
+        seeds = [b"history", counter.key().as_ref()],
+        bump
+    )]
+    pub history: Account<'info, CounterHistory>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCounter<'info> {
+    #[account(mut, close = authority)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, close = authority, has_one = counter @ CounterError::HistoryMismatch)]
+    pub history: Option<Account<'info, CounterHistory>>,
 }
 
 #[account]
 pub struct Counter {
     pub authority: Pubkey,
     pub count: i64,
+    pub min: i64,
+    pub max: i64,
+    pub label: String,
+    pub bump: u8,
+    pub saturating: bool,
+    /// Set by `set_paused`; blocks every change to `count` while true.
+    pub paused: bool,
+    /// Keys allowed to `increment`/`decrement` alongside the authority.
+    pub delegates: Vec<Pubkey>,
+    /// Unix timestamp of the last change to `count`.
+    pub last_updated: i64,
+    /// Seconds between `propose_authority` and the earliest `accept_authority`.
+    pub transfer_delay: i64,
+    /// Set by `propose_authority` until the transfer is accepted or cancelled.
+    pub pending_authority: Option<Pubkey>,
+    /// Unix timestamp from which `pending_authority` may accept; 0 when none is pending.
+    pub transfer_eligible_at: i64,
 }
 
 impl Counter {
-    pub const SIZE: usize = 32 + 8;
+    /// Labels are used as a PDA seed, so they share Solana's 32-byte seed limit.
+    pub const MAX_LABEL: usize = 32;
+    pub const MAX_DELEGATES: usize = 5;
+    /// Upper bound on `increment_many` deltas to keep compute predictable.
+    pub const MAX_BATCH: usize = 32;
+    pub const SIZE: usize = 32
+        + 8
+        + 8
+        + 8
+        + 4
+        + Self::MAX_LABEL
+        + 1
+        + 1
+        + 1
+        + 4
+        + Self::MAX_DELEGATES * 32
+        + 8
+        + 8
+        + (1 + 32)
+        + 8;
+
+    pub fn can_update(&self, signer: &Pubkey) -> bool {
+        self.authority == *signer || self.delegates.contains(signer)
+    }
+
+    /// Value used by `initialize` and `reset`: zero, clamped into `[min, max]`.
+    pub fn start_value(&self) -> i64 {
+        0i64.clamp(self.min, self.max)
+    }
+
+    /// Lowest value a saturating `decrement` clamps to: `min` when a lower bound is
+    /// set, zero when the counter is unrestricted (`min == i64::MIN`).
+    pub fn floor(&self) -> i64 {
+        if self.min == i64::MIN {
+            0
+        } else {
+            self.min
+        }
+    }
+
+    pub fn check_bounds(&self, value: i64) -> Result<i64> {
+        require!(
+            value >= self.min && value <= self.max,
+            CounterError::OutOfBounds
+        );
+        Ok(value)
+    }
+}
+
+/// Ring buffer of the latest count changes, seeded by `[b"history", counter]`.
+/// `head` is the slot the next entry goes to; once `len` reaches `LEN` the
+/// oldest entry is overwritten. Oldest-to-newest order is
+/// `entries[(head + LEN - len + i) % LEN]` for `i` in `0..len`.
+#[account]
+pub struct CounterHistory {
+    pub counter: Pubkey,
+    pub head: u8,
+    pub len: u8,
+    pub entries: [HistoryEntry; CounterHistory::LEN],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct HistoryEntry {
+    pub timestamp: i64,
+    pub delta: i64,
+    pub new_count: i64,
+}
+
+impl CounterHistory {
+    pub const LEN: usize = 16;
+    pub const SIZE: usize = 32 + 1 + 1 + Self::LEN * (8 + 8 + 8);
+
+    pub fn push(&mut self, timestamp: i64, delta: i64, new_count: i64) {
+        self.entries[self.head as usize] = HistoryEntry {
+            timestamp,
+            delta,
+            new_count,
+        };
+        self.head = ((self.head as usize + 1) % Self::LEN) as u8;
+        if (self.len as usize) < Self::LEN {
+            self.len += 1;
+        }
+    }
+}
+
+/// Emitted by `increment`, `increment_many`, and `decrement`; `delta` is the signed
+/// change applied to `count`.
+#[event]
+pub struct CountChanged {
+    pub counter: Pubkey,
+    pub authority: Pubkey,
+    pub old_count: i64,
+    pub new_count: i64,
+    pub delta: i64,
+}
+
+/// Emitted by `preview` only; `projected_count` is what `count` would become after
+/// `increment(delta)`, which the counter does not apply.
+#[event]
+pub struct CountPreviewed {
+    pub counter: Pubkey,
+    pub count: i64,
+    pub projected_count: i64,
+    pub delta: i64,
 }
 
 #[error_code]
 pub enum CounterError {
-    #[msg("Only the authority who initialized the counter can update it.")]
+    #[msg("Signer is not authorized to update this counter.")]
     Unauthorized,
     #[msg("Counter overflow or underflow encountered.")]
     Overflow,
+    #[msg("The new authority must not be the default pubkey.")]
+    InvalidAuthority,
+    #[msg("The minimum bound must not exceed the maximum bound.")]
+    InvalidBounds,
+    #[msg("The new count falls outside the counter's [min, max] bounds.")]
+    OutOfBounds,
+    #[msg("Counter label exceeds the maximum length.")]
+    LabelTooLong,
+    #[msg("The counter already has the maximum number of delegates.")]
+    TooManyDelegates,
+    #[msg("That key is already a delegate.")]
+    DelegateExists,
+    #[msg("That key is not a delegate.")]
+    DelegateNotFound,
+    #[msg("Too many amounts in a single batch.")]
+    BatchTooLarge,
+    #[msg("The counter is paused.")]
+    Paused,
+    #[msg("The history account belongs to a different counter.")]
+    HistoryMismatch,
+    #[msg("The authority transfer delay must not be negative.")]
+    InvalidTransferDelay,
+    #[msg("The pending authority cannot accept before the transfer delay has passed.")]
+    TransferNotReady,
+    #[msg("No authority transfer is pending.")]
+    NoPendingAuthority,
 }
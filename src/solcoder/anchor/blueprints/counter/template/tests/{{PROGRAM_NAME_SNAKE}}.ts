@@ -0,0 +1,698 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+import { {{PROGRAM_NAME_CAMEL}}Client } from "../app/client";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const authority = provider.wallet.publicKey;
+
+  const I64_MIN = new anchor.BN("-9223372036854775808");
+  const I64_MAX = new anchor.BN("9223372036854775807");
+
+  let labelSeq = 0;
+
+  function nextLabel(): string {
+    labelSeq += 1;
+    return `counter-${labelSeq}`;
+  }
+
+  function counterPda(label: string): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("counter"), authority.toBuffer(), Buffer.from(label)],
+      program.programId
+    );
+    return pda;
+  }
+
+  async function createCounter(
+    min: anchor.BN = I64_MIN,
+    max: anchor.BN = I64_MAX,
+    saturating = false,
+    transferDelay = 0
+  ): Promise<anchor.web3.PublicKey> {
+    const label = nextLabel();
+    const counter = counterPda(label);
+    await program.methods
+      .initialize(label, min, max, saturating, new anchor.BN(transferDelay))
+      .accountsPartial({ counter, authority })
+      .rpc();
+    return counter;
+  }
+
+  async function fetchCount(counter: anchor.web3.PublicKey): Promise<number> {
+    const account = await program.account.counter.fetch(counter);
+    return account.count.toNumber();
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  // A failed simulation surfaces as an AnchorError or, depending on the client
+  // version, as an error carrying the simulated logs.
+  async function expectSimulationError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      if (err instanceof anchor.AnchorError) {
+        assert.equal(err.error.errorCode.code, code);
+        return;
+      }
+      const { logs, simulationResponse } = err as {
+        logs?: string[];
+        simulationResponse?: { logs?: string[] | null };
+      };
+      const lines = logs ?? simulationResponse?.logs ?? [];
+      assert.isTrue(
+        lines.some((line) => line.includes(code)),
+        `expected ${code} in logs:\n${lines.join("\n")}`
+      );
+      return;
+    }
+    assert.fail(`expected ${code} simulation error`);
+  }
+
+  async function chainTime(): Promise<number> {
+    const slot = await provider.connection.getSlot();
+    return (await provider.connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+  }
+
+  // `accept_authority` compares `eligible_at` with the validator clock, which the
+  // tests cannot move, so the timelock test polls it instead of sleeping a fixed time.
+  async function waitUntil(timestamp: number): Promise<void> {
+    while ((await chainTime()) <= timestamp) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+  }
+
+  // Importing app/client.ts here also type-checks it against the generated IDL types.
+  it("drives a counter through the generated client", async () => {
+    const client = {{PROGRAM_NAME_CAMEL}}Client(program, authority);
+    const label = nextLabel();
+    const counter = client.counterAddress(label);
+    assert.ok(counter.equals(counterPda(label)));
+
+    await client.initialize(label, { min: -5, max: 5 }).rpc();
+    await client.increment(counter, 4).rpc();
+    await client.incrementMany(counter, [1, -2]).rpc();
+    await client.decrement(counter, 1).rpc();
+    assert.equal((await client.fetch(counter)).count.toNumber(), 2);
+
+    await client.setCount(counter, -5).rpc();
+    await client.reset(counter).rpc();
+    assert.equal(await fetchCount(counter), 0);
+    await client.closeCounter(counter).rpc();
+  });
+
+  it("is deployed at the declared program id", () => {
+    assert.equal(program.programId.toBase58(), "{{PROGRAM_ID}}");
+  });
+
+  it("initializes, increments and decrements", async () => {
+    const counter = await createCounter();
+    let account = await program.account.counter.fetch(counter);
+    assert.ok(account.authority.equals(authority));
+    assert.equal(account.count.toNumber(), 0);
+
+    await program.methods
+      .increment(new anchor.BN(10))
+      .accountsPartial({ counter, authority })
+      .rpc();
+    assert.equal(await fetchCount(counter), 10);
+
+    await program.methods
+      .decrement(new anchor.BN(4))
+      .accountsPartial({ counter, authority })
+      .rpc();
+    assert.equal(await fetchCount(counter), 6);
+  });
+
+  it("rejects increment from a second keypair", async () => {
+    const counter = await createCounter();
+    const intruder = anchor.web3.Keypair.generate();
+
+    await expectError(
+      program.methods
+        .increment(new anchor.BN(1))
+        .accountsPartial({ counter, authority: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+    assert.equal(await fetchCount(counter), 0);
+  });
+
+  it("resets the count back to zero", async () => {
+    const counter = await createCounter();
+    for (const amount of [1, 2, 3]) {
+      await program.methods
+        .increment(new anchor.BN(amount))
+        .accountsPartial({ counter, authority })
+        .rpc();
+    }
+    assert.equal(await fetchCount(counter), 6);
+
+    await program.methods.reset().accountsPartial({ counter, authority }).rpc();
+
+    assert.equal(await fetchCount(counter), 0);
+  });
+
+  it("sets an absolute count", async () => {
+    const counter = await createCounter();
+    await program.methods
+      .setCount(new anchor.BN(-42))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    assert.equal(await fetchCount(counter), -42);
+  });
+
+  it("rejects set_count from an unauthorized signer", async () => {
+    const counter = await createCounter();
+    const intruder = anchor.web3.Keypair.generate();
+
+    await expectError(
+      program.methods
+        .setCount(new anchor.BN(7))
+        .accountsPartial({ counter, authority: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("transfers authority after the delay and locks out the previous authority", async () => {
+    const counter = await createCounter(I64_MIN, I64_MAX, false, 3);
+    const newAuthority = anchor.web3.Keypair.generate();
+
+    const proposedAt = await chainTime();
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accountsPartial({ counter, authority })
+      .rpc();
+    let account = await program.account.counter.fetch(counter);
+    assert.ok(account.authority.equals(authority));
+    assert.ok(account.pendingAuthority?.equals(newAuthority.publicKey));
+    const eligibleAt = account.transferEligibleAt.toNumber();
+    assert.isAtLeast(eligibleAt, proposedAt + 3);
+
+    await waitUntil(eligibleAt);
+    await program.methods
+      .acceptAuthority()
+      .accountsPartial({ counter, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+
+    account = await program.account.counter.fetch(counter);
+    assert.ok(account.authority.equals(newAuthority.publicKey));
+    assert.isNull(account.pendingAuthority);
+
+    await expectError(
+      program.methods
+        .increment(new anchor.BN(1))
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "Unauthorized"
+    );
+
+    await program.methods
+      .increment(new anchor.BN(1))
+      .accountsPartial({ counter, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    assert.equal(await fetchCount(counter), 1);
+  });
+
+  it("rejects accepting authority before the delay has passed", async () => {
+    const counter = await createCounter(I64_MIN, I64_MAX, false, 3600);
+    const newAuthority = anchor.web3.Keypair.generate();
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    await expectError(
+      program.methods
+        .acceptAuthority()
+        .accountsPartial({ counter, authority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc(),
+      "TransferNotReady"
+    );
+    const account = await program.account.counter.fetch(counter);
+    assert.ok(account.authority.equals(authority));
+  });
+
+  it("only lets the pending authority accept, until the transfer is cancelled", async () => {
+    const counter = await createCounter();
+    const newAuthority = anchor.web3.Keypair.generate();
+    const intruder = anchor.web3.Keypair.generate();
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    await expectError(
+      program.methods
+        .acceptAuthority()
+        .accountsPartial({ counter, authority: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    await program.methods.cancelPendingAuthority().accountsPartial({ counter, authority }).rpc();
+    assert.isNull((await program.account.counter.fetch(counter)).pendingAuthority);
+    await expectError(
+      program.methods
+        .acceptAuthority()
+        .accountsPartial({ counter, authority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc(),
+      "Unauthorized"
+    );
+    await expectError(
+      program.methods.cancelPendingAuthority().accountsPartial({ counter, authority }).rpc(),
+      "NoPendingAuthority"
+    );
+  });
+
+  it("rejects transferring authority to the default pubkey", async () => {
+    const counter = await createCounter();
+
+    await expectError(
+      program.methods
+        .proposeAuthority(anchor.web3.PublicKey.default)
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "InvalidAuthority"
+    );
+  });
+
+  it("emits CountChanged with the applied delta", async () => {
+    const counter = await createCounter();
+    const events: { oldCount: anchor.BN; newCount: anchor.BN; delta: anchor.BN }[] = [];
+    const listener = program.addEventListener("countChanged", (event) => {
+      events.push(event);
+    });
+
+    try {
+      await program.methods
+        .increment(new anchor.BN(5))
+        .accountsPartial({ counter, authority })
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .decrement(new anchor.BN(2))
+        .accountsPartial({ counter, authority })
+        .rpc({ commitment: "confirmed" });
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    } finally {
+      await program.removeEventListener(listener);
+    }
+
+    assert.equal(events.length, 2);
+    assert.equal(events[0].oldCount.toNumber(), 0);
+    assert.equal(events[0].newCount.toNumber(), 5);
+    assert.equal(events[0].delta.toNumber(), 5);
+    assert.equal(events[1].oldCount.toNumber(), 5);
+    assert.equal(events[1].newCount.toNumber(), 3);
+    assert.equal(events[1].delta.toNumber(), -2);
+  });
+
+  it("previews an increment through simulation without changing the count", async () => {
+    const client = {{PROGRAM_NAME_CAMEL}}Client(program, authority);
+    const counter = await createCounter(I64_MIN, new anchor.BN(10));
+    await program.methods
+      .increment(new anchor.BN(4))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    const preview = await client.simulatePreview(counter, 5);
+    assert.ok(preview.counter.equals(counter));
+    assert.equal(preview.count.toNumber(), 4);
+    assert.equal(preview.projectedCount.toNumber(), 9);
+    assert.equal(preview.delta.toNumber(), 5);
+    assert.equal(await fetchCount(counter), 4);
+
+    // Simulation fails with the same errors increment would hit.
+    await expectSimulationError(client.preview(counter, 7).simulate(), "OutOfBounds");
+    assert.equal(await fetchCount(counter), 4);
+  });
+
+  it("rejects bounds where min exceeds max", async () => {
+    const label = nextLabel();
+
+    await expectError(
+      program.methods
+        .initialize(label, new anchor.BN(10), new anchor.BN(-10), false, new anchor.BN(0))
+        .accountsPartial({ counter: counterPda(label), authority })
+        .rpc(),
+      "InvalidBounds"
+    );
+  });
+
+  it("keeps the count within [min, max]", async () => {
+    const counter = await createCounter(new anchor.BN(-2), new anchor.BN(3));
+
+    await program.methods
+      .increment(new anchor.BN(3))
+      .accountsPartial({ counter, authority })
+      .rpc();
+    assert.equal(await fetchCount(counter), 3);
+
+    await expectError(
+      program.methods
+        .increment(new anchor.BN(1))
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "OutOfBounds"
+    );
+    await expectError(
+      program.methods
+        .decrement(new anchor.BN(6))
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "OutOfBounds"
+    );
+    assert.equal(await fetchCount(counter), 3);
+  });
+
+  it("derives independent counters per label", async () => {
+    const first = await createCounter();
+    const second = await createCounter();
+    assert.ok(!first.equals(second));
+
+    await program.methods
+      .increment(new anchor.BN(4))
+      .accountsPartial({ counter: first, authority })
+      .rpc();
+
+    assert.equal(await fetchCount(first), 4);
+    assert.equal(await fetchCount(second), 0);
+    const account = await program.account.counter.fetch(second);
+    assert.equal(account.label, `counter-${labelSeq}`);
+  });
+
+  it("closes the counter and refunds rent to the authority", async () => {
+    const counter = await createCounter();
+
+    await program.methods
+      .closeCounter()
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(counter);
+    assert.isNull(info);
+  });
+
+  it("rejects closing a counter from an unauthorized signer", async () => {
+    const counter = await createCounter();
+    const intruder = anchor.web3.Keypair.generate();
+
+    await expectError(
+      program.methods
+        .closeCounter()
+        .accountsPartial({ counter, authority: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("clamps decrement at zero in saturating mode", async () => {
+    const counter = await createCounter(I64_MIN, I64_MAX, true);
+    await program.methods
+      .increment(new anchor.BN(2))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    await program.methods
+      .decrement(new anchor.BN(5))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    assert.equal(await fetchCount(counter), 0);
+  });
+
+  it("clamps decrement at the min bound in saturating mode", async () => {
+    const counter = await createCounter(new anchor.BN(-3), I64_MAX, true);
+
+    await program.methods
+      .decrement(new anchor.BN(10))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    assert.equal(await fetchCount(counter), -3);
+  });
+
+  it("rejects decrement past the min bound when not saturating", async () => {
+    const counter = await createCounter(new anchor.BN(-3), I64_MAX, false);
+
+    await expectError(
+      program.methods
+        .decrement(new anchor.BN(4))
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "OutOfBounds"
+    );
+    assert.equal(await fetchCount(counter), 0);
+  });
+
+  it("still reports underflow when not saturating", async () => {
+    const counter = await createCounter();
+    await program.methods
+      .setCount(I64_MIN)
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    await expectError(
+      program.methods
+        .decrement(new anchor.BN(1))
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "Overflow"
+    );
+  });
+
+  it("lets delegates update but not transfer ownership", async () => {
+    const counter = await createCounter();
+    const delegate = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .addDelegate(delegate.publicKey)
+      .accountsPartial({ counter, authority })
+      .rpc();
+    await program.methods
+      .increment(new anchor.BN(3))
+      .accountsPartial({ counter, authority: delegate.publicKey })
+      .signers([delegate])
+      .rpc();
+    assert.equal(await fetchCount(counter), 3);
+
+    await expectError(
+      program.methods
+        .proposeAuthority(delegate.publicKey)
+        .accountsPartial({ counter, authority: delegate.publicKey })
+        .signers([delegate])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    await program.methods
+      .removeDelegate(delegate.publicKey)
+      .accountsPartial({ counter, authority })
+      .rpc();
+    await expectError(
+      program.methods
+        .decrement(new anchor.BN(1))
+        .accountsPartial({ counter, authority: delegate.publicKey })
+        .signers([delegate])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("caps the delegate list", async () => {
+    const counter = await createCounter();
+    for (let i = 0; i < 5; i += 1) {
+      await program.methods
+        .addDelegate(anchor.web3.Keypair.generate().publicKey)
+        .accountsPartial({ counter, authority })
+        .rpc();
+    }
+
+    await expectError(
+      program.methods
+        .addDelegate(anchor.web3.Keypair.generate().publicKey)
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "TooManyDelegates"
+    );
+  });
+
+  it("moves last_updated forward after an increment", async () => {
+    const counter = await createCounter();
+    const before = (await program.account.counter.fetch(counter)).lastUpdated.toNumber();
+    assert.isAbove(before, 0);
+
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await program.methods
+      .increment(new anchor.BN(1))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    const after = (await program.account.counter.fetch(counter)).lastUpdated.toNumber();
+    assert.isAbove(after, before);
+  });
+
+  it("applies a batch of increments in one call", async () => {
+    const counter = await createCounter();
+
+    await program.methods
+      .incrementMany([1, 2, 3, -4].map((amount) => new anchor.BN(amount)))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    assert.equal(await fetchCount(counter), 2);
+  });
+
+  it("leaves the count unchanged when a batch step overflows", async () => {
+    const counter = await createCounter();
+    await program.methods
+      .setCount(new anchor.BN(10))
+      .accountsPartial({ counter, authority })
+      .rpc();
+
+    await expectError(
+      program.methods
+        .incrementMany([new anchor.BN(1), I64_MAX])
+        .accountsPartial({ counter, authority })
+        .rpc(),
+      "Overflow"
+    );
+    assert.equal(await fetchCount(counter), 10);
+  });
+
+  it("rejects batches above the cap", async () => {
+    const counter = await createCounter();
+    const amounts = Array.from({ length: 33 }, () => new anchor.BN(1));
+
+    await expectError(
+      program.methods.incrementMany(amounts).accountsPartial({ counter, authority }).rpc(),
+      "BatchTooLarge"
+    );
+  });
+
+  it("blocks every count change while paused", async () => {
+    const counter = await createCounter();
+    await program.methods.setPaused(true).accountsPartial({ counter, authority }).rpc();
+    assert.isTrue((await program.account.counter.fetch(counter)).paused);
+
+    const one = new anchor.BN(1);
+    const accounts = { counter, authority };
+    await expectError(program.methods.increment(one).accountsPartial(accounts).rpc(), "Paused");
+    await expectError(
+      program.methods.incrementMany([one]).accountsPartial(accounts).rpc(),
+      "Paused"
+    );
+    await expectError(program.methods.decrement(one).accountsPartial(accounts).rpc(), "Paused");
+    await expectError(program.methods.setCount(one).accountsPartial(accounts).rpc(), "Paused");
+    await expectError(program.methods.reset().accountsPartial(accounts).rpc(), "Paused");
+    assert.equal(await fetchCount(counter), 0);
+
+    await program.methods.setPaused(false).accountsPartial({ counter, authority }).rpc();
+    await program.methods.increment(one).accountsPartial({ counter, authority }).rpc();
+    assert.equal(await fetchCount(counter), 1);
+  });
+
+  it("still transfers authority and closes while paused", async () => {
+    const counter = await createCounter();
+    const newAuthority = anchor.web3.Keypair.generate();
+    await program.methods.setPaused(true).accountsPartial({ counter, authority }).rpc();
+
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accountsPartial({ counter, authority })
+      .rpc();
+    await program.methods
+      .acceptAuthority()
+      .accountsPartial({ counter, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    await program.methods
+      .closeCounter()
+      .accountsPartial({ counter, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(counter));
+  });
+
+  it("rejects set_paused from an unauthorized signer", async () => {
+    const counter = await createCounter();
+    const intruder = anchor.web3.Keypair.generate();
+
+    await expectError(
+      program.methods
+        .setPaused(true)
+        .accountsPartial({ counter, authority: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("keeps the latest changes in the history ring buffer", async () => {
+    const client = {{PROGRAM_NAME_CAMEL}}Client(program, authority, { history: true });
+    const label = nextLabel();
+    const counter = client.counterAddress(label);
+    await client.initialize(label).rpc();
+    await client.initHistory(counter).rpc();
+
+    // 18 changes overflow the 16-entry buffer, dropping the first two.
+    for (let i = 1; i <= 17; i += 1) {
+      await client.increment(counter, i).rpc();
+    }
+    await client.setCount(counter, 5).rpc();
+
+    const entries = await client.fetchHistory(counter);
+    assert.equal(entries.length, 16);
+    assert.deepEqual(
+      entries.map((entry) => entry.delta.toNumber()),
+      [...Array.from({ length: 15 }, (_, i) => i + 3), 5 - 153]
+    );
+    assert.equal(entries[14].newCount.toNumber(), 153);
+    assert.equal(entries[15].newCount.toNumber(), 5);
+    assert.isAtLeast(entries[15].timestamp.toNumber(), entries[0].timestamp.toNumber());
+
+    await client.closeCounter(counter).rpc();
+    assert.isNull(await provider.connection.getAccountInfo(client.historyAddress(counter)));
+  });
+
+  it("rejects a history account that belongs to another counter", async () => {
+    const client = {{PROGRAM_NAME_CAMEL}}Client(program, authority);
+    const first = await createCounter();
+    const second = await createCounter();
+    await client.initHistory(first).rpc();
+
+    await expectError(
+      program.methods
+        .increment(new anchor.BN(1))
+        .accountsPartial({ counter: second, authority, history: client.historyAddress(first) })
+        .rpc(),
+      "HistoryMismatch"
+    );
+  });
+});
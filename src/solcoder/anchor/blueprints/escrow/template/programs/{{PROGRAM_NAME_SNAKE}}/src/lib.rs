@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
 
 declare_id!("{{PROGRAM_ID}}");
 
@@ -6,31 +8,809 @@ declare_id!("{{PROGRAM_ID}}");
 pub mod {{PROGRAM_NAME_SNAKE}} {
     use super::*;
 
-    pub fn init_escrow(_ctx: Context<InitEscrow>, _amount: u64) -> Result<()> {
+    /// Pass a `taker` to restrict `withdraw` to that key; `None` lets anyone take.
+    /// `deadline` is a unix timestamp after which `withdraw` stops and anyone may
+    /// `reclaim_expired` the funds back to the maker.
+    /// `withdraw` sends `fee_bps` of the vault to a token account owned by
+    /// `fee_destination`; pass 0 to disable the fee. Passing `swap` turns the
+    /// escrow into a two-sided swap: the taker must pay `swap.amount` of
+    /// `swap.mint` to the maker in the same `withdraw`. An `arbiter` may also sign
+    /// `withdraw` or `cancel` to settle disputes; `None` disables that path.
+    pub fn init_escrow(
+        ctx: Context<InitEscrow>,
+        amount: u64,
+        taker: Option<Pubkey>,
+        deadline: i64,
+        fee_bps: u16,
+        fee_destination: Pubkey,
+        swap: Option<SwapTerms>,
+        arbiter: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidAmount);
+        require!(
+            deadline > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidDeadline
+        );
+        require!(fee_bps <= Escrow::MAX_FEE_BPS, EscrowError::InvalidFee);
+        if let Some(terms) = &swap {
+            require!(terms.amount > 0, EscrowError::InvalidAmount);
+        }
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.maker = ctx.accounts.maker.key();
+        escrow.mint = ctx.accounts.mint.key();
+        escrow.amount = amount;
+        escrow.funded_amount = 0;
+        escrow.remaining_amount = 0;
+        escrow.taker = taker;
+        escrow.deadline = deadline;
+        escrow.fee_bps = fee_bps;
+        escrow.fee_destination = fee_destination;
+        escrow.swap = swap;
+        escrow.arbiter = arbiter;
+        escrow.bump = ctx.bumps.escrow;
+        emit!(EscrowInitialized {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker,
+            mint: escrow.mint,
+            amount,
+        });
         Ok(())
     }
 
-    pub fn deposit(_ctx: Context<Deposit>) -> Result<()> {
+    /// Moves `deposit_amount` tokens from the maker into the vault. Call it as
+    /// many times as needed until `funded_amount` reaches `amount`; a deposit
+    /// that would overshoot `amount` is rejected.
+    pub fn deposit(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
+        require!(deposit_amount > 0, EscrowError::InvalidAmount);
+        let escrow = &mut ctx.accounts.escrow;
+        require!(
+            escrow.funded_amount < escrow.amount,
+            EscrowError::AlreadyFunded
+        );
+        let funded_amount = escrow
+            .funded_amount
+            .checked_add(deposit_amount)
+            .filter(|total| *total <= escrow.amount)
+            .ok_or(EscrowError::InvalidAmount)?;
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.maker_token.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                },
+            ),
+            deposit_amount,
+        )?;
+        escrow.funded_amount = funded_amount;
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_add(deposit_amount)
+            .ok_or(EscrowError::InvalidAmount)?;
+        emit!(EscrowFunded {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker: escrow.taker,
+            mint: escrow.mint,
+            amount: deposit_amount,
+        });
         Ok(())
     }
 
-    pub fn withdraw(_ctx: Context<Withdraw>) -> Result<()> {
+    /// Releases `take_amount` of the deposited tokens to the taker minus the
+    /// configured fee. Once the escrow is fully funded and nothing remains, the
+    /// vault and escrow close, refunding rent to the maker. For swaps, the taker
+    /// pays the same share of `swap.amount` (rounded up, so partial fills never
+    /// underpay the maker) in the same instruction, so either both legs settle or
+    /// neither does.
+    pub fn withdraw(ctx: Context<Withdraw>, take_amount: u64) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        if !arbiter_signed(escrow.arbiter, &ctx.accounts.arbiter)? {
+            require!(ctx.accounts.taker.is_signer, EscrowError::UnauthorizedTaker);
+        }
+        if let Some(taker) = escrow.taker {
+            require_keys_eq!(
+                taker,
+                ctx.accounts.taker.key(),
+                EscrowError::UnauthorizedTaker
+            );
+        }
+        require!(
+            Clock::get()?.unix_timestamp <= escrow.deadline,
+            EscrowError::Expired
+        );
+        require!(escrow.remaining_amount > 0, EscrowError::NotFunded);
+        require!(take_amount > 0, EscrowError::InvalidAmount);
+        require!(
+            take_amount <= escrow.remaining_amount,
+            EscrowError::InsufficientRemaining
+        );
+        let fee = escrow.fee_for(take_amount)?;
+        let payout = take_amount
+            .checked_sub(fee)
+            .ok_or(EscrowError::InvalidFee)?;
+        let token_program = &ctx.accounts.token_program;
+        if let Some(terms) = escrow.swap {
+            let (Some(payment), Some(receive)) =
+                (&ctx.accounts.taker_payment, &ctx.accounts.maker_receive)
+            else {
+                return err!(EscrowError::MissingSwapAccounts);
+            };
+            require_keys_eq!(payment.mint, terms.mint, EscrowError::InvalidSwapAccount);
+            require_keys_eq!(
+                payment.owner,
+                ctx.accounts.taker.key(),
+                EscrowError::InvalidSwapAccount
+            );
+            require_keys_eq!(receive.mint, terms.mint, EscrowError::InvalidSwapAccount);
+            require_keys_eq!(receive.owner, escrow.maker, EscrowError::InvalidSwapAccount);
+            let owed = escrow.swap_share(terms, take_amount)?;
+            require!(payment.amount >= owed, EscrowError::InsufficientPayment);
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: payment.to_account_info(),
+                        to: receive.to_account_info(),
+                        authority: ctx.accounts.taker.to_account_info(),
+                    },
+                ),
+                owed,
+            )?;
+        }
+        let vault = &ctx.accounts.vault;
+        transfer_from_vault(
+            token_program,
+            vault,
+            ctx.accounts.fee_token.to_account_info(),
+            escrow,
+            fee,
+        )?;
+        transfer_from_vault(
+            token_program,
+            vault,
+            ctx.accounts.taker_token.to_account_info(),
+            escrow,
+            payout,
+        )?;
+        emit!(EscrowWithdrawn {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker: ctx.accounts.taker.key(),
+            mint: escrow.mint,
+            amount: payout,
+            fee,
+        });
+        let maker = ctx.accounts.maker.to_account_info();
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_sub(take_amount)
+            .ok_or(EscrowError::InsufficientRemaining)?;
+        if escrow.remaining_amount == 0 && escrow.funded_amount == escrow.amount {
+            close_vault(token_program, vault, escrow, maker.clone())?;
+            escrow.close(maker)?;
+        }
         Ok(())
     }
 
-    pub fn cancel(_ctx: Context<Cancel>) -> Result<()> {
+    /// Refunds the vault to the maker and closes both accounts. An unfunded escrow
+    /// can also be cancelled to reclaim its rent; once `withdraw` has run the
+    /// accounts are gone, so a late cancel fails account validation.
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.escrow.maker,
+            ctx.accounts.maker.key(),
+            EscrowError::Unauthorized
+        );
+        if !arbiter_signed(ctx.accounts.escrow.arbiter, &ctx.accounts.arbiter)? {
+            require!(ctx.accounts.maker.is_signer, EscrowError::Unauthorized);
+        }
+        refund_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.maker_token.to_account_info(),
+            &ctx.accounts.escrow,
+            ctx.accounts.maker.to_account_info(),
+        )
+    }
+
+    /// Refunds the maker and closes both accounts once the deadline has passed.
+    /// Anyone may call it, so a keeper can unstick an escrow nobody took; the
+    /// tokens and rent still only go to the maker.
+    pub fn reclaim_expired(ctx: Context<ReclaimExpired>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        require!(
+            Clock::get()?.unix_timestamp > escrow.deadline,
+            EscrowError::NotExpired
+        );
+        let vault = &ctx.accounts.vault;
+        let amount = vault.amount;
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            vault,
+            ctx.accounts.maker_token.to_account_info(),
+            escrow,
+            amount,
+        )?;
+        emit!(EscrowExpired {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker: escrow.taker,
+            mint: escrow.mint,
+            amount,
+            caller: ctx.accounts.caller.key(),
+        });
+        close_vault(
+            &ctx.accounts.token_program,
+            vault,
+            escrow,
+            ctx.accounts.maker.to_account_info(),
+        )
+    }
+
+    /// Native SOL counterpart of `init_escrow`; `amount` is in lamports and the
+    /// escrow PDA itself holds the deposit, so there is no mint or vault.
+    pub fn init_sol_escrow(
+        ctx: Context<InitSolEscrow>,
+        amount: u64,
+        taker: Option<Pubkey>,
+        deadline: i64,
+    ) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidAmount);
+        require!(
+            deadline > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidDeadline
+        );
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.maker = ctx.accounts.maker.key();
+        escrow.amount = amount;
+        escrow.taker = taker;
+        escrow.deadline = deadline;
+        escrow.funded = false;
+        escrow.bump = ctx.bumps.escrow;
+        emit!(EscrowInitialized {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker,
+            mint: Pubkey::default(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// The maker is a system account, so deposits are a plain system transfer.
+    pub fn deposit_sol(ctx: Context<DepositSol>) -> Result<()> {
+        require!(!ctx.accounts.escrow.funded, EscrowError::AlreadyFunded);
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.maker.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            ctx.accounts.escrow.amount,
+        )?;
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.funded = true;
+        emit!(EscrowFunded {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker: escrow.taker,
+            mint: Pubkey::default(),
+            amount: escrow.amount,
+        });
+        Ok(())
+    }
+
+    /// The escrow PDA is owned by this program, not the system program, so a system
+    /// transfer out of it would fail; the lamports are moved with
+    /// `try_borrow_mut_lamports` instead. The remaining rent goes back to the maker
+    /// when the account closes.
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        if let Some(taker) = escrow.taker {
+            require_keys_eq!(
+                taker,
+                ctx.accounts.taker.key(),
+                EscrowError::UnauthorizedTaker
+            );
+        }
+        require!(
+            Clock::get()?.unix_timestamp <= escrow.deadline,
+            EscrowError::Expired
+        );
+        require!(escrow.funded, EscrowError::NotFunded);
+        let amount = escrow.amount;
+        **ctx
+            .accounts
+            .escrow
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.taker.try_borrow_mut_lamports()? += amount;
+        emit!(EscrowWithdrawn {
+            escrow: ctx.accounts.escrow.key(),
+            maker: ctx.accounts.escrow.maker,
+            taker: ctx.accounts.taker.key(),
+            mint: Pubkey::default(),
+            amount,
+            fee: 0,
+        });
+        Ok(())
+    }
+
+    /// Closing the escrow returns the deposit and rent to the maker in one step.
+    pub fn cancel_sol(ctx: Context<CancelSol>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        require_keys_eq!(
+            escrow.maker,
+            ctx.accounts.maker.key(),
+            EscrowError::Unauthorized
+        );
+        emit!(EscrowCancelled {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker: escrow.taker,
+            mint: Pubkey::default(),
+            amount: if escrow.funded { escrow.amount } else { 0 },
+        });
         Ok(())
     }
 }
 
 #[derive(Accounts)]
-pub struct InitEscrow {}
+pub struct InitEscrow<'info> {
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Escrow::SIZE,
+        seeds = [b"escrow", maker.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// Token account owned by the escrow PDA that holds the maker's deposit.
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"vault", escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
 
 #[derive(Accounts)]
-pub struct Deposit {}
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = maker
+    )]
+    pub maker_token: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vault", escrow.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub maker: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
 
 #[derive(Accounts)]
-pub struct Withdraw {}
+pub struct Withdraw<'info> {
+    /// Closed by the handler once the last tokens are taken, not on every call.
+    #[account(
+        mut,
+        seeds = [b"escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"vault", escrow.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = taker
+    )]
+    pub taker_token: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = escrow.fee_destination
+    )]
+    pub fee_token: Account<'info, TokenAccount>,
+    /// Swap only: the taker's account for `swap.mint`, debited by `swap.amount`.
+    #[account(mut)]
+    pub taker_payment: Option<Account<'info, TokenAccount>>,
+    /// Swap only: the maker's account for `swap.mint`.
+    #[account(mut)]
+    pub maker_receive: Option<Account<'info, TokenAccount>>,
+    /// Must sign unless the arbiter releases the escrow. Swaps always need the
+    /// taker's signature because the payment leg is debited from their account.
+    pub taker: SystemAccount<'info>,
+    pub arbiter: Option<Signer<'info>>,
+    /// Receives the vault and escrow rent; bound to the escrow by its seeds.
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
 
 #[derive(Accounts)]
-pub struct Cancel {}
+pub struct Cancel<'info> {
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"vault", escrow.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = maker
+    )]
+    pub maker_token: Account<'info, TokenAccount>,
+    /// Must sign unless the arbiter refunds the escrow.
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub arbiter: Option<Signer<'info>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpired<'info> {
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"vault", escrow.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = maker
+    )]
+    pub maker_token: Account<'info, TokenAccount>,
+    /// Receives the refund and the rent; bound to the escrow by its seeds.
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    /// Whoever triggers the cleanup; needs no relation to the escrow.
+    pub caller: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitSolEscrow<'info> {
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + SolEscrow::SIZE,
+        seeds = [b"sol_escrow", maker.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"sol_escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    /// Receives the escrow rent; bound to the escrow by its seeds.
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSol<'info> {
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"sol_escrow", maker.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+    #[account(mut)]
+    pub maker: Signer<'info>,
+}
+
+/// Returns whether the escrow's arbiter signed. Supplying an arbiter that does
+/// not match the configured one, or any arbiter when none was configured, fails.
+fn arbiter_signed(expected: Option<Pubkey>, arbiter: &Option<Signer>) -> Result<bool> {
+    let Some(signer) = arbiter else {
+        return Ok(false);
+    };
+    require!(
+        expected == Some(signer.key()),
+        EscrowError::UnauthorizedArbiter
+    );
+    Ok(true)
+}
+
+/// Sends `amount` from the vault to `destination` with the escrow PDA as signer.
+/// Zero amounts are skipped so callers need not special-case empty shares.
+fn transfer_from_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    escrow: &Account<'info, Escrow>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let bump = [escrow.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow", escrow.maker.as_ref(), &bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: destination,
+                authority: escrow.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+/// Closes the (now empty) vault and sends its rent to `maker`.
+fn close_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    escrow: &Account<'info, Escrow>,
+    maker: AccountInfo<'info>,
+) -> Result<()> {
+    let bump = [escrow.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow", escrow.maker.as_ref(), &bump]];
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault.to_account_info(),
+            destination: maker,
+            authority: escrow.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+/// Refunds the whole vault to the maker and closes it, reporting `EscrowCancelled`.
+fn refund_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    maker_token: AccountInfo<'info>,
+    escrow: &Account<'info, Escrow>,
+    maker: AccountInfo<'info>,
+) -> Result<()> {
+    transfer_from_vault(token_program, vault, maker_token, escrow, vault.amount)?;
+    emit!(EscrowCancelled {
+        escrow: escrow.key(),
+        maker: escrow.maker,
+        taker: escrow.taker,
+        mint: escrow.mint,
+        amount: vault.amount,
+    });
+    close_vault(token_program, vault, escrow, maker)
+}
+
+#[account]
+pub struct Escrow {
+    pub maker: Pubkey,
+    pub mint: Pubkey,
+    /// Token amount (in base units) the maker locks in the vault.
+    pub amount: u64,
+    /// Total deposited so far; `deposit` may be called until it equals `amount`.
+    pub funded_amount: u64,
+    /// Deposited tokens still available to `withdraw`.
+    pub remaining_amount: u64,
+    /// The only key allowed to `withdraw`, or `None` for an open escrow.
+    pub taker: Option<Pubkey>,
+    pub bump: u8,
+    /// Unix timestamp; `withdraw` is rejected once the clock passes it.
+    pub deadline: i64,
+    /// Share of the vault, in basis points, paid to `fee_destination` on `withdraw`.
+    pub fee_bps: u16,
+    /// Wallet whose token account receives the fee.
+    pub fee_destination: Pubkey,
+    /// What the taker owes the maker, or `None` for a one-sided escrow.
+    pub swap: Option<SwapTerms>,
+    /// Third party allowed to release or refund the escrow.
+    pub arbiter: Option<Pubkey>,
+}
+
+impl Escrow {
+    pub const MAX_FEE_BPS: u16 = 10_000;
+    pub const SIZE: usize =
+        32 + 32 + 8 + 8 + 8 + (1 + 32) + 1 + 8 + 2 + 32 + (1 + SwapTerms::SIZE) + (1 + 32);
+
+    /// `total * fee_bps / 10_000`, rounded down, computed in u128 so the product
+    /// cannot overflow.
+    pub fn fee_for(&self, total: u64) -> Result<u64> {
+        let fee = (total as u128)
+            .checked_mul(self.fee_bps as u128)
+            .and_then(|product| product.checked_div(Self::MAX_FEE_BPS as u128))
+            .ok_or(EscrowError::InvalidFee)?;
+        u64::try_from(fee).map_err(|_| EscrowError::InvalidFee.into())
+    }
+
+    /// What the taker owes for `take_amount`: `terms.amount * take_amount / amount`,
+    /// rounded up, so a full fill pays at least `terms.amount`.
+    pub fn swap_share(&self, terms: SwapTerms, take_amount: u64) -> Result<u64> {
+        let share = (terms.amount as u128)
+            .checked_mul(take_amount as u128)
+            .and_then(|product| product.checked_add(self.amount as u128 - 1))
+            .and_then(|product| product.checked_div(self.amount as u128))
+            .ok_or(EscrowError::InvalidAmount)?;
+        u64::try_from(share).map_err(|_| EscrowError::InvalidAmount.into())
+    }
+}
+
+/// The counter-leg of a two-sided escrow.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SwapTerms {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl SwapTerms {
+    pub const SIZE: usize = 32 + 8;
+}
+
+#[account]
+pub struct SolEscrow {
+    pub maker: Pubkey,
+    /// Lamports the maker locks in the escrow PDA, on top of its rent.
+    pub amount: u64,
+    pub taker: Option<Pubkey>,
+    pub deadline: i64,
+    /// The PDA balance always includes rent, so funding is tracked explicitly.
+    pub funded: bool,
+    pub bump: u8,
+}
+
+impl SolEscrow {
+    pub const SIZE: usize = 32 + 8 + (1 + 32) + 8 + 1 + 1;
+}
+
+// Lifecycle events for indexers. SOL escrows report `mint` as `Pubkey::default()`
+// and `amount` in lamports.
+
+#[event]
+pub struct EscrowInitialized {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowFunded {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// `amount` is what the taker received; `fee` went to the fee destination.
+#[event]
+pub struct EscrowWithdrawn {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+/// Emitted by `cancel` and `cancel_sol`; `amount` is what the maker got back
+/// (zero for an unfunded escrow).
+#[event]
+pub struct EscrowCancelled {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted by `reclaim_expired`; `amount` went back to the maker and `caller`
+/// is whoever submitted the cleanup.
+#[event]
+pub struct EscrowExpired {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub caller: Pubkey,
+}
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Escrow amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("The escrow vault has already been funded.")]
+    AlreadyFunded,
+    #[msg("The escrow vault has not been funded.")]
+    NotFunded,
+    #[msg("Signer is not the taker this escrow was created for.")]
+    UnauthorizedTaker,
+    #[msg("Signer is not the escrow maker.")]
+    Unauthorized,
+    #[msg("The escrow deadline must be in the future.")]
+    InvalidDeadline,
+    #[msg("The escrow deadline has passed.")]
+    Expired,
+    #[msg("The escrow deadline has not passed yet.")]
+    NotExpired,
+    #[msg("Fee basis points must not exceed 10_000.")]
+    InvalidFee,
+    #[msg("Swap escrows need the taker payment and maker receive accounts.")]
+    MissingSwapAccounts,
+    #[msg("A swap token account has the wrong mint or owner.")]
+    InvalidSwapAccount,
+    #[msg("The taker's payment account cannot cover the swap amount.")]
+    InsufficientPayment,
+    #[msg("Signer is not the arbiter for this escrow.")]
+    UnauthorizedArbiter,
+    #[msg("The escrow has fewer tokens remaining than requested.")]
+    InsufficientRemaining,
+}
@@ -1,5 +0,0 @@
-import test from "ava";
-
-test("{{PROGRAM_NAME_TITLE}} escrow scaffold compiles", (t) => {
-  t.pass();
-});
@@ -1,8 +0,0 @@
-import { describe, it } from "mocha";
-
-describe("escrow template", () => {
-  it("compiles and can deploy", () => {
-    // Placeholder test to ensure workspace structure is valid.
-  });
-});
-
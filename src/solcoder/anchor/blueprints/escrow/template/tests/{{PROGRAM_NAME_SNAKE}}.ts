@@ -0,0 +1,721 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  // Receives withdraw fees for every escrow unless a test overrides it.
+  const feeWallet = anchor.web3.Keypair.generate();
+
+  let mint: anchor.web3.PublicKey;
+  let feeToken: anchor.web3.PublicKey;
+
+  before(async () => {
+    mint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+    feeToken = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint,
+      feeWallet.publicKey
+    );
+  });
+
+  // Each maker owns a single escrow PDA, so every test uses a fresh funded maker.
+  async function newMaker(): Promise<anchor.web3.Keypair> {
+    const maker = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(maker.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    return maker;
+  }
+
+  function escrowPda(maker: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function solEscrowPda(maker: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_escrow"), maker.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function vaultPda(escrow: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), escrow.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  async function fundedTokenAccount(
+    owner: anchor.web3.PublicKey,
+    amount: number,
+    tokenMint: anchor.web3.PublicKey = mint
+  ): Promise<anchor.web3.PublicKey> {
+    const account = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      tokenMint,
+      owner
+    );
+    if (amount > 0) {
+      await mintTo(provider.connection, payer, tokenMint, account, payer, amount);
+    }
+    return account;
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    return Number((await getAccount(provider.connection, account)).amount);
+  }
+
+  // Deadlines are checked against the validator clock, which can drift from ours.
+  async function chainTime(): Promise<number> {
+    const slot = await provider.connection.getSlot("confirmed");
+    return (await provider.connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+  }
+
+  function sleep(ms: number): Promise<void> {
+    return new Promise((resolve) => setTimeout(resolve, ms));
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  const ONE_HOUR = 60 * 60;
+
+  type EscrowOptions = {
+    taker?: anchor.web3.PublicKey;
+    deadline?: number;
+    feeBps?: number;
+    swap?: { mint: anchor.web3.PublicKey; amount: anchor.BN };
+    arbiter?: anchor.web3.PublicKey;
+  };
+
+  function initEscrow(maker: anchor.web3.Keypair, amount: anchor.BN, options: EscrowOptions = {}) {
+    // An hour of slack makes clock drift irrelevant for the default deadline.
+    const deadline = options.deadline ?? Math.floor(Date.now() / 1000) + ONE_HOUR;
+    return program.methods
+      .initEscrow(
+        amount,
+        options.taker ?? null,
+        new anchor.BN(deadline),
+        options.feeBps ?? 0,
+        feeWallet.publicKey,
+        options.swap ?? null,
+        options.arbiter ?? null
+      )
+      .accountsPartial({
+        escrow: escrowPda(maker.publicKey),
+        vault: vaultPda(escrowPda(maker.publicKey)),
+        mint,
+        maker: maker.publicKey,
+      })
+      .signers([maker]);
+  }
+
+  function deposit(
+    maker: anchor.web3.Keypair,
+    makerToken: anchor.web3.PublicKey,
+    amount = 1_000
+  ) {
+    const escrow = escrowPda(maker.publicKey);
+    return program.methods
+      .deposit(new anchor.BN(amount))
+      .accountsPartial({ escrow, makerToken, vault: vaultPda(escrow), maker: maker.publicKey })
+      .signers([maker]);
+  }
+
+  type SwapAccounts = {
+    takerPayment: anchor.web3.PublicKey;
+    makerReceive: anchor.web3.PublicKey;
+  };
+
+  function withdraw(
+    maker: anchor.web3.PublicKey,
+    taker: anchor.web3.Keypair,
+    takerToken: anchor.web3.PublicKey,
+    swap: SwapAccounts | null = null,
+    takeAmount = 1_000
+  ) {
+    const escrow = escrowPda(maker);
+    return program.methods
+      .withdraw(new anchor.BN(takeAmount))
+      .accountsPartial({
+        escrow,
+        vault: vaultPda(escrow),
+        takerToken,
+        feeToken,
+        takerPayment: swap?.takerPayment ?? null,
+        makerReceive: swap?.makerReceive ?? null,
+        taker: taker.publicKey,
+        arbiter: null,
+        maker,
+      })
+      .signers([taker]);
+  }
+
+  function cancel(
+    maker: anchor.web3.PublicKey,
+    signer: anchor.web3.Keypair,
+    makerToken: anchor.web3.PublicKey
+  ) {
+    const escrow = escrowPda(maker);
+    return program.methods
+      .cancel()
+      .accountsPartial({
+        escrow,
+        vault: vaultPda(escrow),
+        makerToken,
+        maker: signer.publicKey,
+        arbiter: null,
+      })
+      .signers([signer]);
+  }
+
+  function reclaimExpired(
+    maker: anchor.web3.PublicKey,
+    caller: anchor.web3.Keypair,
+    makerToken: anchor.web3.PublicKey
+  ) {
+    const escrow = escrowPda(maker);
+    return program.methods
+      .reclaimExpired()
+      .accountsPartial({
+        escrow,
+        vault: vaultPda(escrow),
+        makerToken,
+        maker,
+        caller: caller.publicKey,
+      })
+      .signers([caller]);
+  }
+
+  // Creates a maker with a funded escrow of `amount` tokens.
+  async function fundedEscrow(
+    amount: number,
+    options: EscrowOptions = {}
+  ): Promise<{ maker: anchor.web3.Keypair; makerToken: anchor.web3.PublicKey }> {
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, amount);
+    await initEscrow(maker, new anchor.BN(amount), options).rpc();
+    await deposit(maker, makerToken, amount).rpc();
+    return { maker, makerToken };
+  }
+
+  it("initializes an escrow", async () => {
+    const maker = await newMaker();
+    await initEscrow(maker, new anchor.BN(1_000)).rpc();
+
+    const escrow = await program.account.escrow.fetch(escrowPda(maker.publicKey));
+    assert.ok(escrow.maker.equals(maker.publicKey));
+    assert.ok(escrow.mint.equals(mint));
+    assert.equal(escrow.amount.toNumber(), 1_000);
+    assert.isNull(escrow.taker);
+  });
+
+  it("rejects a zero amount", async () => {
+    const maker = await newMaker();
+    await expectError(initEscrow(maker, new anchor.BN(0)).rpc(), "InvalidAmount");
+  });
+
+  it("deposits the escrow amount into the vault", async () => {
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 5_000);
+    await initEscrow(maker, new anchor.BN(1_000)).rpc();
+
+    await deposit(maker, makerToken).rpc();
+
+    assert.equal(await tokenBalance(vaultPda(escrowPda(maker.publicKey))), 1_000);
+    assert.equal(await tokenBalance(makerToken), 4_000);
+  });
+
+  it("rejects a second deposit", async () => {
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 5_000);
+    await initEscrow(maker, new anchor.BN(1_000)).rpc();
+    await deposit(maker, makerToken).rpc();
+
+    await expectError(deposit(maker, makerToken).rpc(), "AlreadyFunded");
+    assert.equal(await tokenBalance(makerToken), 4_000);
+  });
+
+  it("accepts the escrow amount over several deposits", async () => {
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 1_000);
+    await initEscrow(maker, new anchor.BN(1_000)).rpc();
+
+    await deposit(maker, makerToken, 400).rpc();
+    await deposit(maker, makerToken, 600).rpc();
+
+    const escrow = await program.account.escrow.fetch(escrowPda(maker.publicKey));
+    assert.equal(escrow.fundedAmount.toNumber(), 1_000);
+    assert.equal(escrow.remainingAmount.toNumber(), 1_000);
+    assert.equal(await tokenBalance(vaultPda(escrowPda(maker.publicKey))), 1_000);
+  });
+
+  it("records each deposit on the escrow account", async () => {
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 1_000);
+    await initEscrow(maker, new anchor.BN(1_000)).rpc();
+
+    await deposit(maker, makerToken, 250).rpc();
+    let escrow = await program.account.escrow.fetch(escrowPda(maker.publicKey));
+    assert.equal(escrow.fundedAmount.toNumber(), 250);
+    assert.equal(escrow.remainingAmount.toNumber(), 250);
+
+    await deposit(maker, makerToken, 300).rpc();
+    escrow = await program.account.escrow.fetch(escrowPda(maker.publicKey));
+    assert.equal(escrow.fundedAmount.toNumber(), 550);
+    assert.equal(escrow.remainingAmount.toNumber(), 550);
+    assert.equal(await tokenBalance(vaultPda(escrowPda(maker.publicKey))), 550);
+  });
+
+  it("rejects a deposit past the escrow amount", async () => {
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 5_000);
+    await initEscrow(maker, new anchor.BN(1_000)).rpc();
+    await deposit(maker, makerToken, 400).rpc();
+
+    await expectError(deposit(maker, makerToken, 700).rpc(), "InvalidAmount");
+  });
+
+  it("fills in parts and closes once nothing remains", async () => {
+    const taker = await newMaker();
+    const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 1_000);
+    await initEscrow(maker, new anchor.BN(1_000), { taker: taker.publicKey }).rpc();
+    await deposit(maker, makerToken, 400).rpc();
+    await deposit(maker, makerToken, 600).rpc();
+    const escrow = escrowPda(maker.publicKey);
+
+    await withdraw(maker.publicKey, taker, takerToken, null, 300).rpc();
+    const partial = await program.account.escrow.fetch(escrow);
+    assert.equal(partial.remainingAmount.toNumber(), 700);
+    assert.equal(await tokenBalance(takerToken), 300);
+
+    await expectError(
+      withdraw(maker.publicKey, taker, takerToken, null, 701).rpc(),
+      "InsufficientRemaining"
+    );
+
+    await withdraw(maker.publicKey, taker, takerToken, null, 700).rpc();
+    assert.equal(await tokenBalance(takerToken), 1_000);
+    assert.isNull(await provider.connection.getAccountInfo(vaultPda(escrow)));
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
+
+  it("releases the vault to the taker and closes the escrow", async () => {
+    const taker = await newMaker();
+    const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+    const { maker } = await fundedEscrow(1_000, { taker: taker.publicKey });
+    const escrow = escrowPda(maker.publicKey);
+    const makerLamports = await provider.connection.getBalance(maker.publicKey);
+
+    await withdraw(maker.publicKey, taker, takerToken).rpc();
+
+    assert.equal(await tokenBalance(takerToken), 1_000);
+    assert.isNull(await provider.connection.getAccountInfo(vaultPda(escrow)));
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+    assert.isAbove(await provider.connection.getBalance(maker.publicKey), makerLamports);
+  });
+
+  async function parseEvents(signature: string) {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx?.meta?.logMessages ?? []));
+  }
+
+  it("emits EscrowWithdrawn with the payout and fee", async () => {
+    const taker = await newMaker();
+    const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+    const { maker } = await fundedEscrow(1_000, { taker: taker.publicKey, feeBps: 250 });
+
+    const signature = await withdraw(maker.publicKey, taker, takerToken).rpc({
+      commitment: "confirmed",
+    });
+    const withdrawn = (await parseEvents(signature)).find(
+      (event) => event.name === "escrowWithdrawn"
+    );
+
+    assert.ok(withdrawn, "EscrowWithdrawn was not emitted");
+    assert.ok((withdrawn.data.escrow as anchor.web3.PublicKey).equals(escrowPda(maker.publicKey)));
+    assert.ok((withdrawn.data.maker as anchor.web3.PublicKey).equals(maker.publicKey));
+    assert.ok((withdrawn.data.taker as anchor.web3.PublicKey).equals(taker.publicKey));
+    assert.ok((withdrawn.data.mint as anchor.web3.PublicKey).equals(mint));
+    assert.equal((withdrawn.data.amount as anchor.BN).toNumber(), 975);
+    assert.equal((withdrawn.data.fee as anchor.BN).toNumber(), 25);
+  });
+
+  it("rejects a withdraw from a key other than the intended taker", async () => {
+    const taker = await newMaker();
+    const intruder = await newMaker();
+    const intruderToken = await fundedTokenAccount(intruder.publicKey, 0);
+    const { maker } = await fundedEscrow(1_000, { taker: taker.publicKey });
+
+    await expectError(
+      withdraw(maker.publicKey, intruder, intruderToken).rpc(),
+      "UnauthorizedTaker"
+    );
+    assert.equal(await tokenBalance(vaultPda(escrowPda(maker.publicKey))), 1_000);
+  });
+
+  it("refunds the maker on cancel", async () => {
+    const { maker, makerToken } = await fundedEscrow(1_000);
+    const escrow = escrowPda(maker.publicKey);
+    assert.equal(await tokenBalance(makerToken), 0);
+
+    await cancel(maker.publicKey, maker, makerToken).rpc();
+
+    assert.equal(await tokenBalance(makerToken), 1_000);
+    assert.isNull(await provider.connection.getAccountInfo(vaultPda(escrow)));
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
+
+  it("does not let another signer cancel someone else's escrow", async () => {
+    const { maker } = await fundedEscrow(1_000);
+    const intruder = await newMaker();
+    const intruderToken = await fundedTokenAccount(intruder.publicKey, 0);
+
+    // The escrow PDA is seeded by its maker, so Anchor rejects the accounts before
+    // the explicit maker check runs.
+    await expectError(
+      cancel(maker.publicKey, intruder, intruderToken).rpc(),
+      "ConstraintSeeds"
+    );
+    assert.equal(await tokenBalance(vaultPda(escrowPda(maker.publicKey))), 1_000);
+  });
+
+  it("cannot cancel after the taker withdrew", async () => {
+    const taker = await newMaker();
+    const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+    const { maker, makerToken } = await fundedEscrow(1_000, { taker: taker.publicKey });
+    await withdraw(maker.publicKey, taker, takerToken).rpc();
+
+    await expectError(
+      cancel(maker.publicKey, maker, makerToken).rpc(),
+      "AccountNotInitialized"
+    );
+    assert.equal(await tokenBalance(takerToken), 1_000);
+  });
+
+  it("rejects a deadline in the past", async () => {
+    const maker = await newMaker();
+    await expectError(
+      initEscrow(maker, new anchor.BN(1_000), { deadline: (await chainTime()) - 1 }).rpc(),
+      "InvalidDeadline"
+    );
+  });
+
+  it("allows a withdraw just before the deadline", async () => {
+    const taker = await newMaker();
+    const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+    const { maker } = await fundedEscrow(1_000, {
+      taker: taker.publicKey,
+      deadline: (await chainTime()) + 15,
+    });
+
+    await withdraw(maker.publicKey, taker, takerToken).rpc();
+    assert.equal(await tokenBalance(takerToken), 1_000);
+  });
+
+  // Expiry is judged by block time, so this escrow's deadline is three seconds
+  // ahead and the test polls until a block lands past it before trying either side.
+  it("rejects a withdraw just after the deadline and lets anyone reclaim it for the maker", async () => {
+    const taker = await newMaker();
+    const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+    const keeper = await newMaker();
+    const maker = await newMaker();
+    const makerToken = await fundedTokenAccount(maker.publicKey, 1_000);
+    const deadline = (await chainTime()) + 3;
+    await initEscrow(maker, new anchor.BN(1_000), { taker: taker.publicKey, deadline }).rpc();
+    await deposit(maker, makerToken).rpc();
+
+    await expectError(reclaimExpired(maker.publicKey, keeper, makerToken).rpc(), "NotExpired");
+    while ((await chainTime()) <= deadline + 1) {
+      await sleep(500);
+    }
+
+    await expectError(withdraw(maker.publicKey, taker, takerToken).rpc(), "Expired");
+    const events: {
+      maker: anchor.web3.PublicKey;
+      amount: anchor.BN;
+      caller: anchor.web3.PublicKey;
+    }[] = [];
+    const listener = program.addEventListener("escrowExpired", (event) => {
+      events.push(event);
+    });
+    try {
+      await reclaimExpired(maker.publicKey, keeper, makerToken).rpc({ commitment: "confirmed" });
+      await sleep(1000);
+    } finally {
+      await program.removeEventListener(listener);
+    }
+
+    assert.equal(await tokenBalance(makerToken), 1_000);
+    assert.equal(await tokenBalance(takerToken), 0);
+    const escrow = escrowPda(maker.publicKey);
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+    assert.isNull(await provider.connection.getAccountInfo(vaultPda(escrow)));
+    assert.equal(events.length, 1);
+    assert.ok(events[0].maker.equals(maker.publicKey));
+    assert.equal(events[0].amount.toNumber(), 1_000);
+    assert.ok(events[0].caller.equals(keeper.publicKey));
+  });
+
+  for (const [feeBps, expectedFee] of [
+    [0, 0],
+    [250, 25],
+    [10_000, 1_000],
+  ]) {
+    it(`splits a withdraw with a ${feeBps} bps fee`, async () => {
+      const taker = await newMaker();
+      const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+      const { maker } = await fundedEscrow(1_000, { taker: taker.publicKey, feeBps });
+      const feeBefore = await tokenBalance(feeToken);
+
+      await withdraw(maker.publicKey, taker, takerToken).rpc();
+
+      assert.equal((await tokenBalance(feeToken)) - feeBefore, expectedFee);
+      assert.equal(await tokenBalance(takerToken), 1_000 - expectedFee);
+    });
+  }
+
+  it("rejects a fee above 10_000 bps", async () => {
+    const maker = await newMaker();
+    await expectError(
+      initEscrow(maker, new anchor.BN(1_000), { feeBps: 10_001 }).rpc(),
+      "InvalidFee"
+    );
+  });
+
+  describe("two-sided swap", () => {
+    let mintB: anchor.web3.PublicKey;
+
+    before(async () => {
+      mintB = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+    });
+
+    async function swapEscrow(takerPays: number) {
+      const taker = await newMaker();
+      const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+      const takerPayment = await fundedTokenAccount(taker.publicKey, takerPays, mintB);
+      const { maker } = await fundedEscrow(1_000, {
+        taker: taker.publicKey,
+        swap: { mint: mintB, amount: new anchor.BN(500) },
+      });
+      const makerReceive = await fundedTokenAccount(maker.publicKey, 0, mintB);
+      return { maker, taker, takerToken, swap: { takerPayment, makerReceive } };
+    }
+
+    it("settles both legs in one withdraw", async () => {
+      const { maker, taker, takerToken, swap } = await swapEscrow(500);
+
+      await withdraw(maker.publicKey, taker, takerToken, swap).rpc();
+
+      assert.equal(await tokenBalance(takerToken), 1_000);
+      assert.equal(await tokenBalance(swap.makerReceive), 500);
+      assert.equal(await tokenBalance(swap.takerPayment), 0);
+    });
+
+    it("rejects a taker who cannot cover the payment", async () => {
+      const { maker, taker, takerToken, swap } = await swapEscrow(100);
+
+      await expectError(
+        withdraw(maker.publicKey, taker, takerToken, swap).rpc(),
+        "InsufficientPayment"
+      );
+      assert.equal(await tokenBalance(vaultPda(escrowPda(maker.publicKey))), 1_000);
+    });
+
+    it("requires the swap accounts", async () => {
+      const { maker, taker, takerToken } = await swapEscrow(500);
+      await expectError(
+        withdraw(maker.publicKey, taker, takerToken).rpc(),
+        "MissingSwapAccounts"
+      );
+    });
+  });
+
+  describe("arbiter", () => {
+    it("lets the arbiter release to the taker without the taker signing", async () => {
+      const arbiter = await newMaker();
+      const taker = anchor.web3.Keypair.generate();
+      const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+      const { maker } = await fundedEscrow(1_000, {
+        taker: taker.publicKey,
+        arbiter: arbiter.publicKey,
+      });
+      const escrow = escrowPda(maker.publicKey);
+
+      await program.methods
+        .withdraw(new anchor.BN(1_000))
+        .accountsPartial({
+          escrow,
+          vault: vaultPda(escrow),
+          takerToken,
+          feeToken,
+          takerPayment: null,
+          makerReceive: null,
+          taker: taker.publicKey,
+          arbiter: arbiter.publicKey,
+          maker: maker.publicKey,
+        })
+        .signers([arbiter])
+        .rpc();
+
+      assert.equal(await tokenBalance(takerToken), 1_000);
+    });
+
+    it("lets the arbiter refund the maker", async () => {
+      const arbiter = await newMaker();
+      const { maker, makerToken } = await fundedEscrow(1_000, { arbiter: arbiter.publicKey });
+      const escrow = escrowPda(maker.publicKey);
+
+      await program.methods
+        .cancel()
+        .accountsPartial({
+          escrow,
+          vault: vaultPda(escrow),
+          makerToken,
+          maker: maker.publicKey,
+          arbiter: arbiter.publicKey,
+        })
+        .signers([arbiter])
+        .rpc();
+
+      assert.equal(await tokenBalance(makerToken), 1_000);
+    });
+
+    for (const configured of [true, false]) {
+      it(`rejects a random key acting as arbiter (${
+        configured ? "arbiter set" : "no arbiter"
+      })`, async () => {
+        const arbiter = await newMaker();
+        const impostor = await newMaker();
+        const taker = anchor.web3.Keypair.generate();
+        const takerToken = await fundedTokenAccount(taker.publicKey, 0);
+        const { maker } = await fundedEscrow(1_000, {
+          taker: taker.publicKey,
+          arbiter: configured ? arbiter.publicKey : undefined,
+        });
+        const escrow = escrowPda(maker.publicKey);
+
+        await expectError(
+          program.methods
+            .withdraw(new anchor.BN(1_000))
+            .accountsPartial({
+              escrow,
+              vault: vaultPda(escrow),
+              takerToken,
+              feeToken,
+              takerPayment: null,
+              makerReceive: null,
+              taker: taker.publicKey,
+              arbiter: impostor.publicKey,
+              maker: maker.publicKey,
+            })
+            .signers([impostor])
+            .rpc(),
+          "UnauthorizedArbiter"
+        );
+        assert.equal(await tokenBalance(vaultPda(escrow)), 1_000);
+      });
+    }
+  });
+
+  async function fundedSolEscrow(
+    lamports: number,
+    taker: anchor.web3.PublicKey | null = null
+  ): Promise<anchor.web3.Keypair> {
+    const maker = await newMaker();
+    const escrow = solEscrowPda(maker.publicKey);
+    const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + ONE_HOUR);
+    await program.methods
+      .initSolEscrow(new anchor.BN(lamports), taker, deadline)
+      .accountsPartial({ escrow, maker: maker.publicKey })
+      .signers([maker])
+      .rpc();
+    await program.methods
+      .depositSol()
+      .accountsPartial({ escrow, maker: maker.publicKey })
+      .signers([maker])
+      .rpc();
+    return maker;
+  }
+
+  it("runs the SOL escrow through deposit and withdraw", async () => {
+    const lamports = anchor.web3.LAMPORTS_PER_SOL / 10;
+    const taker = await newMaker();
+    const maker = await fundedSolEscrow(lamports, taker.publicKey);
+    const escrow = solEscrowPda(maker.publicKey);
+    const state = await program.account.solEscrow.fetch(escrow);
+    assert.isTrue(state.funded);
+    const takerBefore = await provider.connection.getBalance(taker.publicKey);
+
+    await expectError(
+      program.methods
+        .depositSol()
+        .accountsPartial({ escrow, maker: maker.publicKey })
+        .signers([maker])
+        .rpc(),
+      "AlreadyFunded"
+    );
+    await program.methods
+      .withdrawSol()
+      .accountsPartial({ escrow, taker: taker.publicKey, maker: maker.publicKey })
+      .signers([taker])
+      .rpc();
+
+    // The provider wallet pays the fee, so the taker receives exactly `amount`.
+    assert.equal(await provider.connection.getBalance(taker.publicKey), takerBefore + lamports);
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
+
+  it("refunds the deposit and rent on cancel_sol", async () => {
+    const lamports = anchor.web3.LAMPORTS_PER_SOL / 10;
+    const maker = await fundedSolEscrow(lamports);
+    const escrow = solEscrowPda(maker.publicKey);
+    const locked = await provider.connection.getBalance(escrow);
+    const makerBefore = await provider.connection.getBalance(maker.publicKey);
+
+    await program.methods
+      .cancelSol()
+      .accountsPartial({ escrow, maker: maker.publicKey })
+      .signers([maker])
+      .rpc();
+
+    assert.equal(await provider.connection.getBalance(maker.publicKey), makerBefore + locked);
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
+});
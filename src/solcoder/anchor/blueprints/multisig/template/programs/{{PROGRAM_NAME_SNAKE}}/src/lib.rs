@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+declare_id!("{{PROGRAM_ID}}");
+
+#[program]
+pub mod {{PROGRAM_NAME_SNAKE}} {
+    use super::*;
+
+    /// Creates a multisig controlled by `owners`. Transactions execute once
+    /// `threshold` distinct owners have approved them.
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(!owners.is_empty(), MultisigError::NoOwners);
+        require!(owners.len() <= MAX_OWNERS, MultisigError::TooManyOwners);
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            MultisigError::InvalidThreshold
+        );
+        for (index, owner) in owners.iter().enumerate() {
+            require!(
+                !owners[..index].contains(owner),
+                MultisigError::DuplicateOwner
+            );
+        }
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.owners = owners;
+        multisig.threshold = threshold;
+        multisig.nonce = 0;
+        Ok(())
+    }
+
+    /// Proposes an instruction for the multisig signer PDA to sign. The
+    /// proposer must be an owner and counts as the first approval.
+    pub fn create_transaction(
+        ctx: Context<CreateTransaction>,
+        target_program: Pubkey,
+        instruction_accounts: Vec<TransactionAccount>,
+        instruction_data: Vec<u8>,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let proposer = owner_index(multisig, &ctx.accounts.proposer.key())?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.multisig = multisig.key();
+        transaction.index = multisig.nonce;
+        transaction.proposer = ctx.accounts.proposer.key();
+        transaction.program_id = target_program;
+        transaction.accounts = instruction_accounts;
+        transaction.data = instruction_data;
+        transaction.approvals = vec![false; multisig.owners.len()];
+        transaction.approvals[proposer] = true;
+        transaction.executed = false;
+        transaction.bump = ctx.bumps.transaction;
+
+        multisig.nonce = multisig
+            .nonce
+            .checked_add(1)
+            .ok_or(MultisigError::NonceOverflow)?;
+        Ok(())
+    }
+
+    /// Records the calling owner's approval. Each owner may approve once.
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        let index = owner_index(&ctx.accounts.multisig, &ctx.accounts.owner.key())?;
+        let transaction = &mut ctx.accounts.transaction;
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        require!(
+            !transaction.approvals[index],
+            MultisigError::AlreadyApproved
+        );
+        transaction.approvals[index] = true;
+        Ok(())
+    }
+
+    /// Invokes the stored instruction, signed by the multisig signer PDA, once
+    /// enough owners have approved. Every account the instruction touches, plus
+    /// the target program, must be passed as remaining accounts.
+    pub fn execute_transaction(ctx: Context<ExecuteTransaction>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        let approvals = transaction
+            .approvals
+            .iter()
+            .filter(|approved| **approved)
+            .count();
+        require!(
+            approvals >= multisig.threshold as usize,
+            MultisigError::NotEnoughApprovals
+        );
+        // Mark first so the stored instruction cannot re-enter and run twice.
+        transaction.executed = true;
+
+        let instruction = Instruction {
+            program_id: transaction.program_id,
+            accounts: transaction.accounts.iter().map(AccountMeta::from).collect(),
+            data: transaction.data.clone(),
+        };
+        let multisig_key = multisig.key();
+        let bump = [ctx.bumps.multisig_signer];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"signer", multisig_key.as_ref(), &bump]];
+        invoke_signed(&instruction, ctx.remaining_accounts, signer_seeds)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(init, payer = payer, space = 8 + Multisig::SIZE)]
+    pub multisig: Account<'info, Multisig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    target_program: Pubkey,
+    instruction_accounts: Vec<TransactionAccount>,
+    instruction_data: Vec<u8>
+)]
+pub struct CreateTransaction<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Transaction::size(
+            instruction_accounts.len(),
+            instruction_data.len(),
+            multisig.owners.len()
+        ),
+        seeds = [b"transaction", multisig.key().as_ref(), &multisig.nonce.to_le_bytes()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"transaction", multisig.key().as_ref(), &transaction.index.to_le_bytes()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    pub multisig: Account<'info, Multisig>,
+    /// CHECK: signs the stored instruction via seeds; holds no data.
+    #[account(seeds = [b"signer", multisig.key().as_ref()], bump)]
+    pub multisig_signer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"transaction", multisig.key().as_ref(), &transaction.index.to_le_bytes()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+}
+
+/// Position of `key` in the owner list, which indexes `Transaction::approvals`.
+fn owner_index(multisig: &Multisig, key: &Pubkey) -> Result<usize> {
+    multisig
+        .owners
+        .iter()
+        .position(|owner| owner == key)
+        .ok_or_else(|| error!(MultisigError::NotAnOwner))
+}
+
+/// Upper bound on owners, which fixes the size of the multisig account.
+pub const MAX_OWNERS: usize = 10;
+
+#[account]
+pub struct Multisig {
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Index of the next transaction; part of each transaction's PDA seeds.
+    pub nonce: u64,
+}
+
+impl Multisig {
+    pub const SIZE: usize = 4 + 32 * MAX_OWNERS + 1 + 8;
+}
+
+#[account]
+pub struct Transaction {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TransactionAccount>,
+    pub data: Vec<u8>,
+    /// One flag per owner, in the same order as `Multisig::owners`.
+    pub approvals: Vec<bool>,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl Transaction {
+    pub fn size(accounts: usize, data: usize, owners: usize) -> usize {
+        32 + 8 + 32 + 32 + 4 + accounts * TransactionAccount::SIZE + 4 + data + 4 + owners + 1 + 1
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl TransactionAccount {
+    pub const SIZE: usize = 32 + 1 + 1;
+}
+
+impl From<&TransactionAccount> for AccountMeta {
+    fn from(account: &TransactionAccount) -> Self {
+        AccountMeta {
+            pubkey: account.pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }
+    }
+}
+
+#[error_code]
+pub enum MultisigError {
+    #[msg("A multisig needs at least one owner.")]
+    NoOwners,
+    #[msg("Too many owners for one multisig.")]
+    TooManyOwners,
+    #[msg("Threshold must be between one and the number of owners.")]
+    InvalidThreshold,
+    #[msg("Owners must be unique.")]
+    DuplicateOwner,
+    #[msg("Signer is not an owner of this multisig.")]
+    NotAnOwner,
+    #[msg("This owner has already approved the transaction.")]
+    AlreadyApproved,
+    #[msg("The transaction has already been executed.")]
+    AlreadyExecuted,
+    #[msg("Not enough owners have approved the transaction.")]
+    NotEnoughApprovals,
+    #[msg("Transaction nonce overflowed.")]
+    NonceOverflow,
+}
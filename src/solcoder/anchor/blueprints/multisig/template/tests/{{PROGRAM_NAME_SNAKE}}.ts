@@ -0,0 +1,209 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const { SystemProgram, LAMPORTS_PER_SOL } = anchor.web3;
+
+  type Fixture = {
+    multisig: anchor.web3.PublicKey;
+    signer: anchor.web3.PublicKey;
+    owners: anchor.web3.Keypair[];
+  };
+
+  function signerPda(multisig: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [address] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("signer"), multisig.toBuffer()],
+      program.programId
+    );
+    return address;
+  }
+
+  function transactionPda(multisig: anchor.web3.PublicKey, index: number) {
+    const indexSeed = new anchor.BN(index).toArrayLike(Buffer, "le", 8);
+    const [address] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("transaction"), multisig.toBuffer(), indexSeed],
+      program.programId
+    );
+    return address;
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  async function fund(address: anchor.web3.PublicKey, lamports = LAMPORTS_PER_SOL) {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(address, lamports),
+      "confirmed"
+    );
+  }
+
+  function createMultisig(
+    multisig: anchor.web3.Keypair,
+    owners: anchor.web3.PublicKey[],
+    threshold: number
+  ) {
+    return program.methods
+      .createMultisig(owners, threshold)
+      .accountsPartial({ multisig: multisig.publicKey, payer: provider.wallet.publicKey })
+      .signers([multisig]);
+  }
+
+  async function newMultisig(ownerCount: number, threshold: number): Promise<Fixture> {
+    const owners = Array.from({ length: ownerCount }, () => anchor.web3.Keypair.generate());
+    for (const owner of owners) {
+      await fund(owner.publicKey);
+    }
+    const multisig = anchor.web3.Keypair.generate();
+    await createMultisig(
+      multisig,
+      owners.map((owner) => owner.publicKey),
+      threshold
+    ).rpc();
+    const signer = signerPda(multisig.publicKey);
+    await fund(signer);
+    return { multisig: multisig.publicKey, signer, owners };
+  }
+
+  // Proposes a SOL transfer out of the multisig signer PDA.
+  async function proposeTransfer(
+    fixture: Fixture,
+    proposer: anchor.web3.Keypair,
+    recipient: anchor.web3.PublicKey,
+    lamports: number
+  ) {
+    const instruction = SystemProgram.transfer({
+      fromPubkey: fixture.signer,
+      toPubkey: recipient,
+      lamports,
+    });
+    const { nonce } = await program.account.multisig.fetch(fixture.multisig);
+    const transaction = transactionPda(fixture.multisig, nonce.toNumber());
+    await program.methods
+      .createTransaction(instruction.programId, instruction.keys, instruction.data)
+      .accountsPartial({
+        multisig: fixture.multisig,
+        transaction,
+        proposer: proposer.publicKey,
+      })
+      .signers([proposer])
+      .rpc();
+    return { transaction, instruction };
+  }
+
+  function approve(
+    fixture: Fixture,
+    transaction: anchor.web3.PublicKey,
+    owner: anchor.web3.Keypair
+  ) {
+    return program.methods
+      .approve()
+      .accountsPartial({ multisig: fixture.multisig, transaction, owner: owner.publicKey })
+      .signers([owner]);
+  }
+
+  function execute(
+    fixture: Fixture,
+    transaction: anchor.web3.PublicKey,
+    instruction: anchor.web3.TransactionInstruction
+  ) {
+    // The signer PDA cannot sign the outer transaction; the program signs for it.
+    const remaining = instruction.keys
+      .map((meta) => ({ ...meta, isSigner: false }))
+      .concat({ pubkey: instruction.programId, isSigner: false, isWritable: false });
+    return program.methods
+      .executeTransaction()
+      .accountsPartial({
+        multisig: fixture.multisig,
+        multisigSigner: fixture.signer,
+        transaction,
+      })
+      .remainingAccounts(remaining);
+  }
+
+  it("creates a multisig with owners and a threshold", async () => {
+    const fixture = await newMultisig(3, 2);
+
+    const multisig = await program.account.multisig.fetch(fixture.multisig);
+    assert.equal(multisig.threshold, 2);
+    assert.equal(multisig.nonce.toNumber(), 0);
+    assert.deepEqual(
+      multisig.owners.map((owner) => owner.toBase58()),
+      fixture.owners.map((owner) => owner.publicKey.toBase58())
+    );
+  });
+
+  it("rejects a threshold above the number of owners", async () => {
+    const owners = [anchor.web3.Keypair.generate().publicKey];
+    await expectError(
+      createMultisig(anchor.web3.Keypair.generate(), owners, 2).rpc(),
+      "InvalidThreshold"
+    );
+  });
+
+  it("executes only once the threshold is reached", async () => {
+    const fixture = await newMultisig(3, 2);
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const [proposer, second] = fixture.owners;
+    const { transaction, instruction } = await proposeTransfer(
+      fixture,
+      proposer,
+      recipient,
+      LAMPORTS_PER_SOL / 10
+    );
+
+    // The proposer's implicit approval is one short of the threshold.
+    await expectError(execute(fixture, transaction, instruction).rpc(), "NotEnoughApprovals");
+
+    await approve(fixture, transaction, second).rpc();
+    await execute(fixture, transaction, instruction).rpc();
+
+    assert.equal(await provider.connection.getBalance(recipient), LAMPORTS_PER_SOL / 10);
+    assert.isTrue((await program.account.transaction.fetch(transaction)).executed);
+    await expectError(execute(fixture, transaction, instruction).rpc(), "AlreadyExecuted");
+  });
+
+  it("rejects a second approval from the same owner", async () => {
+    const fixture = await newMultisig(3, 3);
+    const [proposer, second] = fixture.owners;
+    const { transaction } = await proposeTransfer(
+      fixture,
+      proposer,
+      anchor.web3.Keypair.generate().publicKey,
+      1_000_000
+    );
+
+    await expectError(approve(fixture, transaction, proposer).rpc(), "AlreadyApproved");
+    await approve(fixture, transaction, second).rpc();
+    await expectError(approve(fixture, transaction, second).rpc(), "AlreadyApproved");
+
+    const approvals = (await program.account.transaction.fetch(transaction)).approvals;
+    assert.deepEqual(approvals, [true, true, false]);
+  });
+
+  it("rejects approvals from non-owners", async () => {
+    const fixture = await newMultisig(2, 2);
+    const { transaction } = await proposeTransfer(
+      fixture,
+      fixture.owners[0],
+      anchor.web3.Keypair.generate().publicKey,
+      1_000_000
+    );
+    const outsider = anchor.web3.Keypair.generate();
+
+    await expectError(approve(fixture, transaction, outsider).rpc(), "NotAnOwner");
+  });
+});
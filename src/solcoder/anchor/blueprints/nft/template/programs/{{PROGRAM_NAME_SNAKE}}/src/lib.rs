@@ -1,4 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::mpl_token_metadata::types::{CollectionDetails, Creator, DataV2};
+use anchor_spl::metadata::{
+    self, BurnNft, CreateMasterEditionV3, CreateMetadataAccountsV3, FreezeDelegatedAccount,
+    MasterEditionAccount, Metadata, MetadataAccount, MintNewEditionFromMasterEditionViaToken,
+    SetAndVerifySizedCollectionItem, ThawDelegatedAccount, UpdateMetadataAccountsV2,
+};
+use anchor_spl::token::{self, Approve, Mint, MintTo, Token, TokenAccount, Transfer};
 
 declare_id!("{{PROGRAM_ID}}");
 
@@ -6,10 +15,775 @@ declare_id!("{{PROGRAM_ID}}");
 pub mod {{PROGRAM_NAME_SNAKE}} {
     use super::*;
 
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
+    /// Mints a single token to the owner, attaches Metaplex metadata, and creates a
+    /// master edition with `max_supply = 0`. The master edition takes over the mint
+    /// and freeze authorities, so no further tokens or prints can ever be minted.
+    ///
+    /// `seller_fee_basis_points` and `creators` are the royalty terms marketplaces
+    /// read from the metadata. Creators are stored unverified.
+    pub fn mint_nft(
+        ctx: Context<MintNft>,
+        name: String,
+        symbol: String,
+        uri: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<NftCreator>,
+    ) -> Result<()> {
+        let data = nft_data(name, symbol, uri, seller_fee_basis_points, creators)?;
+        mint_master_edition(&ctx.accounts, data, None, Some(0))
+    }
+
+    /// Like `mint_nft`, but the master edition allows numbered prints through
+    /// `mint_edition`: up to `max_supply` of them, or without limit for `None`.
+    pub fn mint_master(
+        ctx: Context<MintNft>,
+        name: String,
+        symbol: String,
+        uri: String,
+        max_supply: Option<u64>,
+    ) -> Result<()> {
+        let data = nft_data(name, symbol, uri, 0, Vec::new())?;
+        mint_master_edition(&ctx.accounts, data, None, max_supply)
+    }
+
+    /// Prints edition `edition_number` of a master minted by `mint_master` into a
+    /// new mint held by the master's owner. Metaplex copies the master's metadata
+    /// and records the print in the edition marker PDA for
+    /// `edition_number / 248`, rejecting numbers that were already printed.
+    pub fn mint_edition(ctx: Context<MintEdition>, edition_number: u64) -> Result<()> {
+        require!(edition_number > 0, NftError::EditionOutOfRange);
+        if let Some(max_supply) = ctx.accounts.master_edition.max_supply {
+            require!(edition_number <= max_supply, NftError::EditionOutOfRange);
+        }
+
+        let owner = ctx.accounts.owner.to_account_info();
+        token::mint_to(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.new_mint.to_account_info(),
+                    to: ctx.accounts.new_token_account.to_account_info(),
+                    authority: owner.clone(),
+                },
+            ),
+            1,
+        )?;
+        metadata::mint_new_edition_from_master_edition_via_token(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                MintNewEditionFromMasterEditionViaToken {
+                    new_metadata: ctx.accounts.new_metadata.to_account_info(),
+                    new_edition: ctx.accounts.new_edition.to_account_info(),
+                    master_edition: ctx.accounts.master_edition.to_account_info(),
+                    new_mint: ctx.accounts.new_mint.to_account_info(),
+                    edition_mark_pda: ctx.accounts.edition_marker.to_account_info(),
+                    new_mint_authority: owner.clone(),
+                    payer: owner.clone(),
+                    token_account_owner: owner.clone(),
+                    token_account: ctx.accounts.master_token_account.to_account_info(),
+                    new_metadata_update_authority: owner,
+                    metadata: ctx.accounts.master_metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                    metadata_mint: ctx.accounts.master_mint.to_account_info(),
+                },
+            ),
+            edition_number,
+        )
+    }
+
+    /// Mints a collection NFT that items join through `set_collection`. It is a
+    /// sized collection, so Metaplex keeps count of its verified items.
+    pub fn mint_collection(
+        ctx: Context<MintNft>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let data = nft_data(name, symbol, uri, 0, Vec::new())?;
+        mint_master_edition(
+            &ctx.accounts,
+            data,
+            Some(CollectionDetails::V1 { size: 0 }),
+            Some(0),
+        )
+    }
+
+    /// Sets the item's collection and verifies it in a single CPI. The
+    /// collection's update authority has to sign.
+    pub fn set_collection(ctx: Context<SetCollection>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.collection_metadata.update_authority,
+            ctx.accounts.collection_authority.key(),
+            NftError::UnauthorizedCollectionAuthority
+        );
+
+        metadata::set_and_verify_sized_collection_item(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                SetAndVerifySizedCollectionItem {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    collection_authority: ctx.accounts.collection_authority.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    update_authority: ctx.accounts.update_authority.to_account_info(),
+                    collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                    collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                    collection_master_edition: ctx
+                        .accounts
+                        .collection_master_edition
+                        .to_account_info(),
+                },
+            ),
+            None,
+        )
+    }
+
+    /// Overwrites the name and/or URI of a mutable NFT, keeping every other field
+    /// as it is on chain. Only the update authority may sign.
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        new_uri: Option<String>,
+        new_name: Option<String>,
+    ) -> Result<()> {
+        let current = &ctx.accounts.metadata;
+        require!(current.is_mutable, NftError::ImmutableMetadata);
+        require_keys_eq!(
+            current.update_authority,
+            ctx.accounts.update_authority.key(),
+            NftError::UnauthorizedUpdateAuthority
+        );
+        if let Some(name) = &new_name {
+            require!(name.len() <= MAX_NAME, NftError::NameTooLong);
+        }
+        if let Some(uri) = &new_uri {
+            require!(uri.len() <= MAX_URI, NftError::UriTooLong);
+        }
+
+        // Metaplex pads stored strings with NUL bytes; strip them before writing
+        // the unchanged values back.
+        let unpadded = |value: &str| value.trim_end_matches('\0').to_string();
+        let data = DataV2 {
+            name: new_name.unwrap_or_else(|| unpadded(&current.name)),
+            symbol: unpadded(&current.symbol),
+            uri: new_uri.unwrap_or_else(|| unpadded(&current.uri)),
+            seller_fee_basis_points: current.seller_fee_basis_points,
+            creators: current.creators.clone(),
+            collection: current.collection.clone(),
+            uses: current.uses.clone(),
+        };
+
+        metadata::update_metadata_accounts_v2(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: ctx.accounts.update_authority.to_account_info(),
+                },
+            ),
+            None,
+            Some(data),
+            None,
+            None,
+        )
+    }
+
+    /// Moves the NFT from the owner's token account to the recipient's. A plain
+    /// SPL transfer covers unfrozen tokens. A token frozen by its delegate (via
+    /// Metaplex `freeze_delegated_account`) needs that delegate to co-sign: it is
+    /// thawed, transferred, and, with `refreeze`, approved to the same delegate and
+    /// frozen again in the recipient's account, which the recipient must sign for.
+    pub fn transfer_nft(ctx: Context<TransferNft>, refreeze: bool) -> Result<()> {
+        let frozen = ctx.accounts.source.is_frozen();
+        let delegate = ctx.accounts.delegate.as_ref();
+        if frozen {
+            let delegate = delegate.ok_or(NftError::MissingFreezeDelegate)?;
+            require!(
+                ctx.accounts.source.delegate == COption::Some(delegate.key()),
+                NftError::MissingFreezeDelegate
+            );
+            metadata::thaw_delegated_account(CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                ThawDelegatedAccount {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    delegate: delegate.to_account_info(),
+                    token_account: ctx.accounts.source.to_account_info(),
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+        }
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        if !(frozen && refreeze) {
+            return Ok(());
+        }
+        let delegate = delegate.ok_or(NftError::MissingFreezeDelegate)?;
+        require!(
+            ctx.accounts.recipient.is_signer,
+            NftError::RecipientMustSign
+        );
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.destination.to_account_info(),
+                    delegate: delegate.to_account_info(),
+                    authority: ctx.accounts.recipient.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+        metadata::freeze_delegated_account(CpiContext::new(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            FreezeDelegatedAccount {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                delegate: delegate.to_account_info(),
+                token_account: ctx.accounts.destination.to_account_info(),
+                edition: ctx.accounts.master_edition.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))
+    }
+
+    /// Replaces the NFT's on-chain traits, creating its `Attributes` PDA on first
+    /// use (paid by the signer). The current holder or the metadata update
+    /// authority may sign. Clients read traits by fetching the PDA directly.
+    pub fn set_attributes(ctx: Context<SetAttributes>, attributes: Vec<Attribute>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let is_holder = ctx
+            .accounts
+            .holder_token_account
+            .as_ref()
+            .is_some_and(|account| account.owner == signer && account.amount == 1);
+        require!(
+            is_holder || ctx.accounts.metadata.update_authority == signer,
+            NftError::UnauthorizedAttributesAuthority
+        );
+        require!(
+            attributes.len() <= Attributes::MAX_ATTRIBUTES,
+            NftError::TooManyAttributes
+        );
+        for attribute in &attributes {
+            require!(
+                attribute.key.len() <= Attributes::MAX_KEY
+                    && attribute.value.len() <= Attributes::MAX_VALUE,
+                NftError::AttributeTooLong
+            );
+        }
+
+        let account = &mut ctx.accounts.attributes;
+        account.mint = ctx.accounts.mint.key();
+        account.attributes = attributes;
+        account.bump = ctx.bumps.attributes;
         Ok(())
     }
+
+    /// Burns the owner's NFT. Metaplex burns the token and closes the token
+    /// account, metadata, and master edition, refunding their rent to the owner.
+    /// Items in a verified collection must also pass the collection's metadata.
+    pub fn burn_nft(ctx: Context<BurnNftAccounts>) -> Result<()> {
+        let collection_metadata = ctx
+            .accounts
+            .collection_metadata
+            .as_ref()
+            .map(|account| account.to_account_info());
+
+        metadata::burn_nft(
+            CpiContext::new(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                BurnNft {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    owner: ctx.accounts.owner.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    token: ctx.accounts.token_account.to_account_info(),
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    spl_token: ctx.accounts.token_program.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(collection_metadata.iter().cloned().collect()),
+            collection_metadata.map(|account| account.key()),
+        )
+    }
+}
+
+/// Limits enforced by the token metadata program.
+pub const MAX_NAME: usize = 32;
+pub const MAX_SYMBOL: usize = 10;
+pub const MAX_URI: usize = 200;
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct NftCreator {
+    pub address: Pubkey,
+    /// Percentage of royalties; all shares must add up to 100.
+    pub share: u8,
+}
+
+/// One on-chain trait, e.g. `("level", "7")`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Attribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// Mutable traits of one NFT, at `[b"attributes", mint]`.
+#[account]
+pub struct Attributes {
+    pub mint: Pubkey,
+    pub attributes: Vec<Attribute>,
+    pub bump: u8,
+}
+
+impl Attributes {
+    pub const MAX_ATTRIBUTES: usize = 16;
+    pub const MAX_KEY: usize = 32;
+    pub const MAX_VALUE: usize = 64;
+    pub const SIZE: usize =
+        32 + 4 + Self::MAX_ATTRIBUTES * (4 + Self::MAX_KEY + 4 + Self::MAX_VALUE) + 1;
+}
+
+/// Validates the metadata fields and royalty terms before they reach Metaplex.
+fn nft_data(
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Vec<NftCreator>,
+) -> Result<DataV2> {
+    require!(name.len() <= MAX_NAME, NftError::NameTooLong);
+    require!(symbol.len() <= MAX_SYMBOL, NftError::SymbolTooLong);
+    require!(uri.len() <= MAX_URI, NftError::UriTooLong);
+    require!(
+        seller_fee_basis_points <= MAX_SELLER_FEE_BASIS_POINTS,
+        NftError::InvalidRoyalty
+    );
+
+    let creators = if creators.is_empty() {
+        None
+    } else {
+        let total: u16 = creators.iter().map(|c| u16::from(c.share)).sum();
+        require!(total == 100, NftError::InvalidCreatorShares);
+        Some(
+            creators
+                .into_iter()
+                .map(|c| Creator {
+                    address: c.address,
+                    verified: false,
+                    share: c.share,
+                })
+                .collect(),
+        )
+    };
+
+    Ok(DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        creators,
+        collection: None,
+        uses: None,
+    })
+}
+
+/// Shared by `mint_nft`, `mint_master` and `mint_collection`; only collections
+/// carry `collection_details`, and only `mint_master` allows prints.
+fn mint_master_edition(
+    accounts: &MintNft,
+    data: DataV2,
+    collection_details: Option<CollectionDetails>,
+    max_supply: Option<u64>,
+) -> Result<()> {
+    let owner = accounts.owner.to_account_info();
+    token::mint_to(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            MintTo {
+                mint: accounts.mint.to_account_info(),
+                to: accounts.token_account.to_account_info(),
+                authority: owner.clone(),
+            },
+        ),
+        1,
+    )?;
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new(
+            accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: accounts.metadata.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                mint_authority: owner.clone(),
+                payer: owner.clone(),
+                update_authority: owner.clone(),
+                system_program: accounts.system_program.to_account_info(),
+                rent: accounts.rent.to_account_info(),
+            },
+        ),
+        data,
+        true,
+        true,
+        collection_details,
+    )?;
+    metadata::create_master_edition_v3(
+        CpiContext::new(
+            accounts.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: accounts.master_edition.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                update_authority: owner.clone(),
+                mint_authority: owner.clone(),
+                payer: owner,
+                metadata: accounts.metadata.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                rent: accounts.rent.to_account_info(),
+            },
+        ),
+        max_supply,
+    )
+}
+
+#[derive(Accounts)]
+pub struct MintNft<'info> {
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = owner,
+        mint::freeze_authority = owner
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: created by the token metadata program; the seeds pin the address.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: created by the token metadata program; the seeds pin the address.
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MintEdition<'info> {
+    pub master_mint: Account<'info, Mint>,
+    /// The master's holder pays for and receives the print.
+    #[account(
+        associated_token::mint = master_mint,
+        associated_token::authority = owner,
+        constraint = master_token_account.amount == 1 @ NftError::NotTokenHolder
+    )]
+    pub master_token_account: Account<'info, TokenAccount>,
+    /// CHECK: the master's metadata, read by the token metadata program; the seeds
+    /// pin the address.
+    #[account(
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            master_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub master_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            master_mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub master_edition: Account<'info, MasterEditionAccount>,
+    /// CHECK: `[b"metadata", token_metadata_program, master_mint, b"edition",
+    /// (edition_number / 248).to_string()]`; created and derivation-checked by the
+    /// token metadata program.
+    #[account(mut)]
+    pub edition_marker: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = owner,
+        mint::freeze_authority = owner
+    )]
+    pub new_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = new_mint,
+        associated_token::authority = owner
+    )]
+    pub new_token_account: Account<'info, TokenAccount>,
+    /// CHECK: created by the token metadata program; the seeds pin the address.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), new_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub new_metadata: UncheckedAccount<'info>,
+    /// CHECK: created by the token metadata program; the seeds pin the address.
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            new_mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub new_edition: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct SetCollection<'info> {
+    pub mint: Account<'info, Mint>,
+    /// CHECK: the item's metadata, owned by the token metadata program; the seeds
+    /// pin the address.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: the item's update authority; checked by the token metadata program.
+    pub update_authority: UncheckedAccount<'info>,
+    pub collection_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            collection_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_metadata: Account<'info, MetadataAccount>,
+    /// CHECK: the collection's master edition; the seeds pin the address.
+    #[account(
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            collection_mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    pub collection_authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    pub update_authority: Signer<'info>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct TransferNft<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        constraint = source.amount == 1 @ NftError::NotTokenHolder
+    )]
+    pub source: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = recipient)]
+    pub destination: Account<'info, TokenAccount>,
+    /// CHECK: owner of `destination`; only has to sign when `refreeze` is set.
+    pub recipient: UncheckedAccount<'info>,
+    /// CHECK: read by the token metadata program; the seeds pin the address.
+    #[account(
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: the mint's freeze authority, used by the token metadata program to
+    /// thaw and freeze; the seeds pin the address.
+    #[account(
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    pub owner: Signer<'info>,
+    /// Required when `source` is frozen: the delegate that froze it.
+    pub delegate: Option<Signer<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct SetAttributes<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    /// The signer's token account; pass it when signing as the holder.
+    #[account(token::mint = mint)]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Attributes::SIZE,
+        seeds = [b"attributes", mint.key().as_ref()],
+        bump
+    )]
+    pub attributes: Account<'info, Attributes>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnNftAccounts<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        constraint = token_account.amount == 1 @ NftError::NotTokenHolder
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: closed by the token metadata program; the seeds pin the address.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: closed by the token metadata program; the seeds pin the address.
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: the collection's metadata, validated by the token metadata program.
+    #[account(mut)]
+    pub collection_metadata: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[error_code]
+pub enum NftError {
+    #[msg("NFT name exceeds 32 bytes.")]
+    NameTooLong,
+    #[msg("NFT symbol exceeds 10 bytes.")]
+    SymbolTooLong,
+    #[msg("NFT URI exceeds 200 bytes.")]
+    UriTooLong,
+    #[msg("Signer is not the collection's update authority.")]
+    UnauthorizedCollectionAuthority,
+    #[msg("Seller fee exceeds 10000 basis points.")]
+    InvalidRoyalty,
+    #[msg("Creator shares must add up to 100.")]
+    InvalidCreatorShares,
+    #[msg("Metadata is immutable.")]
+    ImmutableMetadata,
+    #[msg("Signer is not the metadata update authority.")]
+    UnauthorizedUpdateAuthority,
+    #[msg("Owner does not hold this NFT.")]
+    NotTokenHolder,
+    #[msg("The NFT is frozen; its freeze delegate must sign the transfer.")]
+    MissingFreezeDelegate,
+    #[msg("Re-freezing requires the recipient to sign.")]
+    RecipientMustSign,
+    #[msg("Edition number must be between 1 and the master's max supply.")]
+    EditionOutOfRange,
+    #[msg("Only the holder or the update authority may set attributes.")]
+    UnauthorizedAttributesAuthority,
+    #[msg("Too many attributes; the limit is 16.")]
+    TooManyAttributes,
+    #[msg("Attribute keys are limited to 32 bytes and values to 64.")]
+    AttributeTooLong,
+}
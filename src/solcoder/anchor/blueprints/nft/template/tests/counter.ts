@@ -1,5 +0,0 @@
-import test from "ava";
-
-test("{{PROGRAM_NAME_TITLE}} NFT scaffold compiles", (t) => {
-  t.pass();
-});
@@ -0,0 +1,527 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  TOKEN_PROGRAM_ID,
+  approve,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const owner = provider.wallet.publicKey;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  function metadataPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    return pda;
+  }
+
+  function masterEditionPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.toBuffer(),
+        Buffer.from("edition"),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    return pda;
+  }
+
+  type Creator = { address: anchor.web3.PublicKey; verified: boolean; share: number };
+
+  type DecodedMetadata = {
+    updateAuthority: anchor.web3.PublicKey;
+    name: string;
+    symbol: string;
+    uri: string;
+    sellerFeeBasisPoints: number;
+    creators: Creator[] | null;
+    isMutable: boolean;
+    collection: { verified: boolean; key: anchor.web3.PublicKey } | null;
+  };
+
+  // Walks the borsh layout of a Metaplex metadata account up to the collection
+  // field. Strings are padded with NUL bytes on chain.
+  function decodeMetadata(data: Buffer): DecodedMetadata {
+    let offset = 1;
+    const readPubkey = () => {
+      const key = new anchor.web3.PublicKey(data.subarray(offset, offset + 32));
+      offset += 32;
+      return key;
+    };
+    const readString = () => {
+      const len = data.readUInt32LE(offset);
+      const value = data.subarray(offset + 4, offset + 4 + len).toString("utf8");
+      offset += 4 + len;
+      return value.replace(/\0+$/, "");
+    };
+    const readBool = () => data[offset++] === 1;
+
+    const updateAuthority = readPubkey();
+    readPubkey(); // mint
+    const name = readString();
+    const symbol = readString();
+    const uri = readString();
+    const sellerFeeBasisPoints = data.readUInt16LE(offset);
+    offset += 2;
+    let creators: Creator[] | null = null;
+    if (readBool()) {
+      creators = [];
+      const count = data.readUInt32LE(offset);
+      offset += 4;
+      for (let i = 0; i < count; i++) {
+        creators.push({ address: readPubkey(), verified: readBool(), share: data[offset++] });
+      }
+    }
+    readBool(); // primary_sale_happened
+    const isMutable = readBool();
+    if (readBool()) offset += 1; // edition_nonce
+    if (readBool()) offset += 1; // token_standard
+    const collection = readBool() ? { verified: readBool(), key: readPubkey() } : null;
+    return {
+      updateAuthority,
+      name,
+      symbol,
+      uri,
+      sellerFeeBasisPoints,
+      creators,
+      isMutable,
+      collection,
+    };
+  }
+
+  async function fetchMetadata(mint: anchor.web3.PublicKey): Promise<DecodedMetadata> {
+    const account = await provider.connection.getAccountInfo(metadataPda(mint));
+    assert.isNotNull(account);
+    return decodeMetadata(account!.data);
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  type Royalty = {
+    sellerFeeBasisPoints: number;
+    creators: { address: anchor.web3.PublicKey; share: number }[];
+  };
+
+  function mintAccounts(mint: anchor.web3.Keypair) {
+    return {
+      mint: mint.publicKey,
+      tokenAccount: getAssociatedTokenAddressSync(mint.publicKey, owner),
+      metadata: metadataPda(mint.publicKey),
+      masterEdition: masterEditionPda(mint.publicKey),
+      owner,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    };
+  }
+
+  function mintNft(
+    mint: anchor.web3.Keypair,
+    name = "{{PROGRAM_NAME_TITLE}} #1",
+    symbol = "NFT",
+    uri = "https://example.com/nft.json",
+    royalty: Royalty = { sellerFeeBasisPoints: 0, creators: [] }
+  ) {
+    return program.methods
+      .mintNft(name, symbol, uri, royalty.sellerFeeBasisPoints, royalty.creators)
+      .accountsPartial(mintAccounts(mint))
+      .signers([mint]);
+  }
+
+  function setCollection(
+    mint: anchor.web3.PublicKey,
+    collectionMint: anchor.web3.PublicKey,
+    collectionAuthority: anchor.web3.Keypair | null = null
+  ) {
+    const builder = program.methods.setCollection().accountsPartial({
+      mint,
+      metadata: metadataPda(mint),
+      updateAuthority: owner,
+      collectionMint,
+      collectionMetadata: metadataPda(collectionMint),
+      collectionMasterEdition: masterEditionPda(collectionMint),
+      collectionAuthority: collectionAuthority?.publicKey ?? owner,
+      payer: owner,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    });
+    return collectionAuthority ? builder.signers([collectionAuthority]) : builder;
+  }
+
+  async function mintCollection(): Promise<anchor.web3.PublicKey> {
+    const collection = anchor.web3.Keypair.generate();
+    await program.methods
+      .mintCollection(
+        "{{PROGRAM_NAME_TITLE}} Collection",
+        "COL",
+        "https://example.com/collection.json"
+      )
+      .accountsPartial(mintAccounts(collection))
+      .signers([collection])
+      .rpc();
+    return collection.publicKey;
+  }
+
+  it("mints a single NFT and locks the supply", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await mintNft(mint).rpc();
+
+    const mintAccount = await getMint(provider.connection, mint.publicKey);
+    assert.equal(Number(mintAccount.supply), 1);
+    assert.equal(mintAccount.decimals, 0);
+    // The master edition now holds the mint authority, so nobody can mint more.
+    assert.ok(mintAccount.mintAuthority?.equals(masterEditionPda(mint.publicKey)));
+
+    const holding = await getAccount(
+      provider.connection,
+      getAssociatedTokenAddressSync(mint.publicKey, owner)
+    );
+    assert.equal(Number(holding.amount), 1);
+    assert.isNotNull(await provider.connection.getAccountInfo(metadataPda(mint.publicKey)));
+  });
+
+  it("rejects a name longer than 32 bytes", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await expectError(mintNft(mint, "x".repeat(33)).rpc(), "NameTooLong");
+  });
+
+  it("stores royalties and creators in the metadata", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    const creator = anchor.web3.Keypair.generate().publicKey;
+    await mintNft(mint, undefined, undefined, undefined, {
+      sellerFeeBasisPoints: 500,
+      creators: [{ address: creator, share: 100 }],
+    }).rpc();
+
+    const metadata = await fetchMetadata(mint.publicKey);
+    assert.equal(metadata.sellerFeeBasisPoints, 500);
+    assert.lengthOf(metadata.creators!, 1);
+    assert.ok(metadata.creators![0].address.equals(creator));
+    assert.equal(metadata.creators![0].share, 100);
+    assert.isFalse(metadata.creators![0].verified);
+  });
+
+  it("rejects creator shares that do not add up to 100", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await expectError(
+      mintNft(mint, undefined, undefined, undefined, {
+        sellerFeeBasisPoints: 500,
+        creators: [{ address: owner, share: 60 }],
+      }).rpc(),
+      "InvalidCreatorShares"
+    );
+  });
+
+  it("updates only the URI and keeps the name", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await mintNft(mint, "Original", "NFT", "https://example.com/old.json").rpc();
+
+    await program.methods
+      .updateMetadata("https://example.com/new.json", null)
+      .accountsPartial({
+        mint: mint.publicKey,
+        metadata: metadataPda(mint.publicKey),
+        updateAuthority: owner,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc();
+
+    const metadata = await fetchMetadata(mint.publicKey);
+    assert.equal(metadata.uri, "https://example.com/new.json");
+    assert.equal(metadata.name, "Original");
+    assert.equal(metadata.symbol, "NFT");
+  });
+
+  it("burns the NFT and closes its metadata", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await mintNft(mint).rpc();
+    const tokenAccount = getAssociatedTokenAddressSync(mint.publicKey, owner);
+
+    await program.methods
+      .burnNft()
+      .accountsPartial({
+        mint: mint.publicKey,
+        tokenAccount,
+        metadata: metadataPda(mint.publicKey),
+        masterEdition: masterEditionPda(mint.publicKey),
+        collectionMetadata: null,
+        owner,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc();
+
+    assert.equal(Number((await getMint(provider.connection, mint.publicKey)).supply), 0);
+    assert.isNull(await provider.connection.getAccountInfo(metadataPda(mint.publicKey)));
+    assert.isNull(await provider.connection.getAccountInfo(tokenAccount));
+  });
+
+  describe("attributes", () => {
+    type Trait = { key: string; value: string };
+
+    function attributesPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+      const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("attributes"), mint.toBuffer()],
+        program.programId
+      );
+      return pda;
+    }
+
+    function setAttributes(mint: anchor.web3.PublicKey, attributes: Trait[]) {
+      return program.methods.setAttributes(attributes).accountsPartial({
+        mint,
+        metadata: metadataPda(mint),
+        holderTokenAccount: getAssociatedTokenAddressSync(mint, owner),
+        attributes: attributesPda(mint),
+        signer: owner,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      });
+    }
+
+    it("stores and replaces the NFT's traits", async () => {
+      const mint = anchor.web3.Keypair.generate();
+      await mintNft(mint).rpc();
+
+      await setAttributes(mint.publicKey, [
+        { key: "class", value: "mage" },
+        { key: "level", value: "1" },
+      ]).rpc();
+      await setAttributes(mint.publicKey, [{ key: "level", value: "7" }]).rpc();
+
+      const account = await program.account.attributes.fetch(attributesPda(mint.publicKey));
+      assert.ok(account.mint.equals(mint.publicKey));
+      assert.deepEqual(account.attributes, [{ key: "level", value: "7" }]);
+    });
+
+    it("rejects more than 16 attributes", async () => {
+      const mint = anchor.web3.Keypair.generate();
+      await mintNft(mint).rpc();
+      const traits = Array.from({ length: 17 }, (_, i) => ({ key: `trait${i}`, value: "x" }));
+
+      await expectError(setAttributes(mint.publicKey, traits).rpc(), "TooManyAttributes");
+    });
+  });
+
+  describe("editions", () => {
+    const EDITION_MARKER_BIT_SIZE = 248;
+
+    function editionMarkerPda(
+      masterMint: anchor.web3.PublicKey,
+      edition: number
+    ): anchor.web3.PublicKey {
+      const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+          masterMint.toBuffer(),
+          Buffer.from("edition"),
+          Buffer.from(Math.floor(edition / EDITION_MARKER_BIT_SIZE).toString()),
+        ],
+        TOKEN_METADATA_PROGRAM_ID
+      );
+      return pda;
+    }
+
+    async function mintMaster(maxSupply: number | null): Promise<anchor.web3.PublicKey> {
+      const master = anchor.web3.Keypair.generate();
+      await program.methods
+        .mintMaster(
+          "{{PROGRAM_NAME_TITLE}} Master",
+          "ED",
+          "https://example.com/master.json",
+          maxSupply === null ? null : new anchor.BN(maxSupply)
+        )
+        .accountsPartial(mintAccounts(master))
+        .signers([master])
+        .rpc();
+      return master.publicKey;
+    }
+
+    function mintEdition(masterMint: anchor.web3.PublicKey, edition: number) {
+      const newMint = anchor.web3.Keypair.generate();
+      const builder = program.methods
+        .mintEdition(new anchor.BN(edition))
+        .accountsPartial({
+          masterMint,
+          masterTokenAccount: getAssociatedTokenAddressSync(masterMint, owner),
+          masterMetadata: metadataPda(masterMint),
+          masterEdition: masterEditionPda(masterMint),
+          editionMarker: editionMarkerPda(masterMint, edition),
+          newMint: newMint.publicKey,
+          newTokenAccount: getAssociatedTokenAddressSync(newMint.publicKey, owner),
+          newMetadata: metadataPda(newMint.publicKey),
+          newEdition: masterEditionPda(newMint.publicKey),
+          owner,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([newMint]);
+      return { newMint: newMint.publicKey, builder };
+    }
+
+    // Edition accounts are `key (1) | parent (32) | edition (u64)`.
+    async function fetchEdition(mint: anchor.web3.PublicKey) {
+      const account = await provider.connection.getAccountInfo(masterEditionPda(mint));
+      assert.isNotNull(account);
+      return {
+        parent: new anchor.web3.PublicKey(account!.data.subarray(1, 33)),
+        edition: new anchor.BN(account!.data.subarray(33, 41), "le").toNumber(),
+      };
+    }
+
+    it("prints numbered editions that reference the master", async () => {
+      const master = await mintMaster(10);
+
+      for (const number of [1, 2]) {
+        const { newMint, builder } = mintEdition(master, number);
+        await builder.rpc();
+
+        const edition = await fetchEdition(newMint);
+        assert.ok(edition.parent.equals(masterEditionPda(master)));
+        assert.equal(edition.edition, number);
+        assert.equal(Number((await getMint(provider.connection, newMint)).supply), 1);
+      }
+    });
+
+    it("rejects an edition number above the master's max supply", async () => {
+      const master = await mintMaster(2);
+      await expectError(mintEdition(master, 3).builder.rpc(), "EditionOutOfRange");
+    });
+  });
+
+  describe("transfer", () => {
+    // Metaplex `FreezeDelegatedAccount` (instruction 26); the test freezes the
+    // owner's account the way a staking or marketplace program would.
+    function freezeDelegated(mint: anchor.web3.PublicKey, delegate: anchor.web3.PublicKey) {
+      return new anchor.web3.TransactionInstruction({
+        programId: TOKEN_METADATA_PROGRAM_ID,
+        keys: [
+          { pubkey: delegate, isSigner: true, isWritable: false },
+          {
+            pubkey: getAssociatedTokenAddressSync(mint, owner),
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: masterEditionPda(mint), isSigner: false, isWritable: false },
+          { pubkey: mint, isSigner: false, isWritable: false },
+          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.from([26]),
+      });
+    }
+
+    async function recipientAccount(mint: anchor.web3.PublicKey, recipient: anchor.web3.PublicKey) {
+      return (await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, recipient))
+        .address;
+    }
+
+    function transferNft(
+      mint: anchor.web3.PublicKey,
+      recipient: anchor.web3.PublicKey,
+      destination: anchor.web3.PublicKey,
+      delegate: anchor.web3.PublicKey | null = null,
+      refreeze = false
+    ) {
+      return program.methods.transferNft(refreeze).accountsPartial({
+        mint,
+        source: getAssociatedTokenAddressSync(mint, owner),
+        destination,
+        recipient,
+        metadata: metadataPda(mint),
+        masterEdition: masterEditionPda(mint),
+        owner,
+        delegate,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      });
+    }
+
+    it("transfers an unfrozen NFT with a plain SPL transfer", async () => {
+      const mint = anchor.web3.Keypair.generate();
+      await mintNft(mint).rpc();
+      const recipient = anchor.web3.Keypair.generate().publicKey;
+      const destination = await recipientAccount(mint.publicKey, recipient);
+
+      await transferNft(mint.publicKey, recipient, destination).rpc();
+
+      assert.equal(Number((await getAccount(provider.connection, destination)).amount), 1);
+      const source = getAssociatedTokenAddressSync(mint.publicKey, owner);
+      assert.equal(Number((await getAccount(provider.connection, source)).amount), 0);
+    });
+
+    it("thaws, transfers and re-freezes a delegate-frozen NFT", async () => {
+      const mint = anchor.web3.Keypair.generate();
+      await mintNft(mint).rpc();
+      const source = getAssociatedTokenAddressSync(mint.publicKey, owner);
+      const delegate = anchor.web3.Keypair.generate();
+      await approve(provider.connection, payer, source, delegate.publicKey, payer, 1);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(freezeDelegated(mint.publicKey, delegate.publicKey)),
+        [delegate]
+      );
+      const recipient = anchor.web3.Keypair.generate();
+      const destination = await recipientAccount(mint.publicKey, recipient.publicKey);
+
+      await expectError(
+        transferNft(mint.publicKey, recipient.publicKey, destination).rpc(),
+        "MissingFreezeDelegate"
+      );
+      await transferNft(mint.publicKey, recipient.publicKey, destination, delegate.publicKey, true)
+        .signers([delegate, recipient])
+        .rpc();
+
+      const received = await getAccount(provider.connection, destination);
+      assert.equal(Number(received.amount), 1);
+      assert.isTrue(received.isFrozen);
+      assert.ok(received.delegate?.equals(delegate.publicKey));
+    });
+  });
+
+  describe("collections", () => {
+    it("verifies an item into a collection", async () => {
+      const collectionMint = await mintCollection();
+      const item = anchor.web3.Keypair.generate();
+      await mintNft(item).rpc();
+
+      await setCollection(item.publicKey, collectionMint).rpc();
+
+      const { collection } = await fetchMetadata(item.publicKey);
+      assert.isNotNull(collection);
+      assert.ok(collection!.key.equals(collectionMint));
+      assert.isTrue(collection!.verified);
+    });
+
+    it("rejects a signer that is not the collection authority", async () => {
+      const collectionMint = await mintCollection();
+      const item = anchor.web3.Keypair.generate();
+      await mintNft(item).rpc();
+
+      await expectError(
+        setCollection(item.publicKey, collectionMint, anchor.web3.Keypair.generate()).rpc(),
+        "UnauthorizedCollectionAuthority"
+      );
+      assert.isNull((await fetchMetadata(item.publicKey)).collection);
+    });
+  });
+});
@@ -0,0 +1,151 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+type PublicKey = anchor.web3.PublicKey;
+type Bytes = Buffer | string;
+
+/** Mirrors `Record::KIND_*`; the program checks `value` against the kind. */
+export const ValueKind = { Raw: 0, Utf8: 1, Pubkey: 2, U64: 3 } as const;
+export type ValueKind = (typeof ValueKind)[keyof typeof ValueKind];
+
+export type RegistryEntry = {
+  key: Bytes;
+  value: Bytes;
+  valueKind?: ValueKind;
+  label?: string;
+  expectedVersion?: anchor.BN;
+};
+
+/** Mirrors `REGISTRY_SEED` in `programs/{{PROGRAM_NAME_SNAKE}}/src/constants.rs`. */
+export const REGISTRY_SEED = Buffer.from("registry");
+
+function toBuffer(value: Bytes): Buffer {
+  return typeof value === "string" ? Buffer.from(value) : value;
+}
+
+/** Record PDA for `[b"registry", authority, key]`. */
+export function recordAddress(programId: PublicKey, authority: PublicKey, key: Bytes): PublicKey {
+  const [address] = anchor.web3.PublicKey.findProgramAddressSync(
+    [REGISTRY_SEED, authority.toBuffer(), toBuffer(key)],
+    programId
+  );
+  return address;
+}
+
+/** Global record PDA for `[b"registry", key]`, shared by every authority. */
+export function globalRecordAddress(programId: PublicKey, key: Bytes): PublicKey {
+  const [address] = anchor.web3.PublicKey.findProgramAddressSync(
+    [REGISTRY_SEED, toBuffer(key)],
+    programId
+  );
+  return address;
+}
+
+/**
+ * Typed helpers for every {{PROGRAM_NAME_SNAKE}} instruction. Each returns the
+ * Anchor method builder, so callers finish with `.rpc()` or `.instruction()`.
+ * Writes take the record's current version, which `version` reads for you.
+ */
+export function {{PROGRAM_NAME_CAMEL}}Client(
+  program: Program<{{PROGRAM_NAME_PASCAL}}>,
+  authority: PublicKey
+) {
+  const record = (key: Bytes, owner: PublicKey = authority) =>
+    recordAddress(program.programId, owner, key);
+
+  return {
+    program,
+    authority,
+    recordAddress: record,
+
+    fetch: (key: Bytes, owner: PublicKey = authority) =>
+      program.account.record.fetchNullable(record(key, owner)),
+
+    /** Version to pass as `expectedVersion`: 0 until the record exists. */
+    version: async (key: Bytes): Promise<anchor.BN> => {
+      const current = await program.account.record.fetchNullable(record(key));
+      return current ? current.version : new anchor.BN(0);
+    },
+
+    upsert: (
+      key: Bytes,
+      value: Bytes,
+      expectedVersion: anchor.BN,
+      label = "",
+      valueKind: ValueKind = ValueKind.Raw
+    ) =>
+      program.methods
+        .upsert(toBuffer(key), toBuffer(value), valueKind, label, expectedVersion)
+        .accountsPartial({ record: record(key), authority }),
+
+    /** Creates a record sized to this entry; keep writing it with `upsertSized`. */
+    createSized: (key: Bytes, value: Bytes, label = "", valueKind: ValueKind = ValueKind.Raw) =>
+      program.methods
+        .createSized(toBuffer(key), toBuffer(value), valueKind, label)
+        .accountsPartial({ record: record(key), authority }),
+
+    /** Reallocates the record to fit `value`; the authority pays or is refunded the rent delta. */
+    upsertSized: (
+      key: Bytes,
+      value: Bytes,
+      expectedVersion: anchor.BN,
+      label = "",
+      valueKind: ValueKind = ValueKind.Raw
+    ) =>
+      program.methods
+        .upsertSized(toBuffer(key), toBuffer(value), valueKind, label, expectedVersion)
+        .accountsPartial({ record: record(key), authority }),
+
+    upsertMany: (entries: RegistryEntry[]) =>
+      program.methods
+        .upsertMany(
+          entries.map((entry) => ({
+            key: toBuffer(entry.key),
+            value: toBuffer(entry.value),
+            valueKind: entry.valueKind ?? ValueKind.Raw,
+            label: entry.label ?? "",
+            expectedVersion: entry.expectedVersion ?? new anchor.BN(0),
+          }))
+        )
+        .accountsPartial({ authority })
+        .remainingAccounts(
+          entries.map((entry) => ({
+            pubkey: record(entry.key),
+            isWritable: true,
+            isSigner: false,
+          }))
+        ),
+
+    freeze: (key: Bytes) =>
+      program.methods.freeze(toBuffer(key)).accountsPartial({ record: record(key), authority }),
+
+    transferRecord: (key: Bytes, newAuthority: PublicKey) =>
+      program.methods.transferRecord(toBuffer(key), newAuthority).accountsPartial({
+        record: record(key),
+        newRecord: record(key, newAuthority),
+        authority,
+      }),
+
+    remove: (key: Bytes) =>
+      program.methods.remove(toBuffer(key)).accountsPartial({ record: record(key), authority }),
+
+    globalRecordAddress: (key: Bytes) => globalRecordAddress(program.programId, key),
+
+    upsertGlobal: (
+      key: Bytes,
+      value: Bytes,
+      expectedVersion: anchor.BN,
+      label = "",
+      valueKind: ValueKind = ValueKind.Raw
+    ) =>
+      program.methods
+        .upsertGlobal(toBuffer(key), toBuffer(value), valueKind, label, expectedVersion)
+        .accountsPartial({ record: globalRecordAddress(program.programId, key), authority }),
+
+    removeGlobal: (key: Bytes) =>
+      program.methods
+        .removeGlobal(toBuffer(key))
+        .accountsPartial({ record: globalRecordAddress(program.programId, key), authority }),
+  };
+}
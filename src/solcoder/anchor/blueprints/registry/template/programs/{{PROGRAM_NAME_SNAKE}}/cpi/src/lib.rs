@@ -0,0 +1,243 @@
+//! Instruction builders for `{{PROGRAM_NAME_SNAKE}}`, for programs that call it
+//! via CPI and for off-chain Rust clients. Each function returns a plain
+//! `Instruction`; on-chain callers pass it to `invoke` or `invoke_signed` together
+//! with the account infos listed in its `accounts`.
+//!
+//! The crate does not depend on the program itself, so linking it never pulls in
+//! a second entrypoint, and it only needs `alloc`. Seeds come from the program's
+//! own `constants.rs`, so both derive the same record PDAs.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::{pubkey, system_program};
+use anchor_lang::AnchorSerialize;
+
+#[path = "../../src/constants.rs"]
+mod constants;
+pub use constants::*;
+
+/// Program id of `{{PROGRAM_NAME_SNAKE}}`; keep in sync with its `declare_id!`.
+pub const ID: Pubkey = pubkey!("{{PROGRAM_ID}}");
+
+/// Mirrors the program's `Record::KIND_*` constants.
+pub const KIND_RAW: u8 = 0;
+pub const KIND_UTF8: u8 = 1;
+pub const KIND_PUBKEY: u8 = 2;
+pub const KIND_U64: u8 = 3;
+
+/// Record PDA for `[REGISTRY_SEED, authority, key]`; `key` must be at most
+/// `MAX_SEED_KEY` bytes, as for every record.
+pub fn record_address(authority: &Pubkey, key: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED, authority.as_ref(), key], &ID)
+}
+
+/// Global record PDA for `[REGISTRY_SEED, key]`, shared by every authority.
+pub fn global_record_address(key: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED, key], &ID)
+}
+
+/// One `upsert_many` entry, encoded exactly like the program's `RecordEntry`.
+#[derive(AnchorSerialize, Clone)]
+pub struct RecordEntry {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    pub value_kind: u8,
+    pub label: String,
+    pub expected_version: u64,
+}
+
+/// `expected_version` is the record's current version, or 0 to create it.
+pub fn upsert(
+    authority: &Pubkey,
+    key: &[u8],
+    value: &[u8],
+    value_kind: u8,
+    label: &str,
+    expected_version: u64,
+) -> Instruction {
+    instruction(
+        "upsert",
+        (
+            key.to_vec(),
+            value.to_vec(),
+            value_kind,
+            String::from(label),
+            expected_version,
+        ),
+        record_accounts(record_address(authority, key).0, authority),
+    )
+}
+
+pub fn create_sized(
+    authority: &Pubkey,
+    key: &[u8],
+    value: &[u8],
+    value_kind: u8,
+    label: &str,
+) -> Instruction {
+    instruction(
+        "create_sized",
+        (
+            key.to_vec(),
+            value.to_vec(),
+            value_kind,
+            String::from(label),
+        ),
+        record_accounts(record_address(authority, key).0, authority),
+    )
+}
+
+pub fn upsert_sized(
+    authority: &Pubkey,
+    key: &[u8],
+    value: &[u8],
+    value_kind: u8,
+    label: &str,
+    expected_version: u64,
+) -> Instruction {
+    instruction(
+        "upsert_sized",
+        (
+            key.to_vec(),
+            value.to_vec(),
+            value_kind,
+            String::from(label),
+            expected_version,
+        ),
+        record_accounts(record_address(authority, key).0, authority),
+    )
+}
+
+/// Passes each entry's record PDA as a writable remaining account, in order.
+pub fn upsert_many(authority: &Pubkey, entries: &[RecordEntry]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(
+        entries
+            .iter()
+            .map(|entry| AccountMeta::new(record_address(authority, &entry.key).0, false)),
+    );
+    instruction("upsert_many", entries.to_vec(), accounts)
+}
+
+pub fn freeze(authority: &Pubkey, key: &[u8]) -> Instruction {
+    instruction(
+        "freeze",
+        key.to_vec(),
+        vec![
+            AccountMeta::new(record_address(authority, key).0, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    )
+}
+
+pub fn transfer_record(authority: &Pubkey, key: &[u8], new_authority: &Pubkey) -> Instruction {
+    instruction(
+        "transfer_record",
+        (key.to_vec(), *new_authority),
+        vec![
+            AccountMeta::new(record_address(authority, key).0, false),
+            AccountMeta::new(record_address(new_authority, key).0, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn remove(authority: &Pubkey, key: &[u8]) -> Instruction {
+    instruction(
+        "remove",
+        key.to_vec(),
+        vec![
+            AccountMeta::new(record_address(authority, key).0, false),
+            AccountMeta::new(*authority, true),
+        ],
+    )
+}
+
+pub fn upsert_global(
+    authority: &Pubkey,
+    key: &[u8],
+    value: &[u8],
+    value_kind: u8,
+    label: &str,
+    expected_version: u64,
+) -> Instruction {
+    instruction(
+        "upsert_global",
+        (
+            key.to_vec(),
+            value.to_vec(),
+            value_kind,
+            String::from(label),
+            expected_version,
+        ),
+        record_accounts(global_record_address(key).0, authority),
+    )
+}
+
+pub fn remove_global(authority: &Pubkey, key: &[u8]) -> Instruction {
+    instruction(
+        "remove_global",
+        key.to_vec(),
+        vec![
+            AccountMeta::new(global_record_address(key).0, false),
+            AccountMeta::new(*authority, true),
+        ],
+    )
+}
+
+/// Accounts shared by the writes that may create the record: record, payer, system program.
+fn record_accounts(record: Pubkey, authority: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(record, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ]
+}
+
+/// Anchor instruction data: `sha256("global:<name>")[..8]` followed by the Borsh-encoded arguments.
+fn instruction(name: &str, args: impl AnchorSerialize, accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = hashv(&[b"global:", name.as_bytes()]).to_bytes()[..8].to_vec();
+    args.serialize(&mut data)
+        .expect("serializing into a Vec cannot fail");
+    Instruction {
+        program_id: ID,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upsert_targets_the_record_pda() {
+        let authority = Pubkey::new_unique();
+        let (record, _) = record_address(&authority, b"name");
+
+        let ix = upsert(&authority, b"name", b"alice", KIND_UTF8, "", 0);
+
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(record, false),
+                AccountMeta::new(authority, true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        );
+        assert_eq!(ix.data[..8], [174, 55, 164, 168, 242, 93, 194, 123]);
+    }
+}
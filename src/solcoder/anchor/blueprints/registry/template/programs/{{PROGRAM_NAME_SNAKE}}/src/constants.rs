@@ -0,0 +1,11 @@
+//! PDA seed prefixes and limits. `app/client.ts` exports the same prefix bytes as `Buffer`s, so
+//! change both together when renaming a seed. The `cpi` crate includes this file.
+
+/// Prefix of every record PDA: `[REGISTRY_SEED, authority, key]` for personal
+/// records and `[REGISTRY_SEED, key]` for global ones.
+pub const REGISTRY_SEED: &[u8] = b"registry";
+
+/// Longest `key` a record PDA can be seeded with. Solana limits every seed to 32
+/// bytes (`MAX_SEED_LEN`), below the 64 bytes `Record::MAX_KEY` reserves for
+/// storage, so keys of 33 to 64 bytes fail with `KeyTooLongForSeed`.
+pub const MAX_SEED_KEY: usize = 32;
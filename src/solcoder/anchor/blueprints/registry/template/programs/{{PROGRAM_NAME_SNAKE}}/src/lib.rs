@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+mod constants;
+pub use constants::*;
 
 declare_id!("{{PROGRAM_ID}}");
 
@@ -6,30 +10,394 @@ declare_id!("{{PROGRAM_ID}}");
 pub mod {{PROGRAM_NAME_SNAKE}} {
     use super::*;
 
-    pub fn upsert(ctx: Context<Upsert>, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+    /// Optimistic concurrency: clients fetch the record, pass its current `version`
+    /// as `expected_version` (0 for a record that does not exist yet), and retry
+    /// with a fresh read on `VersionMismatch`. `label` is a human-readable name of
+    /// up to `Record::MAX_LABEL` bytes; pass an empty string for none. `value_kind`
+    /// is one of the `Record::KIND_*` constants and `value` must match it. The
+    /// signer becomes the authority of a new record; an existing record keeps its
+    /// authority and rejects any other signer with `Unauthorized`.
+    pub fn upsert(
+        ctx: Context<Upsert>,
+        key: Vec<u8>,
+        value: Vec<u8>,
+        value_kind: u8,
+        label: String,
+        expected_version: u64,
+    ) -> Result<()> {
+        validate_entry(&key, &value, value_kind, &label)?;
+        let now = Clock::get()?.unix_timestamp;
+        let authority = ctx.accounts.authority.key();
+        write_record(
+            &mut ctx.accounts.record,
+            authority,
+            key,
+            value,
+            value_kind,
+            label,
+            expected_version,
+            now,
+        )
+    }
+
+    /// Rent-aware variant of `upsert` for records created by `create_sized`: the
+    /// account is reallocated to `Record::space_for` the new key, value and label. The
+    /// authority pays the rent for growth and receives the excess lamports back
+    /// when the record shrinks; it must be a writable signer either way. Shrinking
+    /// only refunds what is above the rent-exempt minimum for the new size.
+    pub fn upsert_sized(
+        ctx: Context<UpsertSized>,
+        key: Vec<u8>,
+        value: Vec<u8>,
+        value_kind: u8,
+        label: String,
+        expected_version: u64,
+    ) -> Result<()> {
+        validate_entry(&key, &value, value_kind, &label)?;
+        let now = Clock::get()?.unix_timestamp;
+        let authority = ctx.accounts.authority.key();
+        write_record(
+            &mut ctx.accounts.record,
+            authority,
+            key,
+            value,
+            value_kind,
+            label,
+            expected_version,
+            now,
+        )
+    }
+
+    /// Creates a record sized for this entry instead of `Record::SIZE`, so small
+    /// values pay proportionally less rent. Later writes must go through
+    /// `upsert_sized`; `upsert` and `upsert_many` assume a full-size account.
+    pub fn create_sized(
+        ctx: Context<CreateSized>,
+        key: Vec<u8>,
+        value: Vec<u8>,
+        value_kind: u8,
+        label: String,
+    ) -> Result<()> {
+        validate_entry(&key, &value, value_kind, &label)?;
+        let now = Clock::get()?.unix_timestamp;
+        let authority = ctx.accounts.authority.key();
+        write_record(
+            &mut ctx.accounts.record,
+            authority,
+            key,
+            value,
+            value_kind,
+            label,
+            0,
+            now,
+        )
+    }
+
+    /// Writes up to `Record::MAX_BATCH` entries in one transaction. Pass one
+    /// writable record PDA per entry in `remaining_accounts`, in the same order as
+    /// `entries`; each must derive from `[b"registry", authority, entry.key]`.
+    /// Missing records are created with the authority paying rent. The whole batch
+    /// is validated before any record is written.
+    pub fn upsert_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpsertMany<'info>>,
+        entries: Vec<RecordEntry>,
+    ) -> Result<()> {
+        require!(
+            entries.len() <= Record::MAX_BATCH,
+            RegistryError::BatchTooLarge
+        );
+        require!(
+            ctx.remaining_accounts.len() == entries.len(),
+            RegistryError::BatchAccountsMismatch
+        );
+        let authority = ctx.accounts.authority.key();
+        let mut bumps = Vec::with_capacity(entries.len());
+        for (entry, info) in entries.iter().zip(ctx.remaining_accounts) {
+            validate_entry(&entry.key, &entry.value, entry.value_kind, &entry.label)?;
+            let (expected, bump) = Pubkey::find_program_address(
+                &[REGISTRY_SEED, authority.as_ref(), key_seed(&entry.key)?],
+                ctx.program_id,
+            );
+            require_keys_eq!(info.key(), expected, RegistryError::InvalidRecordAccount);
+            require!(info.is_writable, RegistryError::InvalidRecordAccount);
+            bumps.push(bump);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        for ((entry, info), bump) in entries.into_iter().zip(ctx.remaining_accounts).zip(bumps) {
+            let mut record = if info.data_is_empty() {
+                let space = 8 + Record::SIZE;
+                system_program::create_account(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::CreateAccount {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: info.clone(),
+                        },
+                        &[&[
+                            REGISTRY_SEED,
+                            authority.as_ref(),
+                            entry.key.as_ref(),
+                            &[bump],
+                        ]],
+                    ),
+                    Rent::get()?.minimum_balance(space),
+                    space as u64,
+                    ctx.program_id,
+                )?;
+                // The new account is zeroed and has no discriminator yet; `exit` writes it.
+                Account::<Record>::try_from_unchecked(info)?
+            } else {
+                Account::<Record>::try_from(info)?
+            };
+            write_record(
+                &mut record,
+                authority,
+                entry.key,
+                entry.value,
+                entry.value_kind,
+                entry.label,
+                entry.expected_version,
+                now,
+            )?;
+            record.exit(ctx.program_id)?;
+        }
+        Ok(())
+    }
+
+    /// Makes the record write-once. There is intentionally no unfreeze path, so a
+    /// frozen record cannot be removed, transferred away or re-created either.
+    pub fn freeze(ctx: Context<Freeze>, _key: Vec<u8>) -> Result<()> {
         let record = &mut ctx.accounts.record;
-        record.authority = ctx.accounts.authority.key();
-        record.key = key;
-        record.value = value;
+        require_keys_eq!(
+            record.authority,
+            ctx.accounts.authority.key(),
+            RegistryError::Unauthorized
+        );
+        record.frozen = true;
+        Ok(())
+    }
+
+    /// Records are seeded by their authority, so a transfer reseeds the record: the
+    /// data moves to `[b"registry", new_authority, key]` and the old PDA is closed
+    /// back to the current authority, who also pays rent for the new account.
+    pub fn transfer_record(
+        ctx: Context<TransferRecord>,
+        _key: Vec<u8>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let record = &ctx.accounts.record;
+        require!(!record.frozen, RegistryError::RecordFrozen);
+        require_keys_eq!(
+            record.authority,
+            ctx.accounts.authority.key(),
+            RegistryError::Unauthorized
+        );
+        let new_record = &mut ctx.accounts.new_record;
+        new_record.authority = new_authority;
+        new_record.key = record.key.clone();
+        new_record.value = record.value.clone();
+        new_record.value_kind = record.value_kind;
+        new_record.label = record.label.clone();
+        new_record.created_at = record.created_at;
+        new_record.updated_at = Clock::get()?.unix_timestamp;
+        new_record.version = record.version;
+        new_record.frozen = record.frozen;
+        Ok(())
+    }
+
+    /// The seeds already bind the record to its authority; the explicit check keeps
+    /// `remove` safe if the seed scheme ever changes.
+    pub fn remove(ctx: Context<Remove>, key: Vec<u8>) -> Result<()> {
+        require!(!ctx.accounts.record.frozen, RegistryError::RecordFrozen);
+        require_keys_eq!(
+            ctx.accounts.record.authority,
+            ctx.accounts.authority.key(),
+            RegistryError::Unauthorized
+        );
+        emit!(RecordRemoved {
+            authority: ctx.accounts.authority.key(),
+            key,
+        });
         Ok(())
     }
 
-    pub fn remove(_ctx: Context<Remove>) -> Result<()> {
+    /// Program-wide namespace seeded by `[b"registry", key]`, so every user sees
+    /// the same record for a key. The first writer becomes its authority; later
+    /// writes from anyone else fail with `KeyTaken` until the owner removes it.
+    pub fn upsert_global(
+        ctx: Context<UpsertGlobal>,
+        key: Vec<u8>,
+        value: Vec<u8>,
+        value_kind: u8,
+        label: String,
+        expected_version: u64,
+    ) -> Result<()> {
+        validate_entry(&key, &value, value_kind, &label)?;
+        let authority = ctx.accounts.authority.key();
+        let record = &mut ctx.accounts.record;
+        // A claimed key rejects other signers here, before `write_record` would
+        // report them as Unauthorized.
+        if !is_uninitialized(record) {
+            require_keys_eq!(record.authority, authority, RegistryError::KeyTaken);
+        }
+        let now = Clock::get()?.unix_timestamp;
+        write_record(
+            record,
+            authority,
+            key,
+            value,
+            value_kind,
+            label,
+            expected_version,
+            now,
+        )
+    }
+
+    /// Closes a global record; only its first writer may remove it, after which
+    /// the key can be claimed again.
+    pub fn remove_global(ctx: Context<RemoveGlobal>, key: Vec<u8>) -> Result<()> {
+        require!(!ctx.accounts.record.frozen, RegistryError::RecordFrozen);
+        require_keys_eq!(
+            ctx.accounts.record.authority,
+            ctx.accounts.authority.key(),
+            RegistryError::Unauthorized
+        );
+        emit!(RecordRemoved {
+            authority: ctx.accounts.authority.key(),
+            key,
+        });
         Ok(())
     }
 }
 
+/// `key` as a record PDA seed. Solana caps each seed at `MAX_SEED_KEY` bytes,
+/// so a longer key would make PDA derivation fail with an opaque runtime error.
+/// The `seeds` constraints call this, which rejects such keys with
+/// `KeyTooLongForSeed` while the accounts are validated, before any handler
+/// body runs; a `require!` inside `upsert` or `remove` would come too late.
+fn key_seed(key: &[u8]) -> Result<&[u8]> {
+    require!(key.len() <= MAX_SEED_KEY, RegistryError::KeyTooLongForSeed);
+    Ok(key)
+}
+
+fn validate_entry(key: &[u8], value: &[u8], value_kind: u8, label: &str) -> Result<()> {
+    require!(!key.is_empty(), RegistryError::EmptyKey);
+    require!(key.len() <= Record::MAX_KEY, RegistryError::KeyTooLong);
+    require!(
+        value.len() <= Record::MAX_VALUE,
+        RegistryError::ValueTooLong
+    );
+    require!(
+        label.len() <= Record::MAX_LABEL,
+        RegistryError::LabelTooLong
+    );
+    let valid = match value_kind {
+        Record::KIND_RAW => true,
+        Record::KIND_UTF8 => std::str::from_utf8(value).is_ok(),
+        Record::KIND_PUBKEY => value.len() == 32,
+        Record::KIND_U64 => value.len() == 8,
+        _ => false,
+    };
+    require!(valid, RegistryError::InvalidValueForKind);
+    Ok(())
+}
+
+/// Fresh accounts are zeroed, and every write sets a non-default authority.
+fn is_uninitialized(record: &Record) -> bool {
+    record.authority == Pubkey::default()
+}
+
+/// Shared by every write: enforces the frozen flag, the stored authority and
+/// `expected_version`, then writes the record and emits `RecordUpserted`.
+fn write_record(
+    record: &mut Record,
+    authority: Pubkey,
+    key: Vec<u8>,
+    value: Vec<u8>,
+    value_kind: u8,
+    label: String,
+    expected_version: u64,
+    now: i64,
+) -> Result<()> {
+    require!(!record.frozen, RegistryError::RecordFrozen);
+    // `init_if_needed` hands this function existing records as well as new ones, so
+    // an unset authority is the only sign of a first write. Only then are the fields
+    // that never change set; every later call is an update by the same authority,
+    // even under a looser seed scheme that let another signer reach the account.
+    if is_uninitialized(record) {
+        record.authority = authority;
+        record.created_at = now;
+    } else {
+        require_keys_eq!(record.authority, authority, RegistryError::Unauthorized);
+    }
+    require!(
+        record.version == expected_version,
+        RegistryError::VersionMismatch
+    );
+    record.version = record
+        .version
+        .checked_add(1)
+        .ok_or(RegistryError::VersionMismatch)?;
+    record.updated_at = now;
+    emit!(RecordUpserted {
+        authority,
+        key: key.clone(),
+        value_len: value.len() as u32,
+        version: record.version,
+    });
+    record.key = key;
+    record.value = value;
+    record.value_kind = value_kind;
+    record.label = label;
+    Ok(())
+}
+
 #[account]
 pub struct Record {
     pub authority: Pubkey,
     pub key: Vec<u8>,
     pub value: Vec<u8>,
+    /// How `value` is interpreted; one of the `Record::KIND_*` constants.
+    pub value_kind: u8,
+    /// Optional human-readable name; empty when unset.
+    pub label: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    /// Incremented on every successful `upsert`; zero until the first write.
+    pub version: u64,
+    pub frozen: bool,
 }
 
 impl Record {
+    /// Storage reserved for the key. Keys also seed the record PDA, which limits
+    /// them to `MAX_SEED_KEY` (32) bytes, so only half of this is usable today.
     pub const MAX_KEY: usize = 64;
-    pub const MAX_VALUE: usize = 256;
-    pub const SIZE: usize = 32 + 4 + Self::MAX_KEY + 4 + Self::MAX_VALUE;
+    pub const MAX_LABEL: usize = 32;
+    /// Arbitrary bytes.
+    pub const KIND_RAW: u8 = 0;
+    /// Valid UTF-8 text.
+    pub const KIND_UTF8: u8 = 1;
+    /// Exactly 32 bytes.
+    pub const KIND_PUBKEY: u8 = 2;
+    /// Exactly 8 bytes, a little-endian u64.
+    pub const KIND_U64: u8 = 3;
+    /// Chosen at scaffold time; capped at 10_000 so `8 + SIZE` stays under the
+    /// 10 KiB limit for accounts created via CPI.
+    pub const MAX_VALUE: usize = {{REGISTRY_MAX_VALUE}};
+    /// Upper bound on `upsert_many` entries; each one adds an account to the transaction.
+    pub const MAX_BATCH: usize = 8;
+    pub const SIZE: usize =
+        32 + 4 + Self::MAX_KEY + 4 + Self::MAX_VALUE + 1 + 4 + Self::MAX_LABEL + 8 + 8 + 8 + 1;
+
+    /// Account space (discriminator included) for a record holding exactly these
+    /// lengths; used by `create_sized` and `upsert_sized`.
+    pub fn space_for(key_len: usize, value_len: usize, label_len: usize) -> usize {
+        8 + Self::SIZE - Self::MAX_KEY - Self::MAX_VALUE - Self::MAX_LABEL
+            + key_len
+            + value_len
+            + label_len
+    }
 }
 
 #[derive(Accounts)]
@@ -39,7 +407,7 @@ pub struct Upsert<'info> {
         init_if_needed,
         payer = authority,
         space = 8 + Record::SIZE,
-        seeds = [b"registry", authority.key().as_ref(), key.as_ref()],
+        seeds = [REGISTRY_SEED, authority.key().as_ref(), key_seed(&key)?],
         bump
     )]
     pub record: Account<'info, Record>,
@@ -48,16 +416,179 @@ pub struct Upsert<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(key: Vec<u8>, value: Vec<u8>, value_kind: u8, label: String)]
+pub struct CreateSized<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Record::space_for(key.len(), value.len(), label.len()),
+        seeds = [REGISTRY_SEED, authority.key().as_ref(), key_seed(&key)?],
+        bump
+    )]
+    pub record: Account<'info, Record>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `realloc` charges `authority` when the record grows and refunds it when the
+/// record shrinks, always leaving the account rent-exempt at its new size.
+#[derive(Accounts)]
+#[instruction(key: Vec<u8>, value: Vec<u8>, value_kind: u8, label: String)]
+pub struct UpsertSized<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref(), key_seed(&key)?],
+        bump,
+        realloc = Record::space_for(key.len(), value.len(), label.len()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub record: Account<'info, Record>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// One `upsert_many` entry; `expected_version` follows the same rules as `upsert`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecordEntry {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    pub value_kind: u8,
+    pub label: String,
+    pub expected_version: u64,
+}
+
+/// Record PDAs are passed through `remaining_accounts`; see `upsert_many`.
+#[derive(Accounts)]
+pub struct UpsertMany<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Vec<u8>)]
+pub struct Freeze<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref(), key_seed(&key)?],
+        bump
+    )]
+    pub record: Account<'info, Record>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Vec<u8>, new_authority: Pubkey)]
+pub struct TransferRecord<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [REGISTRY_SEED, authority.key().as_ref(), key_seed(&key)?],
+        bump
+    )]
+    pub record: Account<'info, Record>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Record::SIZE,
+        seeds = [REGISTRY_SEED, new_authority.as_ref(), key_seed(&key)?],
+        bump
+    )]
+    pub new_record: Account<'info, Record>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(key: Vec<u8>)]
 pub struct Remove<'info> {
     #[account(
         mut,
         close = authority,
-        seeds = [b"registry", authority.key().as_ref(), key.as_ref()],
+        seeds = [REGISTRY_SEED, authority.key().as_ref(), key_seed(&key)?],
+        bump
+    )]
+    pub record: Account<'info, Record>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Vec<u8>)]
+pub struct UpsertGlobal<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Record::SIZE,
+        seeds = [REGISTRY_SEED, key_seed(&key)?],
+        bump
+    )]
+    pub record: Account<'info, Record>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Vec<u8>)]
+pub struct RemoveGlobal<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [REGISTRY_SEED, key_seed(&key)?],
         bump
     )]
     pub record: Account<'info, Record>,
     #[account(mut)]
     pub authority: Signer<'info>,
 }
+
+/// Carries `value_len` instead of the value bytes to keep program logs small.
+#[event]
+pub struct RecordUpserted {
+    pub authority: Pubkey,
+    pub key: Vec<u8>,
+    pub value_len: u32,
+    pub version: u64,
+}
+
+#[event]
+pub struct RecordRemoved {
+    pub authority: Pubkey,
+    pub key: Vec<u8>,
+}
+
+#[error_code]
+pub enum RegistryError {
+    #[msg("Record key must not be empty.")]
+    EmptyKey,
+    #[msg("Record key exceeds 64 bytes.")]
+    KeyTooLong,
+    #[msg("Record value exceeds {{REGISTRY_MAX_VALUE}} bytes.")]
+    ValueTooLong,
+    #[msg("Record label exceeds 32 bytes.")]
+    LabelTooLong,
+    #[msg("Record version does not match expected_version; re-read and retry.")]
+    VersionMismatch,
+    #[msg("Record is frozen and can no longer be modified.")]
+    RecordFrozen,
+    #[msg("Signer is not the record authority.")]
+    Unauthorized,
+    #[msg("Too many entries in a single batch.")]
+    BatchTooLarge,
+    #[msg("Pass exactly one record account per entry in remaining_accounts.")]
+    BatchAccountsMismatch,
+    #[msg("A remaining account does not match the record PDA for its entry.")]
+    InvalidRecordAccount,
+    #[msg("This global key is already claimed by another authority.")]
+    KeyTaken,
+    #[msg("Record value does not match its value_kind.")]
+    InvalidValueForKind,
+    #[msg("Record key exceeds the 32-byte PDA seed limit.")]
+    KeyTooLongForSeed,
+}
@@ -24,6 +24,8 @@ async function main() {
   console.log(`  Value type: ${valueType}`);
   console.log("\nNext steps:");
   console.log("  - Use the upsert/remove instructions in programs/<name>/src/lib.rs.");
+  console.log("  - Use upsert_global/remove_global for keys shared by every authority.");
+  console.log("  - Use create_sized/upsert_sized to pay rent only for the bytes a record uses.");
   console.log("  - After deploy, call /program wizard <PROGRAM_ID> to try instructions.");
 }
 
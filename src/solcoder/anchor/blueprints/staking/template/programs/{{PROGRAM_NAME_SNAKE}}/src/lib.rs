@@ -0,0 +1,341 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("{{PROGRAM_ID}}");
+
+#[program]
+pub mod {{PROGRAM_NAME_SNAKE}} {
+    use super::*;
+
+    /// Creates a pool that takes deposits of `stake_mint` and pays rewards in
+    /// `reward_mint`. Every staked base unit earns `reward_rate / RATE_PRECISION`
+    /// reward base units per second. Anyone can fund rewards by transferring
+    /// `reward_mint` tokens into the reward vault.
+    pub fn initialize_pool(ctx: Context<InitializePool>, reward_rate: u64) -> Result<()> {
+        require!(reward_rate > 0, StakingError::InvalidRewardRate);
+        let pool = &mut ctx.accounts.pool;
+        pool.authority = ctx.accounts.authority.key();
+        pool.stake_mint = ctx.accounts.stake_mint.key();
+        pool.reward_mint = ctx.accounts.reward_mint.key();
+        pool.reward_rate = reward_rate;
+        pool.total_staked = 0;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    /// Moves `amount` into the stake vault. Rewards earned on the existing stake
+    /// are banked first, so topping up never resets accrued rewards.
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.pool;
+        let stake_account = &mut ctx.accounts.stake_account;
+        if stake_account.owner == Pubkey::default() {
+            stake_account.owner = ctx.accounts.owner.key();
+            stake_account.pool = pool.key();
+            stake_account.bump = ctx.bumps.stake_account;
+        }
+        stake_account.accrue(pool.reward_rate, now)?;
+        stake_account.amount = stake_account
+            .amount
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        pool.total_staked = pool
+            .total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_token.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Returns `amount` of the stake and pays out every reward accrued so far.
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.pool;
+        let stake_account = &mut ctx.accounts.stake_account;
+        require!(
+            stake_account.amount >= amount,
+            StakingError::InsufficientStake
+        );
+        stake_account.accrue(pool.reward_rate, now)?;
+        stake_account.amount -= amount;
+        pool.total_staked -= amount;
+
+        transfer_from_pool(
+            &ctx.accounts.token_program,
+            &ctx.accounts.stake_vault,
+            ctx.accounts.owner_token.to_account_info(),
+            pool,
+            amount,
+        )?;
+        pay_rewards(
+            &ctx.accounts.token_program,
+            &ctx.accounts.reward_vault,
+            ctx.accounts.owner_reward_token.to_account_info(),
+            pool,
+            stake_account,
+        )
+    }
+
+    /// Pays out accrued rewards and leaves the stake in place.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &ctx.accounts.pool;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.accrue(pool.reward_rate, now)?;
+
+        pay_rewards(
+            &ctx.accounts.token_program,
+            &ctx.accounts.reward_vault,
+            ctx.accounts.owner_reward_token.to_account_info(),
+            pool,
+            stake_account,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Pool::SIZE,
+        seeds = [b"pool", authority.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    pub stake_mint: Account<'info, Mint>,
+    pub reward_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"stake_vault", pool.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = pool
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward_vault", pool.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = pool
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.authority.as_ref(), pool.stake_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeAccount::SIZE,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake_vault", pool.key().as_ref()], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = pool.stake_mint,
+        token::authority = owner
+    )]
+    pub owner_token: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.authority.as_ref(), pool.stake_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake_vault", pool.key().as_ref()], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"reward_vault", pool.key().as_ref()], bump)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = pool.stake_mint,
+        token::authority = owner
+    )]
+    pub owner_token: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = pool.reward_mint,
+        token::authority = owner
+    )]
+    pub owner_reward_token: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        seeds = [b"pool", pool.authority.as_ref(), pool.stake_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"reward_vault", pool.key().as_ref()], bump)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = pool.reward_mint,
+        token::authority = owner
+    )]
+    pub owner_reward_token: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` out of a pool-owned vault, signing with the pool PDA.
+fn transfer_from_pool<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    pool: &Account<'info, Pool>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let bump = [pool.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pool",
+        pool.authority.as_ref(),
+        pool.stake_mint.as_ref(),
+        &bump,
+    ]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: destination,
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+/// Pays as much of the pending reward as the vault holds; any shortfall stays
+/// pending so an underfunded pool never blocks `unstake`.
+fn pay_rewards<'info>(
+    token_program: &Program<'info, Token>,
+    reward_vault: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    pool: &Account<'info, Pool>,
+    stake_account: &mut Account<'info, StakeAccount>,
+) -> Result<()> {
+    let payout = stake_account.pending_rewards.min(reward_vault.amount);
+    stake_account.pending_rewards -= payout;
+    transfer_from_pool(token_program, reward_vault, destination, pool, payout)
+}
+
+/// `reward_rate` is expressed in reward base units per staked base unit per
+/// second, scaled by this factor.
+pub const RATE_PRECISION: u128 = 1_000_000;
+
+#[account]
+pub struct Pool {
+    pub authority: Pubkey,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    /// Rewards per staked base unit per second, scaled by `RATE_PRECISION`.
+    pub reward_rate: u64,
+    pub total_staked: u64,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const SIZE: usize = 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    /// Rewards accrued but not yet paid out.
+    pub pending_rewards: u64,
+    /// Unix timestamp up to which rewards have been accrued.
+    pub last_update: i64,
+    pub bump: u8,
+}
+
+impl StakeAccount {
+    pub const SIZE: usize = 32 + 32 + 8 + 8 + 8 + 1;
+
+    /// Banks the rewards earned since `last_update` at the pool's current rate.
+    fn accrue(&mut self, reward_rate: u64, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_update).max(0) as u128;
+        let earned = (self.amount as u128)
+            .checked_mul(reward_rate as u128)
+            .and_then(|value| value.checked_mul(elapsed))
+            .map(|value| value / RATE_PRECISION)
+            .ok_or(StakingError::MathOverflow)?;
+        let earned = u64::try_from(earned).map_err(|_| StakingError::MathOverflow)?;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(earned)
+            .ok_or(StakingError::MathOverflow)?;
+        self.last_update = now;
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum StakingError {
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Reward rate must be greater than zero.")]
+    InvalidRewardRate,
+    #[msg("Cannot unstake more than is staked.")]
+    InsufficientStake,
+    #[msg("Reward calculation overflowed.")]
+    MathOverflow,
+}
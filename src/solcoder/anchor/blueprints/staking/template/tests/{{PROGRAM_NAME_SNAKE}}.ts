@@ -0,0 +1,242 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const authority = provider.wallet.publicKey;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  // Mirrors RATE_PRECISION: this rate pays one reward unit per staked unit per second.
+  const RATE_PRECISION = 1_000_000;
+
+  type PoolFixture = {
+    pool: anchor.web3.PublicKey;
+    stakeMint: anchor.web3.PublicKey;
+    rewardMint: anchor.web3.PublicKey;
+  };
+
+  type Staker = {
+    owner: anchor.web3.Keypair;
+    ownerToken: anchor.web3.PublicKey;
+    ownerRewardToken: anchor.web3.PublicKey;
+  };
+
+  function pda(seeds: Buffer[]): anchor.web3.PublicKey {
+    const [address] = anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId);
+    return address;
+  }
+
+  function poolPda(stakeMint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    return pda([Buffer.from("pool"), authority.toBuffer(), stakeMint.toBuffer()]);
+  }
+
+  function stakePda(pool: anchor.web3.PublicKey, owner: anchor.web3.PublicKey) {
+    return pda([Buffer.from("stake"), pool.toBuffer(), owner.toBuffer()]);
+  }
+
+  function stakeVaultPda(pool: anchor.web3.PublicKey) {
+    return pda([Buffer.from("stake_vault"), pool.toBuffer()]);
+  }
+
+  function rewardVaultPda(pool: anchor.web3.PublicKey) {
+    return pda([Buffer.from("reward_vault"), pool.toBuffer()]);
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    return Number((await getAccount(provider.connection, account)).amount);
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  function initializePool(
+    stakeMint: anchor.web3.PublicKey,
+    rewardMint: anchor.web3.PublicKey,
+    rate: number
+  ) {
+    return program.methods
+      .initializePool(new anchor.BN(rate))
+      .accountsPartial({ pool: poolPda(stakeMint), stakeMint, rewardMint, authority });
+  }
+
+  // Every pool gets its own stake mint so tests never share a pool PDA.
+  async function createPool(rate = RATE_PRECISION): Promise<PoolFixture> {
+    const stakeMint = await createMint(provider.connection, payer, authority, null, 6);
+    const rewardMint = await createMint(provider.connection, payer, authority, null, 6);
+    await initializePool(stakeMint, rewardMint, rate).rpc();
+    const pool = poolPda(stakeMint);
+    // Fund rewards generously; payouts are capped by the vault balance.
+    await mintTo(provider.connection, payer, rewardMint, rewardVaultPda(pool), payer, 1e9);
+    return { pool, stakeMint, rewardMint };
+  }
+
+  async function newStaker(fixture: PoolFixture, balance: number): Promise<Staker> {
+    const owner = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(owner.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const ownerToken = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      fixture.stakeMint,
+      owner.publicKey
+    );
+    await mintTo(provider.connection, payer, fixture.stakeMint, ownerToken, payer, balance);
+    const ownerRewardToken = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      fixture.rewardMint,
+      owner.publicKey
+    );
+    return { owner, ownerToken, ownerRewardToken };
+  }
+
+  function stake(fixture: PoolFixture, staker: Staker, amount: number) {
+    return program.methods
+      .stake(new anchor.BN(amount))
+      .accountsPartial({
+        pool: fixture.pool,
+        stakeAccount: stakePda(fixture.pool, staker.owner.publicKey),
+        stakeVault: stakeVaultPda(fixture.pool),
+        ownerToken: staker.ownerToken,
+        owner: staker.owner.publicKey,
+      })
+      .signers([staker.owner]);
+  }
+
+  function unstake(fixture: PoolFixture, staker: Staker, amount: number) {
+    return program.methods
+      .unstake(new anchor.BN(amount))
+      .accountsPartial({
+        pool: fixture.pool,
+        stakeAccount: stakePda(fixture.pool, staker.owner.publicKey),
+        stakeVault: stakeVaultPda(fixture.pool),
+        rewardVault: rewardVaultPda(fixture.pool),
+        ownerToken: staker.ownerToken,
+        ownerRewardToken: staker.ownerRewardToken,
+        owner: staker.owner.publicKey,
+      })
+      .signers([staker.owner]);
+  }
+
+  function claimRewards(fixture: PoolFixture, staker: Staker) {
+    return program.methods
+      .claimRewards()
+      .accountsPartial({
+        pool: fixture.pool,
+        stakeAccount: stakePda(fixture.pool, staker.owner.publicKey),
+        rewardVault: rewardVaultPda(fixture.pool),
+        ownerRewardToken: staker.ownerRewardToken,
+        owner: staker.owner.publicKey,
+      })
+      .signers([staker.owner]);
+  }
+
+  async function lastUpdate(fixture: PoolFixture, staker: Staker): Promise<number> {
+    const account = await program.account.stakeAccount.fetch(
+      stakePda(fixture.pool, staker.owner.publicKey)
+    );
+    return account.lastUpdate.toNumber();
+  }
+
+  it("initializes a pool with empty vaults", async () => {
+    const stakeMint = await createMint(provider.connection, payer, authority, null, 6);
+    const rewardMint = await createMint(provider.connection, payer, authority, null, 6);
+    await initializePool(stakeMint, rewardMint, 42).rpc();
+
+    const pool = await program.account.pool.fetch(poolPda(stakeMint));
+    assert.ok(pool.authority.equals(authority));
+    assert.ok(pool.stakeMint.equals(stakeMint));
+    assert.ok(pool.rewardMint.equals(rewardMint));
+    assert.equal(pool.rewardRate.toNumber(), 42);
+    assert.equal(pool.totalStaked.toNumber(), 0);
+    assert.equal(await tokenBalance(stakeVaultPda(poolPda(stakeMint))), 0);
+  });
+
+  it("rejects a zero reward rate", async () => {
+    const stakeMint = await createMint(provider.connection, payer, authority, null, 6);
+    const rewardMint = await createMint(provider.connection, payer, authority, null, 6);
+    await expectError(initializePool(stakeMint, rewardMint, 0).rpc(), "InvalidRewardRate");
+  });
+
+  it("moves staked tokens into the vault", async () => {
+    const fixture = await createPool();
+    const staker = await newStaker(fixture, 5_000);
+
+    await stake(fixture, staker, 2_000).rpc();
+
+    assert.equal(await tokenBalance(staker.ownerToken), 3_000);
+    assert.equal(await tokenBalance(stakeVaultPda(fixture.pool)), 2_000);
+    const account = await program.account.stakeAccount.fetch(
+      stakePda(fixture.pool, staker.owner.publicKey)
+    );
+    assert.equal(account.amount.toNumber(), 2_000);
+    assert.equal((await program.account.pool.fetch(fixture.pool)).totalStaked.toNumber(), 2_000);
+  });
+
+  it("rejects staking zero tokens", async () => {
+    const fixture = await createPool();
+    const staker = await newStaker(fixture, 1_000);
+    await expectError(stake(fixture, staker, 0).rpc(), "InvalidAmount");
+  });
+
+  it("accrues rewards in proportion to time staked", async () => {
+    const fixture = await createPool();
+    const staker = await newStaker(fixture, 1_000);
+    await stake(fixture, staker, 1_000).rpc();
+    const stakedAt = await lastUpdate(fixture, staker);
+
+    await sleep(2_000);
+    await claimRewards(fixture, staker).rpc();
+
+    const elapsed = (await lastUpdate(fixture, staker)) - stakedAt;
+    assert.isAbove(elapsed, 0);
+    assert.equal(await tokenBalance(staker.ownerRewardToken), 1_000 * elapsed);
+  });
+
+  it("returns the stake and pays rewards on unstake", async () => {
+    const fixture = await createPool();
+    const staker = await newStaker(fixture, 1_000);
+    await stake(fixture, staker, 1_000).rpc();
+    const stakedAt = await lastUpdate(fixture, staker);
+
+    await sleep(1_000);
+    await unstake(fixture, staker, 1_000).rpc();
+
+    const elapsed = (await lastUpdate(fixture, staker)) - stakedAt;
+    assert.equal(await tokenBalance(staker.ownerToken), 1_000);
+    assert.equal(await tokenBalance(stakeVaultPda(fixture.pool)), 0);
+    assert.equal(await tokenBalance(staker.ownerRewardToken), 1_000 * elapsed);
+    assert.equal((await program.account.pool.fetch(fixture.pool)).totalStaked.toNumber(), 0);
+  });
+
+  it("rejects unstaking more than is staked", async () => {
+    const fixture = await createPool();
+    const staker = await newStaker(fixture, 1_000);
+    await stake(fixture, staker, 500).rpc();
+
+    await expectError(unstake(fixture, staker, 501).rpc(), "InsufficientStake");
+  });
+});
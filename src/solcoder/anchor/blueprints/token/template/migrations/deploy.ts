@@ -0,0 +1,62 @@
+// Run by `anchor migrate` with the provider configured in Anchor.toml. On the first
+// run it creates the mint with the scaffolded decimals and mints the initial supply
+// to the wallet; the mint keypair is kept in target/deploy, so later runs find the
+// existing mint and leave it alone.
+
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import * as fs from "fs";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+const PROGRAM_ID = new anchor.web3.PublicKey("{{PROGRAM_ID}}");
+const MINT_KEYPAIR = "target/deploy/{{PROGRAM_NAME_SNAKE}}-mint-keypair.json";
+// TOKEN_DECIMALS and INITIAL_SUPPLY_TOKENS in lib.rs.
+const TOKEN_DECIMALS = {{TOKEN_DECIMALS}};
+const INITIAL_SUPPLY_TOKENS = new anchor.BN("{{TOKEN_INITIAL_SUPPLY}}");
+const UNCAPPED = new anchor.BN("18446744073709551615");
+
+function loadOrCreateMint(): anchor.web3.Keypair {
+  if (fs.existsSync(MINT_KEYPAIR)) {
+    const secret = JSON.parse(fs.readFileSync(MINT_KEYPAIR, "utf8"));
+    return anchor.web3.Keypair.fromSecretKey(Uint8Array.from(secret));
+  }
+  const mint = anchor.web3.Keypair.generate();
+  fs.writeFileSync(MINT_KEYPAIR, JSON.stringify(Array.from(mint.secretKey)));
+  return mint;
+}
+
+module.exports = async function (provider: anchor.AnchorProvider) {
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  if (!program.programId.equals(PROGRAM_ID)) {
+    throw new Error(
+      `Workspace program id ${program.programId.toBase58()} is not ${PROGRAM_ID.toBase58()}.`
+    );
+  }
+  const mint = loadOrCreateMint();
+  if (await provider.connection.getAccountInfo(mint.publicKey)) {
+    console.log(`Mint already exists at ${mint.publicKey.toBase58()}.`);
+    return;
+  }
+  const authority = provider.wallet.publicKey;
+  const supply = INITIAL_SUPPLY_TOKENS.mul(new anchor.BN(10).pow(new anchor.BN(TOKEN_DECIMALS)));
+  const minting = !supply.isZero();
+  await program.methods
+    .initialize(TOKEN_DECIMALS, null, null, UNCAPPED, supply)
+    .accountsPartial({
+      mint: mint.publicKey,
+      authority,
+      metadata: null,
+      tokenMetadataProgram: null,
+      creatorToken: minting ? getAssociatedTokenAddressSync(mint.publicKey, authority) : null,
+      associatedTokenProgram: minting ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
+    })
+    .signers([mint])
+    .rpc();
+  console.log(
+    `Created mint ${mint.publicKey.toBase58()} with ${TOKEN_DECIMALS} decimals and minted ` +
+      `${INITIAL_SUPPLY_TOKENS.toString()} tokens to ${authority.toBase58()}.`
+  );
+};
@@ -1,15 +1,743 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{self, CreateMetadataAccountsV3, Metadata};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{
+    self, Burn, FreezeAccount, InitializeMint, Mint, MintTo, SetAuthority, ThawAccount, Token,
+    TokenAccount, Transfer,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Mint2022State;
+use anchor_spl::token_2022::{self, InitializeMint2, Token2022};
+use anchor_spl::token_2022_extensions::{self, TransferFeeInitialize};
 
 declare_id!("{{PROGRAM_ID}}");
 
+/// Decimals chosen at scaffold time (wizard: decimals); `initialize` takes them as
+/// an argument, so other mints from this program may differ.
+pub const TOKEN_DECIMALS: u8 = {{TOKEN_DECIMALS}};
+/// Whole tokens `migrations/deploy.ts` mints to the creator (wizard: initial_supply).
+pub const INITIAL_SUPPLY_TOKENS: u64 = {{TOKEN_INITIAL_SUPPLY}};
+/// `INITIAL_SUPPLY_TOKENS` in base units; solcoder checks at scaffold time that it
+/// fits in a u64.
+pub const INITIAL_SUPPLY: u64 = INITIAL_SUPPLY_TOKENS * 10u64.pow(TOKEN_DECIMALS as u32);
+
 #[program]
 pub mod {{PROGRAM_NAME_SNAKE}} {
     use super::*;
 
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
+    /// Creates a new SPL mint whose mint authority is the `[b"config", mint]` PDA,
+    /// so tokens can only be minted through this program; the signer is recorded
+    /// as the config's `authority`, the wallet allowed to call the mint
+    /// instructions. The mint is a fresh keypair that co-signs the transaction.
+    /// Pass a `freeze_authority` (often the signer) to enable
+    /// `freeze_account`/`thaw_account`; `None` disables freezing for good. Passing
+    /// `metadata` also creates a Metaplex metadata account with the signer as
+    /// update authority, which requires the optional metadata accounts.
+    /// `max_supply` caps the mint's supply across every mint instruction;
+    /// `u64::MAX` leaves it uncapped.
+    /// A non-zero `initial_supply` (base units, e.g. `INITIAL_SUPPLY`) is minted to
+    /// the signer's associated token account, which is created here and must be
+    /// passed as `creator_token` along with the associated token program.
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
+        metadata: Option<TokenMetadataArgs>,
+        max_supply: u64,
+        initial_supply: u64,
+    ) -> Result<()> {
+        if let Some(args) = &metadata {
+            args.validate()?;
+        }
+        require!(initial_supply <= max_supply, TokenError::SupplyCapExceeded);
+        let config = &mut ctx.accounts.config;
+        config.mint = ctx.accounts.mint.key();
+        config.authority = ctx.accounts.authority.key();
+        config.max_supply = max_supply;
+        config.bump = ctx.bumps.config;
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            ctx.accounts.rent.minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &ctx.accounts.token_program.key(),
+        )?;
+        token::initialize_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+            ),
+            decimals,
+            &ctx.accounts.config.key(),
+            freeze_authority.as_ref(),
+        )?;
+        if initial_supply > 0 {
+            ctx.accounts.mint_initial_supply(initial_supply)?;
+        }
+
+        let Some(args) = metadata else {
+            return Ok(());
+        };
+        let (Some(metadata_account), Some(metadata_program)) =
+            (&ctx.accounts.metadata, &ctx.accounts.token_metadata_program)
+        else {
+            return err!(TokenError::MissingMetadataAccounts);
+        };
+        let authority = ctx.accounts.authority.to_account_info();
+        let config = &ctx.accounts.config;
+        let bump = [config.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"config", config.mint.as_ref(), &bump]];
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: metadata_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: config.to_account_info(),
+                    payer: authority.clone(),
+                    update_authority: authority,
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            DataV2 {
+                name: args.name,
+                symbol: args.symbol,
+                uri: args.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )
+    }
+
+    /// Only the config's `authority` may mint; the CPI is signed by the config
+    /// PDA, which holds the mint authority, so the supply cap cannot be bypassed
+    /// through the token program. The destination's owner must be on the mint's
+    /// allowlist unless it is empty.
+    pub fn mint_to(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenError::InvalidAmount);
+        Allowlist::check_recipient(&ctx.accounts.allowlist, &ctx.accounts.destination.owner)?;
+        ctx.accounts
+            .config
+            .check_supply(ctx.accounts.mint.supply, amount)?;
+        mint_with_config(
+            &ctx.accounts.token_program,
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            &ctx.accounts.config,
+            amount,
+        )
+    }
+
+    /// Same as `mint_to`, but mints into `recipient`'s associated token account,
+    /// creating it first (paid by `authority`) when it does not exist yet. Kept
+    /// separate from `mint_to` so nobody pays for an ATA by accident.
+    pub fn mint_to_ata(ctx: Context<MintToAta>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenError::InvalidAmount);
+        Allowlist::check_recipient(&ctx.accounts.allowlist, &ctx.accounts.recipient.key())?;
+        ctx.accounts
+            .config
+            .check_supply(ctx.accounts.mint.supply, amount)?;
+        mint_with_config(
+            &ctx.accounts.token_program,
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            &ctx.accounts.config,
+            amount,
+        )
+    }
+
+    /// Mints `amounts[i]` to the i-th token account in `remaining_accounts`, up to
+    /// `TokenConfig::MAX_AIRDROP` recipients per call. Pass one writable token
+    /// account of this mint per amount, in the same order as `amounts`. The total
+    /// is checked against the supply cap, and every owner against the allowlist,
+    /// before anything is minted.
+    pub fn airdrop<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Airdrop<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            amounts.len() <= TokenConfig::MAX_AIRDROP,
+            TokenError::AirdropTooLarge
+        );
+        require!(
+            ctx.remaining_accounts.len() == amounts.len(),
+            TokenError::AirdropAccountsMismatch
+        );
+        let mut total: u64 = 0;
+        for (amount, info) in amounts.iter().zip(ctx.remaining_accounts) {
+            require!(*amount > 0, TokenError::InvalidAmount);
+            require!(info.is_writable, TokenError::InvalidAirdropAccount);
+            let destination = Account::<TokenAccount>::try_from(info)?;
+            require_keys_eq!(
+                destination.mint,
+                ctx.accounts.mint.key(),
+                TokenError::InvalidAirdropAccount
+            );
+            Allowlist::check_recipient(&ctx.accounts.allowlist, &destination.owner)?;
+            total = total
+                .checked_add(*amount)
+                .ok_or(TokenError::SupplyCapExceeded)?;
+        }
+        ctx.accounts
+            .config
+            .check_supply(ctx.accounts.mint.supply, total)?;
+        for (amount, info) in amounts.into_iter().zip(ctx.remaining_accounts) {
+            mint_with_config(
+                &ctx.accounts.token_program,
+                ctx.accounts.mint.to_account_info(),
+                info.clone(),
+                &ctx.accounts.config,
+                amount,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Approves `wallet` to receive minted tokens, creating the mint's allowlist
+    /// on first use. Only the config's `authority` may change the list; once it holds
+    /// a wallet, `mint_to`, `mint_to_ata` and `airdrop` only mint to listed owners.
+    pub fn add_to_allowlist(ctx: Context<UpdateAllowlist>, wallet: Pubkey) -> Result<()> {
+        let allowlist = &mut ctx.accounts.allowlist;
+        allowlist.mint = ctx.accounts.mint.key();
+        allowlist.bump = ctx.bumps.allowlist;
+        require!(
+            !allowlist.wallets.contains(&wallet),
+            TokenError::AlreadyAllowlisted
+        );
+        require!(
+            allowlist.wallets.len() < Allowlist::MAX_WALLETS,
+            TokenError::AllowlistFull
+        );
+        allowlist.wallets.push(wallet);
         Ok(())
     }
+
+    /// Removing the last wallet disables the allowlist again: an empty list lets
+    /// anyone receive minted tokens.
+    pub fn remove_from_allowlist(ctx: Context<UpdateAllowlist>, wallet: Pubkey) -> Result<()> {
+        let wallets = &mut ctx.accounts.allowlist.wallets;
+        let index = wallets
+            .iter()
+            .position(|allowed| *allowed == wallet)
+            .ok_or(TokenError::NotAllowlisted)?;
+        wallets.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn transfer(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenError::InvalidAmount);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.from.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Burns from the owner's account, reducing the mint's supply.
+    pub fn burn(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenError::InvalidAmount);
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.from.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Frozen accounts cannot send, receive, or burn until thawed.
+    pub fn freeze_account(ctx: Context<FreezeTokens>) -> Result<()> {
+        token::freeze_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.freeze_authority.to_account_info(),
+            },
+        ))
+    }
+
+    pub fn thaw_account(ctx: Context<FreezeTokens>) -> Result<()> {
+        token::thaw_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.freeze_authority.to_account_info(),
+            },
+        ))
+    }
+
+    /// Hands minting through this program to `new_authority`, or with `None`
+    /// revokes the config PDA's mint authority permanently, which fixes the supply.
+    /// The mint authority itself never leaves the PDA, so the program's checks
+    /// cannot be bypassed by minting through the token program directly.
+    pub fn set_mint_authority(
+        ctx: Context<SetMintAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        if let Some(new_authority) = new_authority {
+            ctx.accounts.config.authority = new_authority;
+            return Ok(());
+        }
+        let config = &ctx.accounts.config;
+        let bump = [config.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"config", config.mint.as_ref(), &bump]];
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: config.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )
+    }
+
+    /// Token-2022 counterpart of `initialize` that enables the transfer-fee
+    /// extension: every transfer withholds `fee_basis_points` of the amount, capped
+    /// at `maximum_fee`, in the recipient's account. The signer becomes mint, fee
+    /// config, and withdraw-withheld authority.
+    ///
+    /// Extensions live after the base mint, so the account must be sized with
+    /// `ExtensionType::try_calculate_account_len` (82-byte base mint, padding to the
+    /// 165-byte account length, the account-type byte, then each extension's TLV
+    /// entry) and the extension must be initialized before `initialize_mint2`.
+    pub fn initialize_with_transfer_fee(
+        ctx: Context<InitializeTransferFeeMint>,
+        decimals: u8,
+        fee_basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<()> {
+        require!(
+            fee_basis_points <= MAX_FEE_BASIS_POINTS,
+            TokenError::InvalidFee
+        );
+        let space = ExtensionType::try_calculate_account_len::<Mint2022State>(&[
+            ExtensionType::TransferFeeConfig,
+        ])?;
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            ctx.accounts.rent.minimum_balance(space),
+            space as u64,
+            &ctx.accounts.token_program.key(),
+        )?;
+        let authority = ctx.accounts.authority.key();
+        token_2022_extensions::transfer_fee_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferFeeInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            Some(&authority),
+            Some(&authority),
+            fee_basis_points,
+            maximum_fee,
+        )?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &authority,
+            None,
+        )
+    }
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: the metadata PDA `[b"metadata", token_metadata_program, mint]`;
+    /// derivation is verified by the token metadata program during the CPI.
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenConfig::SIZE,
+        seeds = [b"config", mint.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: the authority's associated token account for `mint`, created by
+    /// `initialize`; the associated token program verifies the address.
+    #[account(mut)]
+    pub creator_token: Option<UncheckedAccount<'info>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+impl<'info> Initialize<'info> {
+    /// The mint does not exist when Anchor checks the accounts, so the creator's
+    /// ATA is created by CPI instead of with `associated_token::` constraints.
+    fn mint_initial_supply(&self, amount: u64) -> Result<()> {
+        let (Some(creator_token), Some(associated_token_program)) =
+            (&self.creator_token, &self.associated_token_program)
+        else {
+            return err!(TokenError::MissingCreatorTokenAccount);
+        };
+        associated_token::create(CpiContext::new(
+            associated_token_program.to_account_info(),
+            Create {
+                payer: self.authority.to_account_info(),
+                associated_token: creator_token.to_account_info(),
+                authority: self.authority.to_account_info(),
+                mint: self.mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+        mint_with_config(
+            &self.token_program,
+            self.mint.to_account_info(),
+            creator_token.to_account_info(),
+            &self.config,
+            amount,
+        )
+    }
+}
+
+/// Mints `amount` of `mint` into `to`, signed by the config PDA that holds the
+/// mint authority.
+fn mint_with_config<'info>(
+    token_program: &Program<'info, Token>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    config: &Account<'info, TokenConfig>,
+    amount: u64,
+) -> Result<()> {
+    let bump = [config.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"config", config.mint.as_ref(), &bump]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint,
+                to,
+                authority: config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+#[account]
+pub struct TokenConfig {
+    pub mint: Pubkey,
+    /// Wallet allowed to mint and to change the allowlist; the mint authority
+    /// itself is this PDA.
+    pub authority: Pubkey,
+    /// Upper bound on the mint's supply; `u64::MAX` means uncapped.
+    pub max_supply: u64,
+    pub bump: u8,
+}
+
+impl TokenConfig {
+    pub const SIZE: usize = 32 + 32 + 8 + 1;
+    /// Upper bound on `airdrop` recipients; each adds an account and a CPI.
+    pub const MAX_AIRDROP: usize = 10;
+
+    pub fn check_supply(&self, current_supply: u64, amount: u64) -> Result<()> {
+        if self.max_supply == u64::MAX {
+            return Ok(());
+        }
+        let new_supply = current_supply
+            .checked_add(amount)
+            .ok_or(TokenError::SupplyCapExceeded)?;
+        require!(new_supply <= self.max_supply, TokenError::SupplyCapExceeded);
+        Ok(())
+    }
+}
+
+/// Wallets approved to receive minted tokens, seeded by `[b"allowlist", mint]`.
+/// An empty list, or no allowlist account at all, leaves minting unrestricted.
+/// The list binds every mint because only the config PDA can sign one.
+#[account]
+pub struct Allowlist {
+    pub mint: Pubkey,
+    pub wallets: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl Allowlist {
+    pub const MAX_WALLETS: usize = 32;
+    pub const SIZE: usize = 32 + 4 + Self::MAX_WALLETS * 32 + 1;
+
+    pub fn allows(&self, wallet: &Pubkey) -> bool {
+        self.wallets.is_empty() || self.wallets.contains(wallet)
+    }
+
+    /// Rejects `owner` unless the allowlist PDA `account` allows it. The PDA only
+    /// exists once `add_to_allowlist` has run, so an empty account allows everyone.
+    pub fn check_recipient(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
+        if account.data_is_empty() {
+            return Ok(());
+        }
+        let allowlist = Allowlist::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(allowlist.allows(owner), TokenError::RecipientNotAllowed);
+        Ok(())
+    }
+}
+
+/// Display fields for the Metaplex metadata account created by `initialize`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl TokenMetadataArgs {
+    /// Limits enforced by the token metadata program.
+    pub const MAX_NAME: usize = 32;
+    pub const MAX_SYMBOL: usize = 10;
+    pub const MAX_URI: usize = 200;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.name.len() <= Self::MAX_NAME, TokenError::NameTooLong);
+        require!(
+            self.symbol.len() <= Self::MAX_SYMBOL,
+            TokenError::SymbolTooLong
+        );
+        require!(self.uri.len() <= Self::MAX_URI, TokenError::UriTooLong);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"config", mint.key().as_ref()],
+        bump = config.bump,
+        has_one = authority @ TokenError::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: the mint's `Allowlist` PDA, which may not exist yet; see
+    /// `Allowlist::check_recipient`.
+    #[account(seeds = [b"allowlist", mint.key().as_ref()], bump)]
+    pub allowlist: UncheckedAccount<'info>,
+    #[account(mut, token::mint = mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MintToAta<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"config", mint.key().as_ref()],
+        bump = config.bump,
+        has_one = authority @ TokenError::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: the mint's `Allowlist` PDA, which may not exist yet; see
+    /// `Allowlist::check_recipient`.
+    #[account(seeds = [b"allowlist", mint.key().as_ref()], bump)]
+    pub allowlist: UncheckedAccount<'info>,
+    /// CHECK: only the wallet the ATA is derived from; it does not sign.
+    pub recipient: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = recipient
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Destination token accounts are passed through `remaining_accounts`; see `airdrop`.
+#[derive(Accounts)]
+pub struct Airdrop<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"config", mint.key().as_ref()],
+        bump = config.bump,
+        has_one = authority @ TokenError::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: the mint's `Allowlist` PDA, which may not exist yet; see
+    /// `Allowlist::check_recipient`.
+    #[account(seeds = [b"allowlist", mint.key().as_ref()], bump)]
+    pub allowlist: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Shared by `add_to_allowlist` and `remove_from_allowlist`.
+#[derive(Accounts)]
+pub struct UpdateAllowlist<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"config", mint.key().as_ref()],
+        bump = config.bump,
+        has_one = authority @ TokenError::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Allowlist::SIZE,
+        seeds = [b"allowlist", mint.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferTokens<'info> {
+    #[account(mut, token::authority = owner)]
+    pub from: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = from.mint)]
+    pub to: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(mut, token::mint = mint, token::authority = owner)]
+    pub from: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Shared by `freeze_account` and `thaw_account`; the token program checks that
+/// `freeze_authority` matches the mint.
+#[derive(Accounts)]
+pub struct FreezeTokens<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(mut, token::mint = mint)]
+    pub account: Account<'info, TokenAccount>,
+    pub freeze_authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTransferFeeMint<'info> {
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintAuthority<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"config", mint.key().as_ref()],
+        bump = config.bump,
+        has_one = authority @ TokenError::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Token-2022 rejects transfer fees above 100%.
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+#[error_code]
+pub enum TokenError {
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Token name exceeds 32 bytes.")]
+    NameTooLong,
+    #[msg("Token symbol exceeds 10 bytes.")]
+    SymbolTooLong,
+    #[msg("Token URI exceeds 200 bytes.")]
+    UriTooLong,
+    #[msg("Metadata requires the metadata and token metadata program accounts.")]
+    MissingMetadataAccounts,
+    #[msg("Transfer fee basis points must not exceed 10_000.")]
+    InvalidFee,
+    #[msg("Minting this amount would exceed the max supply.")]
+    SupplyCapExceeded,
+    #[msg("An initial supply requires the creator token and associated token program accounts.")]
+    MissingCreatorTokenAccount,
+    #[msg("Too many recipients in a single airdrop.")]
+    AirdropTooLarge,
+    #[msg("Pass exactly one token account per amount in remaining_accounts.")]
+    AirdropAccountsMismatch,
+    #[msg("An airdrop account is not a writable token account for this mint.")]
+    InvalidAirdropAccount,
+    #[msg("Only the token's authority can mint or change the allowlist.")]
+    Unauthorized,
+    #[msg("The recipient's wallet is not on the mint's allowlist.")]
+    RecipientNotAllowed,
+    #[msg("The allowlist already holds the maximum number of wallets.")]
+    AllowlistFull,
+    #[msg("That wallet is already on the allowlist.")]
+    AlreadyAllowlisted,
+    #[msg("That wallet is not on the allowlist.")]
+    NotAllowlisted,
+}
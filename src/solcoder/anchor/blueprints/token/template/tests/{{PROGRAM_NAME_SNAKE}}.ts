@@ -0,0 +1,559 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  getTransferFeeAmount,
+  mintTo as splMintTo,
+  transferCheckedWithFee,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const authority = provider.wallet.publicKey;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+
+  type MetadataArgs = { name: string; symbol: string; uri: string };
+
+  const UNCAPPED = new anchor.BN("18446744073709551615");
+  // Scaffold-time values of TOKEN_DECIMALS and INITIAL_SUPPLY_TOKENS in lib.rs.
+  const TOKEN_DECIMALS = {{TOKEN_DECIMALS}};
+  const INITIAL_SUPPLY_TOKENS = new anchor.BN("{{TOKEN_INITIAL_SUPPLY}}");
+
+  function configPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), mint.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  function metadataPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    return pda;
+  }
+
+  function initialize(
+    mint: anchor.web3.Keypair,
+    decimals: number,
+    freezeAuthority: anchor.web3.PublicKey | null = null,
+    metadata: MetadataArgs | null = null,
+    maxSupply: anchor.BN = UNCAPPED,
+    initialSupply: anchor.BN = new anchor.BN(0)
+  ) {
+    const minting = !initialSupply.isZero();
+    return program.methods
+      .initialize(decimals, freezeAuthority, metadata, maxSupply, initialSupply)
+      .accountsPartial({
+        mint: mint.publicKey,
+        authority,
+        metadata: metadata ? metadataPda(mint.publicKey) : null,
+        tokenMetadataProgram: metadata ? TOKEN_METADATA_PROGRAM_ID : null,
+        creatorToken: minting ? getAssociatedTokenAddressSync(mint.publicKey, authority) : null,
+        associatedTokenProgram: minting ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
+      })
+      .signers([mint]);
+  }
+
+  async function createMint(
+    decimals: number,
+    freezeAuthority: anchor.web3.PublicKey | null = null,
+    maxSupply: anchor.BN = UNCAPPED
+  ): Promise<anchor.web3.PublicKey> {
+    const mint = anchor.web3.Keypair.generate();
+    await initialize(mint, decimals, freezeAuthority, null, maxSupply).rpc();
+    return mint.publicKey;
+  }
+
+  // Metaplex stores name and symbol as borsh strings after the key, update
+  // authority, and mint, padded with NUL bytes.
+  function readMetadataStrings(data: Buffer): { name: string; symbol: string } {
+    let offset = 1 + 32 + 32;
+    const readString = () => {
+      const len = data.readUInt32LE(offset);
+      const value = data.subarray(offset + 4, offset + 4 + len).toString("utf8");
+      offset += 4 + len;
+      return value.replace(/\0+$/, "");
+    };
+    const name = readString();
+    const symbol = readString();
+    return { name, symbol };
+  }
+
+  async function ata(
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey = authority
+  ): Promise<anchor.web3.PublicKey> {
+    return (await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, owner))
+      .address;
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    return Number((await getAccount(provider.connection, account)).amount);
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  // Failures raised inside a token program CPI are not Anchor errors, so match
+  // on the token program's log line instead.
+  async function expectTokenError(promise: Promise<unknown>, message: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      const logs: string[] = (err as { logs?: string[] }).logs ?? [];
+      assert.isTrue(
+        logs.some((line) => line.includes(message)),
+        `expected "${message}" in logs:\n${logs.join("\n")}`
+      );
+      return;
+    }
+    assert.fail(`expected "${message}" failure`);
+  }
+
+  function mintTo(
+    mint: anchor.web3.PublicKey,
+    destination: anchor.web3.PublicKey,
+    amount: number,
+    signer: anchor.web3.Keypair | null = null
+  ) {
+    const builder = program.methods
+      .mintTo(new anchor.BN(amount))
+      .accountsPartial({ mint, destination, authority: signer?.publicKey ?? authority });
+    return signer ? builder.signers([signer]) : builder;
+  }
+
+  function setMintAuthority(
+    mint: anchor.web3.PublicKey,
+    newAuthority: anchor.web3.PublicKey | null
+  ) {
+    return program.methods
+      .setMintAuthority(newAuthority)
+      .accountsPartial({ mint, config: configPda(mint), authority });
+  }
+
+  function transfer(from: anchor.web3.PublicKey, to: anchor.web3.PublicKey, amount: number) {
+    return program.methods
+      .transfer(new anchor.BN(amount))
+      .accountsPartial({ from, to, owner: authority });
+  }
+
+  it("creates a mint with zero supply and the config PDA as mint authority", async () => {
+    const mint = await createMint(6);
+    const info = await getMint(provider.connection, mint);
+
+    assert.equal(info.decimals, 6);
+    assert.equal(Number(info.supply), 0);
+    assert.ok(info.mintAuthority?.equals(configPda(mint)));
+    const config = await program.account.tokenConfig.fetch(configPda(mint));
+    assert.ok(config.authority.equals(authority));
+  });
+
+  it("mints to an associated token account", async () => {
+    const mint = await createMint(6);
+    const destination = await ata(mint);
+
+    await mintTo(mint, destination, 1_500_000).rpc();
+
+    assert.equal(await tokenBalance(destination), 1_500_000);
+    assert.equal(Number((await getMint(provider.connection, mint)).supply), 1_500_000);
+  });
+
+  it("creates the recipient's associated token account on mint_to_ata", async () => {
+    const mint = await createMint(6);
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const destination = getAssociatedTokenAddressSync(mint, recipient);
+    assert.isNull(await provider.connection.getAccountInfo(destination));
+
+    await program.methods
+      .mintToAta(new anchor.BN(500))
+      .accountsPartial({ mint, recipient, destination, authority })
+      .rpc();
+    // A second mint reuses the account the first one created.
+    await program.methods
+      .mintToAta(new anchor.BN(250))
+      .accountsPartial({ mint, recipient, destination, authority })
+      .rpc();
+
+    const account = await getAccount(provider.connection, destination);
+    assert.ok(account.owner.equals(recipient));
+    assert.equal(Number(account.amount), 750);
+  });
+
+  it("rejects minting zero tokens", async () => {
+    const mint = await createMint(6);
+    await expectError(mintTo(mint, await ata(mint), 0).rpc(), "InvalidAmount");
+  });
+
+  it("transfers between two wallets", async () => {
+    const mint = await createMint(6);
+    const recipient = anchor.web3.Keypair.generate();
+    const from = await ata(mint);
+    const to = await ata(mint, recipient.publicKey);
+    await mintTo(mint, from, 1_000).rpc();
+
+    await transfer(from, to, 400).rpc();
+
+    assert.equal(await tokenBalance(from), 600);
+    assert.equal(await tokenBalance(to), 400);
+  });
+
+  it("burns tokens and reduces supply", async () => {
+    const mint = await createMint(6);
+    const from = await ata(mint);
+    await mintTo(mint, from, 1_000).rpc();
+
+    await program.methods
+      .burn(new anchor.BN(250))
+      .accountsPartial({ mint, from, owner: authority })
+      .rpc();
+
+    assert.equal(await tokenBalance(from), 750);
+    assert.equal(Number((await getMint(provider.connection, mint)).supply), 750);
+  });
+
+  it("blocks transfers from a frozen account until it is thawed", async () => {
+    const mint = await createMint(6, authority);
+    const from = await ata(mint);
+    const to = await ata(mint, anchor.web3.Keypair.generate().publicKey);
+    await mintTo(mint, from, 1_000).rpc();
+
+    await program.methods
+      .freezeAccount()
+      .accountsPartial({ mint, account: from, freezeAuthority: authority })
+      .rpc();
+    await expectTokenError(transfer(from, to, 100).rpc(), "Account is frozen");
+
+    await program.methods
+      .thawAccount()
+      .accountsPartial({ mint, account: from, freezeAuthority: authority })
+      .rpc();
+    await transfer(from, to, 100).rpc();
+    assert.equal(await tokenBalance(to), 100);
+  });
+
+  it("cannot mint after the mint authority is revoked", async () => {
+    const mint = await createMint(6);
+    const destination = await ata(mint);
+    await mintTo(mint, destination, 1_000).rpc();
+
+    await setMintAuthority(mint, null).rpc();
+
+    assert.isNull((await getMint(provider.connection, mint)).mintAuthority);
+    await expectTokenError(mintTo(mint, destination, 1).rpc(), "supply of this token is fixed");
+    assert.equal(await tokenBalance(destination), 1_000);
+  });
+
+  it("lets a transferred authority mint while the old one cannot", async () => {
+    const mint = await createMint(6);
+    const destination = await ata(mint);
+    const newAuthority = anchor.web3.Keypair.generate();
+
+    await setMintAuthority(mint, newAuthority.publicKey).rpc();
+
+    await mintTo(mint, destination, 500, newAuthority).rpc();
+    await expectError(mintTo(mint, destination, 1).rpc(), "Unauthorized");
+    assert.equal(await tokenBalance(destination), 500);
+    // Only the program's record changes; the PDA keeps the mint authority.
+    assert.ok((await getMint(provider.connection, mint)).mintAuthority?.equals(configPda(mint)));
+  });
+
+  it("creates a readable metadata account", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await initialize(mint, 6, null, {
+      name: "{{PROGRAM_NAME_TITLE}}",
+      symbol: "TKN",
+      uri: "https://example.com/token.json",
+    }).rpc();
+
+    const account = await provider.connection.getAccountInfo(metadataPda(mint.publicKey));
+    assert.isNotNull(account);
+    assert.ok(account!.owner.equals(TOKEN_METADATA_PROGRAM_ID));
+    const { name, symbol } = readMetadataStrings(account!.data);
+    assert.equal(name, "{{PROGRAM_NAME_TITLE}}");
+    assert.equal(symbol, "TKN");
+  });
+
+  it("rejects a symbol longer than 10 bytes", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    await expectError(
+      initialize(mint, 6, null, { name: "Token", symbol: "TOOLONGSYMB", uri: "" }).rpc(),
+      "SymbolTooLong"
+    );
+  });
+
+  it("mints the scaffolded initial supply to the creator", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    const supply = INITIAL_SUPPLY_TOKENS.mul(new anchor.BN(10).pow(new anchor.BN(TOKEN_DECIMALS)));
+    // A zero initial supply skips the ATA, so mint one base unit to exercise it.
+    const amount = supply.isZero() ? new anchor.BN(1) : supply;
+    await initialize(mint, TOKEN_DECIMALS, null, null, UNCAPPED, amount).rpc();
+
+    const creatorToken = getAssociatedTokenAddressSync(mint.publicKey, authority);
+    const account = await getAccount(provider.connection, creatorToken);
+    assert.equal(account.amount.toString(), amount.toString());
+    const info = await getMint(provider.connection, mint.publicKey);
+    assert.equal(info.decimals, TOKEN_DECIMALS);
+    assert.equal(info.supply.toString(), amount.toString());
+  });
+
+  it("rejects an initial supply above the cap or without the creator account", async () => {
+    const capped = anchor.web3.Keypair.generate();
+    await expectError(
+      initialize(capped, 0, null, null, new anchor.BN(10), new anchor.BN(11)).rpc(),
+      "SupplyCapExceeded"
+    );
+
+    const mint = anchor.web3.Keypair.generate();
+    await expectError(
+      program.methods
+        .initialize(0, null, null, UNCAPPED, new anchor.BN(5))
+        .accountsPartial({
+          mint: mint.publicKey,
+          authority,
+          metadata: null,
+          tokenMetadataProgram: null,
+          creatorToken: null,
+          associatedTokenProgram: null,
+        })
+        .signers([mint])
+        .rpc(),
+      "MissingCreatorTokenAccount"
+    );
+  });
+
+  it("mints up to the supply cap and rejects one token over", async () => {
+    const mint = await createMint(0, null, new anchor.BN(1_000));
+    const destination = await ata(mint);
+
+    await mintTo(mint, destination, 600).rpc();
+    await mintTo(mint, destination, 400).rpc();
+    await expectError(mintTo(mint, destination, 1).rpc(), "SupplyCapExceeded");
+
+    assert.equal(Number((await getMint(provider.connection, mint)).supply), 1_000);
+  });
+
+  it("keeps the supply cap after minting is handed to another wallet", async () => {
+    const mint = await createMint(0, null, new anchor.BN(100));
+    const destination = await ata(mint);
+    const newAuthority = anchor.web3.Keypair.generate();
+    await setMintAuthority(mint, newAuthority.publicKey).rpc();
+
+    await mintTo(mint, destination, 100, newAuthority).rpc();
+    await expectError(mintTo(mint, destination, 1, newAuthority).rpc(), "SupplyCapExceeded");
+
+    assert.equal(Number((await getMint(provider.connection, mint)).supply), 100);
+  });
+
+  function allowlistPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allowlist"), mint.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  it("only mints to wallets on a non-empty allowlist", async () => {
+    const mint = await createMint(0);
+    const allowed = anchor.web3.Keypair.generate().publicKey;
+    const stranger = anchor.web3.Keypair.generate().publicKey;
+    const allowedAta = await ata(mint, allowed);
+    const strangerAta = await ata(mint, stranger);
+
+    // No allowlist yet: minting is unrestricted.
+    await mintTo(mint, strangerAta, 1).rpc();
+
+    await program.methods
+      .addToAllowlist(allowed)
+      .accountsPartial({ mint, allowlist: allowlistPda(mint), authority })
+      .rpc();
+    await mintTo(mint, allowedAta, 10).rpc();
+    await expectError(mintTo(mint, strangerAta, 10).rpc(), "RecipientNotAllowed");
+    await expectError(
+      program.methods
+        .mintToAta(new anchor.BN(10))
+        .accountsPartial({
+          mint,
+          recipient: stranger,
+          destination: getAssociatedTokenAddressSync(mint, stranger),
+          authority,
+        })
+        .rpc(),
+      "RecipientNotAllowed"
+    );
+    await expectError(
+      airdrop(mint, [allowedAta, strangerAta], [1, 1]).rpc(),
+      "RecipientNotAllowed"
+    );
+    assert.equal(await tokenBalance(allowedAta), 10);
+    assert.equal(await tokenBalance(strangerAta), 1);
+
+    // Emptying the list disables it again.
+    await program.methods
+      .removeFromAllowlist(allowed)
+      .accountsPartial({ mint, allowlist: allowlistPda(mint), authority })
+      .rpc();
+    await mintTo(mint, strangerAta, 2).rpc();
+    assert.equal(await tokenBalance(strangerAta), 3);
+  });
+
+  it("rejects a direct token program mint that would skip the allowlist", async () => {
+    const mint = await createMint(0);
+    const stranger = await ata(mint, anchor.web3.Keypair.generate().publicKey);
+    await program.methods
+      .addToAllowlist(anchor.web3.Keypair.generate().publicKey)
+      .accountsPartial({ mint, allowlist: allowlistPda(mint), authority })
+      .rpc();
+
+    // The wallet is not the mint authority, so it cannot mint around the program.
+    await expectTokenError(
+      splMintTo(provider.connection, payer, mint, stranger, payer, 10),
+      "owner does not match"
+    );
+    assert.equal(await tokenBalance(stranger), 0);
+  });
+
+  it("lets only the token's authority change the allowlist", async () => {
+    const mint = await createMint(0);
+    const outsider = anchor.web3.Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      outsider.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature);
+
+    await expectError(
+      program.methods
+        .addToAllowlist(outsider.publicKey)
+        .accountsPartial({ mint, allowlist: allowlistPda(mint), authority: outsider.publicKey })
+        .signers([outsider])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  function airdrop(
+    mint: anchor.web3.PublicKey,
+    recipients: anchor.web3.PublicKey[],
+    amounts: number[]
+  ) {
+    return program.methods
+      .airdrop(amounts.map((amount) => new anchor.BN(amount)))
+      .accountsPartial({ mint, authority })
+      .remainingAccounts(
+        recipients.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+      );
+  }
+
+  it("airdrops to three recipients in one transaction", async () => {
+    const mint = await createMint(6);
+    const recipients = await Promise.all(
+      [0, 1, 2].map(() => ata(mint, anchor.web3.Keypair.generate().publicKey))
+    );
+
+    await airdrop(mint, recipients, [100, 200, 300]).rpc();
+
+    assert.deepEqual(await Promise.all(recipients.map(tokenBalance)), [100, 200, 300]);
+    assert.equal(Number((await getMint(provider.connection, mint)).supply), 600);
+  });
+
+  it("rejects an airdrop whose accounts do not match the amounts", async () => {
+    const mint = await createMint(6);
+    const recipient = await ata(mint, anchor.web3.Keypair.generate().publicKey);
+    await expectError(airdrop(mint, [recipient], [100, 200]).rpc(), "AirdropAccountsMismatch");
+  });
+
+  it("rejects an airdrop over the supply cap", async () => {
+    const mint = await createMint(0, null, new anchor.BN(500));
+    const recipients = await Promise.all(
+      [0, 1].map(() => ata(mint, anchor.web3.Keypair.generate().publicKey))
+    );
+    await expectError(airdrop(mint, recipients, [300, 300]).rpc(), "SupplyCapExceeded");
+  });
+
+  describe("Token-2022 transfer fee", () => {
+    it("withholds the fee on transfer", async () => {
+      const mint = anchor.web3.Keypair.generate();
+      await program.methods
+        .initializeWithTransferFee(6, 100, new anchor.BN(1_000_000))
+        .accountsPartial({
+          mint: mint.publicKey,
+          authority,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([mint])
+        .rpc();
+
+      const ata2022 = async (owner: anchor.web3.PublicKey) =>
+        (
+          await getOrCreateAssociatedTokenAccount(
+            provider.connection,
+            payer,
+            mint.publicKey,
+            owner,
+            false,
+            undefined,
+            undefined,
+            TOKEN_2022_PROGRAM_ID
+          )
+        ).address;
+      const from = await ata2022(authority);
+      const to = await ata2022(anchor.web3.Keypair.generate().publicKey);
+      await splMintTo(
+        provider.connection,
+        payer,
+        mint.publicKey,
+        from,
+        payer,
+        10_000,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      // 100 bps of 10_000 is 100; the fee stays withheld in the recipient account.
+      await transferCheckedWithFee(
+        provider.connection,
+        payer,
+        from,
+        mint.publicKey,
+        to,
+        payer,
+        BigInt(10_000),
+        6,
+        BigInt(100),
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const received = await getAccount(provider.connection, to, undefined, TOKEN_2022_PROGRAM_ID);
+      assert.equal(Number(received.amount), 9_900);
+      assert.equal(Number(getTransferFeeAmount(received)?.withheldAmount), 100);
+    });
+  });
+});
@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("{{PROGRAM_ID}}");
+
+#[program]
+pub mod {{PROGRAM_NAME_SNAKE}} {
+    use super::*;
+
+    /// Locks `total_amount` in a vault that releases linearly to the
+    /// beneficiary from `start` over `duration` seconds. Nothing is claimable
+    /// before `cliff`, a unix timestamp between `start` and the end.
+    pub fn create_schedule(
+        ctx: Context<CreateSchedule>,
+        total_amount: u64,
+        start: i64,
+        cliff: i64,
+        duration: i64,
+    ) -> Result<()> {
+        require!(total_amount > 0, VestingError::InvalidAmount);
+        require!(duration > 0, VestingError::InvalidSchedule);
+        let end = start
+            .checked_add(duration)
+            .ok_or(VestingError::InvalidSchedule)?;
+        require!(
+            start <= cliff && cliff <= end,
+            VestingError::InvalidSchedule
+        );
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.granter = ctx.accounts.granter.key();
+        schedule.beneficiary = ctx.accounts.beneficiary.key();
+        schedule.mint = ctx.accounts.mint.key();
+        schedule.total_amount = total_amount;
+        schedule.claimed_amount = 0;
+        schedule.start = start;
+        schedule.cliff = cliff;
+        schedule.duration = duration;
+        schedule.revoked = false;
+        schedule.bump = ctx.bumps.schedule;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.granter_token.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.granter.to_account_info(),
+                },
+            ),
+            total_amount,
+        )
+    }
+
+    /// Transfers everything vested so far that has not been claimed yet.
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.schedule;
+        let claimable = schedule.vested_amount(now)? - schedule.claimed_amount;
+        require!(claimable > 0, VestingError::NothingToClaim);
+        schedule.claimed_amount += claimable;
+
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.beneficiary_token.to_account_info(),
+            schedule,
+            claimable,
+        )
+    }
+
+    /// Stops vesting and returns the unvested remainder to the granter. Tokens
+    /// vested before the revocation stay claimable by the beneficiary.
+    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.schedule;
+        require!(!schedule.revoked, VestingError::AlreadyRevoked);
+        let vested = schedule.vested_amount(now)?;
+        let unvested = schedule.total_amount - vested;
+        schedule.total_amount = vested;
+        schedule.revoked = true;
+
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.granter_token.to_account_info(),
+            schedule,
+            unvested,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateSchedule<'info> {
+    #[account(
+        init,
+        payer = granter,
+        space = 8 + VestingSchedule::SIZE,
+        seeds = [
+            b"vesting",
+            granter.key().as_ref(),
+            beneficiary.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub schedule: Account<'info, VestingSchedule>,
+    #[account(
+        init,
+        payer = granter,
+        seeds = [b"vault", schedule.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = schedule
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: only recorded as the recipient; it does not need to sign.
+    pub beneficiary: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = granter
+    )]
+    pub granter_token: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub granter: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        has_one = beneficiary,
+        seeds = [
+            b"vesting",
+            schedule.granter.as_ref(),
+            schedule.beneficiary.as_ref(),
+            schedule.mint.as_ref()
+        ],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, VestingSchedule>,
+    #[account(mut, seeds = [b"vault", schedule.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = schedule.mint,
+        token::authority = beneficiary
+    )]
+    pub beneficiary_token: Account<'info, TokenAccount>,
+    pub beneficiary: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    #[account(
+        mut,
+        has_one = granter,
+        seeds = [
+            b"vesting",
+            schedule.granter.as_ref(),
+            schedule.beneficiary.as_ref(),
+            schedule.mint.as_ref()
+        ],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, VestingSchedule>,
+    #[account(mut, seeds = [b"vault", schedule.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = schedule.mint,
+        token::authority = granter
+    )]
+    pub granter_token: Account<'info, TokenAccount>,
+    pub granter: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` out of the schedule's vault, signing with the schedule PDA.
+fn transfer_from_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    schedule: &Account<'info, VestingSchedule>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let bump = [schedule.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vesting",
+        schedule.granter.as_ref(),
+        schedule.beneficiary.as_ref(),
+        schedule.mint.as_ref(),
+        &bump,
+    ]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: destination,
+                authority: schedule.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+#[account]
+pub struct VestingSchedule {
+    pub granter: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    /// Amount that vests over the schedule; frozen at the vested amount on revoke.
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start: i64,
+    /// Unix timestamp before which nothing is claimable.
+    pub cliff: i64,
+    /// Seconds from `start` until everything has vested.
+    pub duration: i64,
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    pub const SIZE: usize = 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+
+    /// Amount vested at `now`: zero before the cliff, `total_amount` from the
+    /// end of the schedule (or after revocation), and linear in between.
+    fn vested_amount(&self, now: i64) -> Result<u64> {
+        if self.revoked || now >= self.start + self.duration {
+            return Ok(self.total_amount);
+        }
+        if now < self.cliff {
+            return Ok(0);
+        }
+        let elapsed = (now - self.start) as u128;
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .map(|value| value / self.duration as u128)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(vested as u64)
+    }
+}
+
+#[error_code]
+pub enum VestingError {
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Schedule needs a positive duration and a cliff between start and end.")]
+    InvalidSchedule,
+    #[msg("Nothing has vested since the last claim.")]
+    NothingToClaim,
+    #[msg("The schedule has already been revoked.")]
+    AlreadyRevoked,
+    #[msg("Vesting calculation overflowed.")]
+    MathOverflow,
+}
@@ -0,0 +1,227 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const granter = provider.wallet.publicKey;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const TOTAL = 3_600_000;
+  const HOUR = 3_600;
+
+  type Fixture = {
+    schedule: anchor.web3.PublicKey;
+    vault: anchor.web3.PublicKey;
+    mint: anchor.web3.PublicKey;
+    granterToken: anchor.web3.PublicKey;
+    beneficiary: anchor.web3.Keypair;
+    beneficiaryToken: anchor.web3.PublicKey;
+  };
+
+  type Timing = { start: number; cliff: number; duration: number };
+
+  function pda(seeds: Buffer[]): anchor.web3.PublicKey {
+    const [address] = anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId);
+    return address;
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    return Number((await getAccount(provider.connection, account)).amount);
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  // Vested amounts follow the validator clock, so rather than waiting, each test
+  // places its schedule relative to chain time: a start in the past puts "now"
+  // past the cliff or the end.
+  async function chainTime(): Promise<number> {
+    const slot = await provider.connection.getSlot();
+    return (await provider.connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+  }
+
+  // Every fixture gets its own mint so tests never share a schedule PDA.
+  async function setup(): Promise<Fixture> {
+    const mint = await createMint(provider.connection, payer, granter, null, 6);
+    const granterToken = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint,
+      granter
+    );
+    await mintTo(provider.connection, payer, mint, granterToken, payer, TOTAL);
+    const beneficiary = anchor.web3.Keypair.generate();
+    const beneficiaryToken = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint,
+      beneficiary.publicKey
+    );
+    const schedule = pda([
+      Buffer.from("vesting"),
+      granter.toBuffer(),
+      beneficiary.publicKey.toBuffer(),
+      mint.toBuffer(),
+    ]);
+    const vault = pda([Buffer.from("vault"), schedule.toBuffer()]);
+    return { schedule, vault, mint, granterToken, beneficiary, beneficiaryToken };
+  }
+
+  function createSchedule(fixture: Fixture, timing: Timing, amount = TOTAL) {
+    return program.methods
+      .createSchedule(
+        new anchor.BN(amount),
+        new anchor.BN(timing.start),
+        new anchor.BN(timing.cliff),
+        new anchor.BN(timing.duration)
+      )
+      .accountsPartial({
+        schedule: fixture.schedule,
+        vault: fixture.vault,
+        mint: fixture.mint,
+        beneficiary: fixture.beneficiary.publicKey,
+        granterToken: fixture.granterToken,
+        granter,
+      });
+  }
+
+  async function scheduleWith(timing: Timing): Promise<Fixture> {
+    const fixture = await setup();
+    await createSchedule(fixture, timing).rpc();
+    return fixture;
+  }
+
+  function claim(fixture: Fixture) {
+    return program.methods
+      .claim()
+      .accountsPartial({
+        schedule: fixture.schedule,
+        vault: fixture.vault,
+        beneficiaryToken: fixture.beneficiaryToken,
+        beneficiary: fixture.beneficiary.publicKey,
+      })
+      .signers([fixture.beneficiary]);
+  }
+
+  function revoke(fixture: Fixture) {
+    return program.methods.revoke().accountsPartial({
+      schedule: fixture.schedule,
+      vault: fixture.vault,
+      granterToken: fixture.granterToken,
+      granter,
+    });
+  }
+
+  it("locks the full amount in the vault", async () => {
+    const now = await chainTime();
+    const fixture = await scheduleWith({ start: now, cliff: now + HOUR, duration: 2 * HOUR });
+
+    const schedule = await program.account.vestingSchedule.fetch(fixture.schedule);
+    assert.ok(schedule.beneficiary.equals(fixture.beneficiary.publicKey));
+    assert.equal(schedule.totalAmount.toNumber(), TOTAL);
+    assert.equal(await tokenBalance(fixture.vault), TOTAL);
+    assert.equal(await tokenBalance(fixture.granterToken), 0);
+  });
+
+  it("rejects a cliff after the end of the schedule", async () => {
+    const now = await chainTime();
+    const fixture = await setup();
+    await expectError(
+      createSchedule(fixture, { start: now, cliff: now + 2 * HOUR, duration: HOUR }).rpc(),
+      "InvalidSchedule"
+    );
+  });
+
+  it("releases nothing before the cliff", async () => {
+    const now = await chainTime();
+    const fixture = await scheduleWith({
+      start: now - HOUR,
+      cliff: now + HOUR,
+      duration: 4 * HOUR,
+    });
+
+    await expectError(claim(fixture).rpc(), "NothingToClaim");
+    assert.equal(await tokenBalance(fixture.beneficiaryToken), 0);
+  });
+
+  it("releases a linear share between cliff and end", async () => {
+    const now = await chainTime();
+    const start = now - HOUR;
+    const fixture = await scheduleWith({ start, cliff: start, duration: 2 * HOUR });
+
+    await claim(fixture).rpc();
+
+    // Roughly half has vested; allow a minute of drift between the two clocks.
+    const claimed = await tokenBalance(fixture.beneficiaryToken);
+    assert.isAtLeast(claimed, (TOTAL * (HOUR - 60)) / (2 * HOUR));
+    assert.isAtMost(claimed, (TOTAL * (HOUR + 60)) / (2 * HOUR));
+    assert.equal(await tokenBalance(fixture.vault), TOTAL - claimed);
+  });
+
+  it("releases everything once fully vested", async () => {
+    const now = await chainTime();
+    const fixture = await scheduleWith({
+      start: now - 3 * HOUR,
+      cliff: now - 2 * HOUR,
+      duration: 2 * HOUR,
+    });
+
+    await claim(fixture).rpc();
+
+    assert.equal(await tokenBalance(fixture.beneficiaryToken), TOTAL);
+    assert.equal(await tokenBalance(fixture.vault), 0);
+    await expectError(claim(fixture).rpc(), "NothingToClaim");
+  });
+
+  it("returns the unvested remainder to the granter on revoke", async () => {
+    const now = await chainTime();
+    const start = now - HOUR;
+    const fixture = await scheduleWith({ start, cliff: start, duration: 2 * HOUR });
+
+    await revoke(fixture).rpc();
+    const schedule = await program.account.vestingSchedule.fetch(fixture.schedule);
+    assert.isTrue(schedule.revoked);
+    const vested = schedule.totalAmount.toNumber();
+    assert.equal(await tokenBalance(fixture.granterToken), TOTAL - vested);
+
+    // What vested before the revocation remains claimable.
+    await claim(fixture).rpc();
+    assert.equal(await tokenBalance(fixture.beneficiaryToken), vested);
+    await expectError(revoke(fixture).rpc(), "AlreadyRevoked");
+  });
+
+  it("only lets the granter revoke", async () => {
+    const now = await chainTime();
+    const fixture = await scheduleWith({ start: now, cliff: now, duration: HOUR });
+
+    const attempt = program.methods
+      .revoke()
+      .accountsPartial({
+        schedule: fixture.schedule,
+        vault: fixture.vault,
+        granterToken: fixture.beneficiaryToken,
+        granter: fixture.beneficiary.publicKey,
+      })
+      .signers([fixture.beneficiary]);
+    await expectError(attempt.rpc(), "ConstraintHasOne");
+  });
+});
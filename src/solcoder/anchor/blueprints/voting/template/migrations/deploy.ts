@@ -0,0 +1,19 @@
+// Run by `anchor migrate` with the provider configured in Anchor.toml. It only
+// checks that the program is deployed, so it is safe to run any number of times;
+// add one-off setup calls below.
+
+import * as anchor from "@coral-xyz/anchor";
+
+const PROGRAM_ID = new anchor.web3.PublicKey("{{PROGRAM_ID}}");
+
+module.exports = async function (provider: anchor.AnchorProvider) {
+  anchor.setProvider(provider);
+
+  const account = await provider.connection.getAccountInfo(PROGRAM_ID);
+  if (!account?.executable) {
+    throw new Error(
+      `{{PROGRAM_NAME_SNAKE}} is not deployed at ${PROGRAM_ID.toBase58()}; run anchor deploy first.`
+    );
+  }
+  console.log(`{{PROGRAM_NAME_SNAKE}} is deployed at ${PROGRAM_ID.toBase58()}.`);
+};
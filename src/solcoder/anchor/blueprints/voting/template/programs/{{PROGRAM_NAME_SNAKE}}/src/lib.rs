@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+
+declare_id!("{{PROGRAM_ID}}");
+
+#[program]
+pub mod {{PROGRAM_NAME_SNAKE}} {
+    use super::*;
+
+    /// Opens a proposal with 2 to `Proposal::MAX_OPTIONS` options. Votes are
+    /// accepted until `deadline`, a unix timestamp that must be in the future.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        title: String,
+        options: Vec<String>,
+        deadline: i64,
+    ) -> Result<()> {
+        require!(
+            title.len() <= Proposal::MAX_TITLE,
+            VotingError::TitleTooLong
+        );
+        require!(
+            (2..=Proposal::MAX_OPTIONS).contains(&options.len()),
+            VotingError::InvalidOptionCount
+        );
+        require!(
+            options
+                .iter()
+                .all(|option| option.len() <= Proposal::MAX_OPTION_LEN),
+            VotingError::OptionTooLong
+        );
+        require!(
+            deadline > Clock::get()?.unix_timestamp,
+            VotingError::DeadlineInPast
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.creator = ctx.accounts.creator.key();
+        proposal.proposal_id = proposal_id;
+        proposal.title = title;
+        proposal.tallies = vec![0; options.len()];
+        proposal.options = options;
+        proposal.deadline = deadline;
+        proposal.finalized = false;
+        proposal.winner = None;
+        proposal.bump = ctx.bumps.proposal;
+        Ok(())
+    }
+
+    /// Records one vote for `option`. The voter's receipt PDA is created here,
+    /// so a second vote on the same proposal fails because it already exists.
+    pub fn cast_vote(ctx: Context<CastVote>, option: u8) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.finalized, VotingError::AlreadyFinalized);
+        require!(
+            Clock::get()?.unix_timestamp < proposal.deadline,
+            VotingError::VotingClosed
+        );
+        let tally = proposal
+            .tallies
+            .get_mut(option as usize)
+            .ok_or(VotingError::InvalidOption)?;
+        *tally = tally.checked_add(1).ok_or(VotingError::MathOverflow)?;
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.proposal = proposal.key();
+        receipt.voter = ctx.accounts.voter.key();
+        receipt.option = option;
+        receipt.bump = ctx.bumps.receipt;
+        Ok(())
+    }
+
+    /// Closes voting once the deadline has passed and records the winning
+    /// option. A tie for the most votes leaves `winner` empty. Anyone may call it.
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.finalized, VotingError::AlreadyFinalized);
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.deadline,
+            VotingError::VotingOpen
+        );
+        proposal.finalized = true;
+        proposal.winner = proposal.leading_option();
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Proposal::SIZE,
+        seeds = [b"proposal", creator.key().as_ref(), &proposal_id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            proposal.creator.as_ref(),
+            &proposal.proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteReceipt::SIZE,
+        seeds = [b"receipt", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, VoteReceipt>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Finalize<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            proposal.creator.as_ref(),
+            &proposal.proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[account]
+pub struct Proposal {
+    pub creator: Pubkey,
+    /// Creator-chosen id; lets one creator run several proposals.
+    pub proposal_id: u64,
+    pub title: String,
+    pub options: Vec<String>,
+    /// Votes per option, in the same order as `options`.
+    pub tallies: Vec<u64>,
+    /// Unix timestamp from which votes are rejected and `finalize` is allowed.
+    pub deadline: i64,
+    pub finalized: bool,
+    /// Index of the option with the most votes, set by `finalize`; None on a tie.
+    pub winner: Option<u8>,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const MAX_TITLE: usize = 64;
+    pub const MAX_OPTIONS: usize = 8;
+    pub const MAX_OPTION_LEN: usize = 32;
+    pub const SIZE: usize = 32
+        + 8
+        + (4 + Self::MAX_TITLE)
+        + (4 + Self::MAX_OPTIONS * (4 + Self::MAX_OPTION_LEN))
+        + (4 + Self::MAX_OPTIONS * 8)
+        + 8
+        + 1
+        + (1 + 1)
+        + 1;
+
+    fn leading_option(&self) -> Option<u8> {
+        let top = *self.tallies.iter().max()?;
+        let mut leaders = self
+            .tallies
+            .iter()
+            .enumerate()
+            .filter(|(_, votes)| **votes == top);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) if top > 0 => Some(index as u8),
+            _ => None,
+        }
+    }
+}
+
+#[account]
+pub struct VoteReceipt {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub option: u8,
+    pub bump: u8,
+}
+
+impl VoteReceipt {
+    pub const SIZE: usize = 32 + 32 + 1 + 1;
+}
+
+#[error_code]
+pub enum VotingError {
+    #[msg("Title exceeds 64 bytes.")]
+    TitleTooLong,
+    #[msg("A proposal needs between 2 and 8 options.")]
+    InvalidOptionCount,
+    #[msg("An option exceeds 32 bytes.")]
+    OptionTooLong,
+    #[msg("The deadline must be in the future.")]
+    DeadlineInPast,
+    #[msg("The option index is out of range.")]
+    InvalidOption,
+    #[msg("Voting closed at the proposal's deadline.")]
+    VotingClosed,
+    #[msg("Voting is still open until the deadline.")]
+    VotingOpen,
+    #[msg("The proposal has already been finalized.")]
+    AlreadyFinalized,
+    #[msg("Vote tally overflowed.")]
+    MathOverflow,
+}
@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { {{PROGRAM_NAME_PASCAL}} } from "../target/types/{{PROGRAM_NAME_SNAKE}}";
+
+describe("{{PROGRAM_NAME_SNAKE}}", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.{{PROGRAM_NAME_PASCAL}} as Program<{{PROGRAM_NAME_PASCAL}}>;
+  const creator = provider.wallet.publicKey;
+
+  const OPTIONS = ["yes", "no", "abstain"];
+  const HOUR = 3_600;
+
+  let nextId = 0;
+
+  function pda(seeds: Buffer[]): anchor.web3.PublicKey {
+    const [address] = anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId);
+    return address;
+  }
+
+  function receiptAddress(
+    proposal: anchor.web3.PublicKey,
+    voter: anchor.web3.PublicKey
+  ): anchor.web3.PublicKey {
+    return pda([Buffer.from("receipt"), proposal.toBuffer(), voter.toBuffer()]);
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      assert.instanceOf(err, anchor.AnchorError);
+      assert.equal((err as anchor.AnchorError).error.errorCode.code, code);
+      return;
+    }
+    assert.fail(`expected ${code} error`);
+  }
+
+  // Creating an account that already exists fails inside the system program,
+  // which is not an Anchor error, so match on its log line instead.
+  async function expectLogError(promise: Promise<unknown>, message: string): Promise<void> {
+    try {
+      await promise;
+    } catch (err) {
+      const logs: string[] = (err as { logs?: string[] }).logs ?? [];
+      assert.isTrue(
+        logs.some((line) => line.includes(message)),
+        `expected "${message}" in logs:\n${logs.join("\n")}`
+      );
+      return;
+    }
+    assert.fail(`expected "${message}" failure`);
+  }
+
+  async function chainTime(): Promise<number> {
+    const slot = await provider.connection.getSlot();
+    return (await provider.connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+  }
+
+  // Voting closes at the proposal's deadline on the validator clock; tests that need
+  // a closed proposal set it seconds ahead and poll until a block is past it.
+  async function waitUntil(timestamp: number): Promise<void> {
+    while ((await chainTime()) <= timestamp) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+  }
+
+  async function fundedVoter(): Promise<anchor.web3.Keypair> {
+    const voter = anchor.web3.Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      voter.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature);
+    return voter;
+  }
+
+  function createProposal(deadline: number, options = OPTIONS) {
+    const proposalId = new anchor.BN(nextId++);
+    const proposal = pda([
+      Buffer.from("proposal"),
+      creator.toBuffer(),
+      proposalId.toArrayLike(Buffer, "le", 8),
+    ]);
+    const builder = program.methods
+      .createProposal(proposalId, "Ship it?", options, new anchor.BN(deadline))
+      .accountsPartial({ proposal, creator });
+    return { proposal, builder };
+  }
+
+  async function openProposal(deadline: number): Promise<anchor.web3.PublicKey> {
+    const { proposal, builder } = createProposal(deadline);
+    await builder.rpc();
+    return proposal;
+  }
+
+  function castVote(proposal: anchor.web3.PublicKey, voter: anchor.web3.Keypair, option: number) {
+    return program.methods
+      .castVote(option)
+      .accountsPartial({
+        proposal,
+        receipt: receiptAddress(proposal, voter.publicKey),
+        voter: voter.publicKey,
+      })
+      .signers([voter]);
+  }
+
+  function finalize(proposal: anchor.web3.PublicKey) {
+    return program.methods.finalize().accountsPartial({ proposal });
+  }
+
+  it("creates a proposal with empty tallies", async () => {
+    const deadline = (await chainTime()) + HOUR;
+    const proposal = await openProposal(deadline);
+
+    const account = await program.account.proposal.fetch(proposal);
+    assert.ok(account.creator.equals(creator));
+    assert.deepEqual(account.options, OPTIONS);
+    assert.deepEqual(account.tallies.map((tally) => tally.toNumber()), [0, 0, 0]);
+    assert.equal(account.deadline.toNumber(), deadline);
+    assert.isFalse(account.finalized);
+  });
+
+  it("rejects a deadline in the past", async () => {
+    const { builder } = createProposal((await chainTime()) - 1);
+    await expectError(builder.rpc(), "DeadlineInPast");
+  });
+
+  it("rejects a proposal with a single option", async () => {
+    const { builder } = createProposal((await chainTime()) + HOUR, ["yes"]);
+    await expectError(builder.rpc(), "InvalidOptionCount");
+  });
+
+  it("tallies votes and records a receipt", async () => {
+    const proposal = await openProposal((await chainTime()) + HOUR);
+    const voter = await fundedVoter();
+
+    await castVote(proposal, voter, 1).rpc();
+
+    const account = await program.account.proposal.fetch(proposal);
+    assert.deepEqual(account.tallies.map((tally) => tally.toNumber()), [0, 1, 0]);
+    const receipt = await program.account.voteReceipt.fetch(
+      receiptAddress(proposal, voter.publicKey)
+    );
+    assert.ok(receipt.voter.equals(voter.publicKey));
+    assert.equal(receipt.option, 1);
+  });
+
+  it("rejects a second vote from the same voter", async () => {
+    const proposal = await openProposal((await chainTime()) + HOUR);
+    const voter = await fundedVoter();
+    await castVote(proposal, voter, 0).rpc();
+
+    // The voter's receipt PDA already exists, so it cannot be initialised again.
+    await expectLogError(castVote(proposal, voter, 1).rpc(), "already in use");
+
+    const account = await program.account.proposal.fetch(proposal);
+    assert.deepEqual(account.tallies.map((tally) => tally.toNumber()), [1, 0, 0]);
+  });
+
+  it("rejects an option index out of range", async () => {
+    const proposal = await openProposal((await chainTime()) + HOUR);
+    await expectError(castVote(proposal, await fundedVoter(), 3).rpc(), "InvalidOption");
+  });
+
+  it("rejects votes after the deadline and finalizes the winner", async () => {
+    const voters = [await fundedVoter(), await fundedVoter(), await fundedVoter()];
+    const deadline = (await chainTime()) + 5;
+    const proposal = await openProposal(deadline);
+    await castVote(proposal, voters[0], 2).rpc();
+    await castVote(proposal, voters[1], 2).rpc();
+    await expectError(finalize(proposal).rpc(), "VotingOpen");
+
+    await waitUntil(deadline);
+
+    await expectError(castVote(proposal, voters[2], 0).rpc(), "VotingClosed");
+    await finalize(proposal).rpc();
+    const account = await program.account.proposal.fetch(proposal);
+    assert.isTrue(account.finalized);
+    assert.equal(account.winner, 2);
+    await expectError(finalize(proposal).rpc(), "AlreadyFinalized");
+  });
+
+  it("leaves the winner empty on a tie", async () => {
+    const voters = [await fundedVoter(), await fundedVoter()];
+    const deadline = (await chainTime()) + 5;
+    const proposal = await openProposal(deadline);
+    await castVote(proposal, voters[0], 0).rpc();
+    await castVote(proposal, voters[1], 1).rpc();
+
+    await waitUntil(deadline);
+
+    await finalize(proposal).rpc();
+    assert.isNull((await program.account.proposal.fetch(proposal)).winner);
+  });
+});
@@ -21,9 +21,14 @@ from solcoder.core import (
     ConfigContext,
     ConfigManager,
     TemplateError,
+    list_templates,
     render_template,
 )
 from solcoder.core.config import CONFIG_FILENAME
+from solcoder.core.program_ids import check_program_ids
+from solcoder.core.program_rename import rename_program
+from solcoder.core.template_manifest import ManifestError, TemplateVariable
+from solcoder.core.template_stubs import stub_instructions
 from solcoder.core.llm import LLMClient, LLMError, LLMSettings
 from solcoder.session import SessionLoadError, SessionManager
 from solcoder.session.manager import MAX_SESSIONS
@@ -806,6 +811,104 @@ def version() -> None:
     styled_echo(f"SolCoder CLI version {pkg_version}")
 
 
+def _format_variable(variable: TemplateVariable) -> str:
+    if variable.default is None:
+        return f"{variable.name} (required)"
+    return f"{variable.name} (default: {variable.default!r})"
+
+
+@app.command("list-templates")
+def list_templates_command() -> None:
+    """List bundled templates with their manifest variables."""
+    templates = list_templates()
+    if not templates:
+        styled_echo("No templates found.")
+        return
+    for info in templates:
+        status = "complete" if info.complete else "stub"
+        styled_echo(f"{info.key} ({status}) - {info.name}: {info.description or 'no description'}")
+        if not info.variables:
+            styled_echo("  no variables declared (missing solcoder.toml)")
+        for variable in info.variables:
+            styled_echo(f"  {_format_variable(variable)}")
+
+
+@app.command()
+def doctor(
+    template: str | None = typer.Argument(None, help="Check only this template key"),  # noqa: B008
+) -> None:
+    """Flag template instructions that ship without an implementation."""
+    templates = [info for info in list_templates() if template in (None, info.key)]
+    if not templates:
+        styled_echo(f"❌ Unknown template '{template}'.")
+        raise typer.Exit(code=2)
+    incomplete = False
+    for info in templates:
+        if info.path is None or not any(info.path.glob("programs/*/src/lib.rs")):
+            styled_echo(f"{info.key}: stub - no program source")
+            incomplete = True
+            continue
+        try:
+            stubs = stub_instructions(info.path)
+        except ManifestError as exc:
+            styled_echo(f"{info.key}: {exc}")
+            incomplete = True
+            continue
+        if not stubs:
+            styled_echo(f"{info.key}: ok")
+            continue
+        incomplete = True
+        styled_echo(f"{info.key}: {len(stubs)} stub instruction(s) left to implement")
+        for stub in stubs:
+            styled_echo(f"  {stub.path}:{stub.line}: {stub.name}")
+    if incomplete:
+        raise typer.Exit(code=1)
+
+
+@app.command()
+def rename(
+    old: str = typer.Option(..., "--from", help="Current program name"),  # noqa: B008
+    new: str = typer.Option(..., "--to", help="New program name"),  # noqa: B008
+    path: Path = typer.Option(Path("."), "--path", help="Anchor workspace root"),  # noqa: B008
+    new_keypair: bool = typer.Option(False, "--new-keypair", help="Generate a new program keypair and id"),  # noqa: B008
+) -> None:
+    """Rename a scaffolded program's module, crate, paths and client references."""
+    try:
+        result = rename_program(path, old, new, new_keypair=new_keypair)
+    except TemplateError as exc:
+        styled_echo(f"❌ {exc}")
+        raise typer.Exit(code=1) from exc
+    styled_echo(f"Renamed program '{result.old}' to '{result.new}' ({len(result.changed)} files updated).")
+    for item in result.changed:
+        styled_echo(f"  {item}")
+    if result.program_id is not None:
+        styled_echo(f"New program id: {result.program_id}")
+    styled_echo("Run `anchor build` to regenerate the IDL and types under target/.")
+
+
+@app.command("verify-id-consistency")
+def verify_id_consistency(
+    path: Path = typer.Option(Path("."), "--path", help="Anchor workspace root"),  # noqa: B008
+) -> None:
+    """Check declare_id!, Anchor.toml, deploy keypairs and TS clients agree on each program id."""
+    root = path.expanduser().resolve()
+    if not (root / "Anchor.toml").exists():
+        styled_echo(f"❌ No Anchor.toml in {root}.")
+        raise typer.Exit(code=2)
+    try:
+        mismatches = check_program_ids(root)
+    except TemplateError as exc:
+        styled_echo(f"❌ {exc}")
+        raise typer.Exit(code=2) from exc
+    if not mismatches:
+        styled_echo("Program ids are consistent.")
+        return
+    styled_echo(f"{len(mismatches)} program id mismatch(es):")
+    for mismatch in mismatches:
+        styled_echo(f"  {mismatch}")
+    raise typer.Exit(code=1)
+
+
 def _extract_dump_args(args: list[str]) -> tuple[str | None, str, Path | None, list[str]]:
     session_id: str | None = None
     dump_format = "json"
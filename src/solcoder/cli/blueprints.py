@@ -6,6 +6,10 @@ from dataclasses import dataclass
 from pathlib import Path
 from typing import Any
 
+from solcoder.core.template_manifest import ManifestError, TemplateManifest, load_manifest
+from solcoder.core.templates import TemplateError
+from solcoder.core.templates import normalise_program_name as normalise_program_name
+
 BLUEPRINTS_ROOT = Path(__file__).resolve().parents[1] / "anchor" / "blueprints"
 REGISTRY_PATH = BLUEPRINTS_ROOT / "registry.json"
 
@@ -126,28 +130,144 @@ def _should_ask_question(
     return str(value).lower() in normalized_expected
 
 
-def prompt_wizard(app, questions: list[dict[str, Any]], defaults: dict[str, Any]) -> dict[str, Any]:
+class WizardInputError(ValueError):
+    """Raised when a wizard answer is missing or invalid and prompting is disabled."""
+
+
+def load_blueprint_manifest(key: str) -> TemplateManifest | None:
+    """Return the solcoder.toml manifest bundled with blueprint ``key``, if any."""
+    try:
+        return load_manifest(BLUEPRINTS_ROOT / key / "template")
+    except ManifestError:
+        return None
+
+
+def _answer_error(
+    question: dict[str, Any],
+    value: str,
+    manifest: TemplateManifest | None,
+) -> str | None:
+    """Return why ``value`` is not acceptable for ``question``, or None when it is."""
+    pattern = question.get("pattern")
+    if pattern and not re.match(pattern, value):
+        return f"'{value}' does not match {pattern}"
+    minimum = question.get("min")
+    maximum = question.get("max")
+    if minimum is not None or maximum is not None:
+        try:
+            number = int(value)
+        except ValueError:
+            return f"'{value}' is not an integer"
+        if (minimum is not None and number < minimum) or (maximum is not None and number > maximum):
+            return f"{number} is not between {minimum} and {maximum}"
+    if manifest is not None:
+        # program_name feeds PROGRAM_NAME_SNAKE once normalised; other answers map explicitly.
+        if question.get("key") == "program_name":
+            try:
+                placeholder, rendered = "PROGRAM_NAME_SNAKE", normalise_program_name(value)
+            except TemplateError as exc:
+                return str(exc).rstrip(".")
+        else:
+            placeholder, rendered = question.get("placeholder"), value
+        variable = manifest.variables.get(str(placeholder)) if placeholder else None
+        if variable is not None:
+            try:
+                variable.validate(rendered)
+            except ManifestError as exc:
+                return str(exc).rstrip(".")
+    return None
+
+
+def prompt_wizard(
+    app,
+    questions: list[dict[str, Any]],
+    defaults: dict[str, Any],
+    *,
+    interactive: bool = True,
+    manifest: TemplateManifest | None = None,
+    max_attempts: int = 3,
+) -> dict[str, Any]:
+    """Collect answers for ``questions``, seeding each from ``defaults``.
+
+    Interactive runs re-prompt on invalid input and fall back to the default after
+    ``max_attempts``; a question without a default raises WizardInputError instead.
+    With ``interactive=False`` nothing is prompted: values come from
+    ``defaults`` or the question's own default, and a missing or invalid value raises
+    WizardInputError.
+    """
     answers: dict[str, Any] = {}
     for q in questions:
         key = q.get("key")
         prompt = q.get("prompt") or key
         default = q.get("default")
-        pattern = q.get("pattern")
         if key is None or prompt is None:
             continue
         if not _should_ask_question(q, answers, defaults):
             continue
         if key in defaults:
             default = defaults[key]
-        val = app._prompt_text(f"{prompt}") if default is None else app._prompt_text(f"{prompt} [{default}]")
-        val = (val or "").strip() or (str(default) if default is not None else "")
-        if pattern and not re.match(pattern, val):
-            app.console.print(f"[yellow]Value for {key} does not match expected pattern; using default.[/yellow]")
-            val = str(default) if default is not None else val
+        if not interactive:
+            if default is None:
+                raise WizardInputError(f"'{key}' is required; pass --var {key}=<value>.")
+            val = str(default).strip()
+            error = _answer_error(q, val, manifest)
+            if error:
+                raise WizardInputError(f"Invalid value for {key}: {error}.")
+            answers[key] = val
+            continue
+        for _ in range(max_attempts):
+            val = app._prompt_text(f"{prompt}") if default is None else app._prompt_text(f"{prompt} [{default}]")
+            val = (val or "").strip() or (str(default) if default is not None else "")
+            error = "a value is required" if default is None and not val else _answer_error(q, val, manifest)
+            if error is None:
+                break
+            app.console.print(f"[yellow]Invalid value for {key}: {error}.[/yellow]")
+        else:
+            if default is None:
+                raise WizardInputError(
+                    f"No valid value for {key} after {max_attempts} attempts: {error}."
+                )
+            app.console.print(f"[yellow]Using the default for {key}.[/yellow]")
+            val = str(default)
         answers[key] = val
     return answers
 
 
+def resolve_placeholder_values(
+    questions: list[dict[str, Any]],
+    answers: dict[str, Any],
+) -> dict[str, str]:
+    """Map wizard answers onto template placeholders, applying defaults and limits.
+
+    Only questions that declare a `placeholder` contribute. Questions with `min`/`max`
+    must resolve to an integer within that inclusive range.
+    """
+    values: dict[str, str] = {}
+    for q in questions:
+        key = q.get("key")
+        placeholder = q.get("placeholder")
+        if not key or not placeholder:
+            continue
+        raw = answers.get(key)
+        if raw is None or str(raw).strip() == "":
+            raw = q.get("default")
+        if raw is None:
+            raise ValueError(f"'{key}' is required.")
+        text = str(raw).strip()
+        minimum = q.get("min")
+        maximum = q.get("max")
+        if minimum is not None or maximum is not None:
+            try:
+                number = int(text)
+            except ValueError:
+                raise ValueError(f"'{key}' must be an integer.") from None
+            if (minimum is not None and number < minimum) or (maximum is not None and number > maximum):
+                raise ValueError(f"'{key}' must be between {minimum} and {maximum}.")
+            text = str(number)
+        values[str(placeholder)] = text
+    return values
+
+
 def persist_answers_readme(root: Path, answers: dict[str, Any]) -> None:
     readme = root / "README.md"
     if not readme.exists():
@@ -158,8 +278,3 @@ def persist_answers_readme(root: Path, answers: dict[str, Any]) -> None:
         lines.append(f"- {k}: {v}")
     readme.write_text("\n".join(lines) + "\n")
 
-
-def normalise_program_name(name: str) -> str:
-    base = re.sub(r"[^0-9a-zA-Z]+", "_", name.strip())
-    base = base.lower().strip("_")
-    return base or "counter"
@@ -7,7 +7,24 @@ from pathlib import Path
 from typing import TYPE_CHECKING
 
 from solcoder.cli.types import CommandResponse, CommandRouter, SlashCommand
-from solcoder.core import RenderOptions, TemplateError, render_template
+from solcoder.core import PlannedFile, RenderOptions, TemplateError, preview_template, render_template
+from solcoder.core.templates import (
+    PLACEHOLDER_PROGRAM_ID,
+    ensure_gitignore,
+    merge_validator_clones,
+    program_keypair_path,
+    read_program_id,
+)
+from solcoder.cli.template_utils import (
+    anchor_version_diagnostics,
+    format_build_check,
+    format_preview,
+    program_id_diagnostics,
+    rent_report,
+    space_diagnostics,
+)
+from solcoder.core.template_verify import verify_build
+from solcoder.core.workspace_templates import CLIENT_PATH, client_path, merge_program_client
 from solcoder.cli.blueprints import (
     persist_answers_readme,
     normalise_program_name,
@@ -24,12 +41,17 @@ if TYPE_CHECKING:  # pragma: no cover
 def register(app: CLIApp, router: CommandRouter) -> None:
     def handle(app: CLIApp, args: list[str]) -> CommandResponse:
         if not args or args[0] != "scaffold":
-            return CommandResponse(messages=[("system", "Usage: /blueprint scaffold --key <key> --target <dir> [--workspace <dir>] --answers-json <json>")])
+            return CommandResponse(messages=[("system", "Usage: /blueprint scaffold --key <key> --target <dir> [--workspace <dir>] --answers-json <json> [--license <id>] [--anchor-version <semver>] [--force | --merge] [--dry-run] [--verify]")])
         key: str | None = None
         target: Path | None = None
         workspace: Path | None = None
         answers_json: str | None = None
         force: bool = False
+        merge: bool = False
+        dry_run: bool = False
+        verify: bool = False
+        license_id: str | None = None
+        anchor_version: str | None = None
 
         i = 1
         while i < len(args):
@@ -77,6 +99,26 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 force = True
                 i += 1
                 continue
+            if tok == "--merge":
+                merge = True
+                i += 1
+                continue
+            if tok == "--dry-run":
+                dry_run = True
+                i += 1
+                continue
+            if tok == "--license" and i + 1 < len(args):
+                license_id = args[i + 1]
+                i += 2
+                continue
+            if tok == "--anchor-version" and i + 1 < len(args):
+                anchor_version = args[i + 1]
+                i += 2
+                continue
+            if tok == "--verify":
+                verify = True
+                i += 1
+                continue
             return CommandResponse(messages=[("system", f"Unknown or misplaced arg '{tok}'.")])
 
         if not key or target is None or answers_json is None:
@@ -94,6 +136,12 @@ def register(app: CLIApp, router: CommandRouter) -> None:
             network = getattr(cfg_ctx.config, "network", None)
         cluster = answers.get("cluster") or network or "devnet"
         program_id = answers.get("program_id") or "replace-with-program-id"
+        program_keypair = Path(answers["program_keypair"]).expanduser() if answers.get("program_keypair") else None
+
+        if dry_run:
+            return _dry_run(
+                key, target, workspace, program_name, author, cluster, program_id, program_keypair, answers, license_id, anchor_version
+            )
 
         # Insertion into existing workspace if provided
         if workspace is not None and (workspace / "Anchor.toml").exists():
@@ -110,6 +158,10 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                     tpl_path = None
                     if key in reg and reg[key].template_path:
                         tpl_path = resolve_registry_template_path(reg[key].template_path)
+                    existing_keypair = program_keypair_path(workspace, normalise_program_name(program_name))
+                    if merge and program_keypair is None and program_id == PLACEHOLDER_PROGRAM_ID and existing_keypair.exists():
+                        # Kept files still declare the existing program id.
+                        program_keypair = existing_keypair
                     opts = RenderOptions(
                         template=key,
                         destination=staging_root,
@@ -119,26 +171,45 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                         program_id=program_id,
                         overwrite=force,
                         template_path=tpl_path,
+                        answers=answers,
+                        program_keypair=program_keypair,
+                        license=license_id,
+                        anchor_version=anchor_version,
                     )
-                    render_template(opts)
+                    try:
+                        render_template(opts)
+                    except TemplateError as exc:
+                        return CommandResponse(messages=[("system", f"Template error: {exc}")])
+                    rent = rent_report(opts, staging_root)
+                    space_warning = space_diagnostics(opts, staging_root)
                     src_prog = staging_root / "programs" / normalise_program_name(program_name)
                     dst_prog = workspace / "programs" / src_prog.name
-                    if dst_prog.exists():
+                    if dst_prog.exists() and not merge:
                         if not force:
-                            return CommandResponse(messages=[("system", f"Program '{src_prog.name}' already exists. Re-run with --force to overwrite.")])
+                            return CommandResponse(messages=[("system", f"Program '{src_prog.name}' already exists. Re-run with --force to overwrite or --merge to keep its files and add the rest.")])
                         shutil.rmtree(dst_prog)
-                    shutil.copytree(src_prog, dst_prog)
+                    # With --merge, files already in the workspace are kept.
+                    copy = _copy_missing if merge else shutil.copy2
+                    shutil.copytree(src_prog, dst_prog, copy_function=copy, dirs_exist_ok=merge)
+                    # carry the program keypair so Anchor.toml and declare_id! agree
+                    staged_keypair = program_keypair_path(staging_root, src_prog.name)
+                    if staged_keypair.exists():
+                        program_id = read_program_id(staged_keypair)
+                        dst_keypair = program_keypair_path(workspace, src_prog.name)
+                        dst_keypair.parent.mkdir(parents=True, exist_ok=True)
+                        copy(staged_keypair, dst_keypair)
                     # copy tests
                     (workspace / "tests").mkdir(parents=True, exist_ok=True)
                     for test_file in (staging_root / "tests").glob("*.ts"):
-                        shutil.copy2(test_file, workspace / "tests" / test_file.name)
+                        copy(test_file, workspace / "tests" / test_file.name)
                     # copy scripts (helper demos)
                     src_scripts = staging_root / "scripts"
                     if src_scripts.exists():
                         (workspace / "scripts").mkdir(parents=True, exist_ok=True)
                         for script_file in src_scripts.glob("*"):
                             if script_file.is_file():
-                                shutil.copy2(script_file, workspace / "scripts" / script_file.name)
+                                copy(script_file, workspace / "scripts" / script_file.name)
+                    merge_program_client(staging_root, workspace, src_prog.name)
                     # patch Anchor.toml
                     anchor_toml = workspace / "Anchor.toml"
                     text = anchor_toml.read_text()
@@ -150,6 +221,7 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                     else:
                         text = text.rstrip() + f"\n\n{sect}\n{prog_line}\n"
                     anchor_toml.write_text(text)
+                    merge_validator_clones(staging_root / "Anchor.toml", anchor_toml)
                     try:
                         cfg = deploy_mod.load_anchor_config(anchor_toml)
                         deploy_mod.ensure_toolchain_version(
@@ -162,18 +234,38 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                     # patch Cargo.toml
                     cargo = workspace / "Cargo.toml"
                     ctext = cargo.read_text() if cargo.exists() else ""
-                    member = f"\"programs/{src_prog.name}\""
-                    if member not in ctext:
+                    # The program crate plus any crate nested in it (e.g. its `cpi` client).
+                    crates = [f"programs/{src_prog.name}"] + [
+                        f"programs/{src_prog.name}/{manifest.parent.name}" for manifest in sorted(src_prog.glob("*/Cargo.toml"))
+                    ]
+                    original = ctext
+                    for crate in crates:
+                        member = f"\"{crate}\""
+                        if member in ctext:
+                            continue
                         if re.search(r"(?s)\[workspace\].*members\s*=\s*\[", ctext):
                             ctext = re.sub(r"(?s)(members\s*=\s*\[)(.*?)(\])", lambda m: m.group(1) + (m.group(2).rstrip() + (",\n    " if m.group(2).strip() else "\n    ") + member) + "\n]", ctext)
                         else:
                             ctext += f"\n[workspace]\nmembers = [\n    {member},\n]\n"
+                    if ctext != original:
                         cargo.write_text(ctext)
+                    ensure_gitignore(workspace)
                     persist_answers_readme(workspace, answers)
 
             app.session_context.metadata.active_project = str(workspace)
             app.session_manager.save(app.session_context)
-            return CommandResponse(messages=[("system", f"Program '{program_name}' added to workspace {workspace}.")])
+            message = f"Program '{program_name}' added to workspace {workspace}."
+            if rent:
+                message = f"{message}\n{rent}"
+            if space_warning:
+                message = f"{message}\n{space_warning}"
+            id_warning = program_id_diagnostics(workspace)
+            if id_warning:
+                message = f"{message}\n{id_warning}"
+            version_warning = anchor_version_diagnostics(opts)
+            if version_warning:
+                message = f"{message}\n{version_warning}"
+            return CommandResponse(messages=[("system", _with_verify(app, message, workspace) if verify else message)])
 
         # Otherwise, scaffold fresh workspace at target
         with app.console.status(f"Scaffolding '{key}' blueprint…", spinner="dots"):
@@ -191,7 +283,12 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                     cluster=cluster,
                     program_id=program_id,
                     overwrite=force,
+                    merge=merge,
                     template_path=tpl_path,
+                    answers=answers,
+                    program_keypair=program_keypair,
+                    license=license_id,
+                    anchor_version=anchor_version,
                 )
                 output = render_template(opts)
             except TemplateError as exc:
@@ -214,9 +311,99 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 )
         except Exception:
             pass
-        return CommandResponse(messages=[("system", f"Blueprint '{key}' rendered to {target}")])
+        message = f"Blueprint '{key}' rendered to {target}"
+        rent = rent_report(opts, output)
+        if rent:
+            message = f"{message}\n{rent}"
+        space_warning = space_diagnostics(opts, output)
+        if space_warning:
+            message = f"{message}\n{space_warning}"
+        id_warning = program_id_diagnostics(output)
+        if id_warning:
+            message = f"{message}\n{id_warning}"
+        version_warning = anchor_version_diagnostics(opts)
+        if version_warning:
+            message = f"{message}\n{version_warning}"
+        return CommandResponse(messages=[("system", _with_verify(app, message, target) if verify else message)])
 
     router.register(SlashCommand("blueprint", handle, "Internal blueprint scaffolder"))
 
 
+def _copy_missing(source: Path, target: Path) -> None:
+    """copy2 that leaves an existing ``target`` alone; used for --merge."""
+    if not Path(target).exists():
+        shutil.copy2(source, target)
+
+
+def _with_verify(app: CLIApp, message: str, root: Path) -> str:
+    """Append the --verify build report for ``root`` to ``message``."""
+    try:
+        with app.console.status("Verifying the generated project builds…", spinner="dots"):
+            check = verify_build(root)
+    except TemplateError as exc:
+        return f"{message}\nVerify error: {exc}"
+    if not check.ok:
+        app.log_event("build", f"Build check failed in {root}", severity="error")
+    return f"{message}\n{format_build_check(check, root)}"
+
+
+def _dry_run(
+    key: str,
+    target: Path,
+    workspace: Path | None,
+    program_name: str,
+    author: str,
+    cluster: str,
+    program_id: str,
+    program_keypair: Path | None,
+    answers: dict,
+    license_id: str | None = None,
+    anchor_version: str | None = None,
+) -> CommandResponse:
+    """Report what a scaffold would write without touching the filesystem."""
+    from solcoder.cli.blueprints import load_registry
+
+    reg = {e.key: e for e in load_registry()}
+    tpl_path = resolve_registry_template_path(reg[key].template_path) if key in reg and reg[key].template_path else None
+    inserting = workspace is not None and (workspace / "Anchor.toml").exists()
+    root = workspace if inserting else target
+    opts = RenderOptions(
+        template=key,
+        destination=root,
+        program_name=program_name,
+        author_pubkey=author,
+        cluster=cluster,
+        program_id=program_id,
+        template_path=tpl_path,
+        answers=answers,
+        program_keypair=program_keypair,
+        license=license_id,
+        anchor_version=anchor_version,
+    )
+    try:
+        planned = preview_template(opts)
+    except TemplateError as exc:
+        return CommandResponse(messages=[("system", f"Template error: {exc}")])
+    if inserting:
+        # Insertion only copies the program crate, tests, scripts, client and the program keypair.
+        program_dir = Path("programs") / normalise_program_name(program_name)
+        keypair = program_keypair_path(Path(), program_dir.name)
+        if (root / CLIENT_PATH).exists():
+            planned = [
+                PlannedFile(path=client_path(program_dir.name), source=item.source) if item.path == CLIENT_PATH else item
+                for item in planned
+            ]
+        planned = [
+            item
+            for item in planned
+            if program_dir in item.path.parents
+            or item.path in {keypair, CLIENT_PATH, client_path(program_dir.name)}
+            or (item.path.parent.name in {"tests", "scripts"} and len(item.path.parts) == 2)
+        ]
+        header = f"Dry run: '{key}' would add {len(planned)} files to workspace {root} and patch Anchor.toml and Cargo.toml (nothing written)."
+    else:
+        header = f"Dry run: '{key}' would write {len(planned)} files to {root} (nothing written)."
+    return CommandResponse(messages=[("system", f"{header}\n{format_preview(planned, root)}")])
+
+
 __all__ = ["register"]
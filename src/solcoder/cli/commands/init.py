@@ -8,6 +8,7 @@ from pathlib import Path
 from typing import TYPE_CHECKING
 
 from solcoder.cli.types import CommandResponse, CommandRouter, SlashCommand
+from solcoder.core.templates import ensure_gitignore
 from solcoder.solana import deploy as deploy_mod
 
 if TYPE_CHECKING:  # pragma: no cover
@@ -66,6 +67,7 @@ def _offline_scaffold(root: Path, *, name: str, force: bool) -> None:
     _write_file(root / "Anchor.toml", anchor_toml)
     _write_file(root / "Cargo.toml", cargo_toml)
     _write_file(root / ".gitignore", gitignore)
+    ensure_gitignore(root)
     _write_file(root / "README.md", readme)
     (root / "programs").mkdir(parents=True, exist_ok=True)
 
@@ -185,7 +187,7 @@ def register(app: CLIApp, router: CommandRouter) -> None:
         summary = (
             f"Anchor workspace initialized at {workspace_root}.\n"
             "Next steps:\n"
-            "  - /new counter (or token, nft, registry, escrow)\n"
+            "  - /new counter (or token, nft, registry, escrow, staking, multisig, vesting, voting)\n"
             "  - /deploy once ready to build and deploy"
         )
         return CommandResponse(messages=[("system", summary)])
@@ -14,6 +14,8 @@ from typing import TYPE_CHECKING
 from rich.panel import Panel
 
 from solcoder.cli.blueprints import (
+    WizardInputError,
+    load_blueprint_manifest,
     load_registry,
     load_wizard_schema,
     prompt_wizard,
@@ -21,7 +23,23 @@ from solcoder.cli.blueprints import (
 )
 from solcoder.cli.types import CommandResponse, CommandRouter, SlashCommand
 from solcoder.cli.commands.deploy import _rpc_for_cluster
-from solcoder.core import RenderOptions, TemplateError, available_templates, render_template
+from solcoder.cli.template_utils import (
+    anchor_version_diagnostics,
+    format_build_check,
+    format_preview,
+    program_id_diagnostics,
+)
+from solcoder.core import (
+    DEFAULT_CONFIG_DIR,
+    RenderOptions,
+    TemplateError,
+    available_templates,
+    preview_template,
+    render_template,
+)
+from solcoder.core.remote_templates import fetch_git_template, is_git_template
+from solcoder.core.template_verify import verify_build
+from solcoder.core.workspace_templates import error_code_offsets, preview_workspace, render_workspace
 from solcoder.solana.constants import TOKEN_2022_PROGRAM_ID
 import json
 
@@ -29,7 +47,7 @@ if TYPE_CHECKING:  # pragma: no cover
     from solcoder.cli.app import CLIApp
 
 
-KNOWN_KEYS = {"counter", "token", "nft", "registry", "escrow"}
+KNOWN_KEYS = {"counter", "token", "nft", "registry", "escrow", "staking", "multisig", "vesting", "voting"}
 
 TOKEN_2022_PROGRAM_ARGS = ["--program-id", TOKEN_2022_PROGRAM_ID]
 
@@ -42,6 +60,150 @@ def _prompt_or_default(app: CLIApp, prompt: str, default: str) -> str:
         return default
 
 
+def _scaffold_remote_template(
+    app: CLIApp,
+    spec: str,
+    *,
+    dest: Path | None,
+    program_name: str | None,
+    author: str | None,
+    cluster: str | None,
+    program_id: str | None,
+    program_keypair: str | None,
+    force: bool,
+    dry_run: bool,
+    verify: bool,
+    license_id: str | None = None,
+    merge: bool = False,
+    anchor_version: str | None = None,
+) -> CommandResponse:
+    """Render a git+<url>#<ref> template through the same pipeline as bundled ones."""
+    if not is_git_template(spec):
+        return CommandResponse(messages=[("system", f"--template expects git+<url>#<ref>, got '{spec}'.")])
+    defaults = app._default_template_metadata()
+    program_name = program_name or defaults.get("program_name") or "program"
+    cfg = getattr(app, "config_context", None)
+    try:
+        with app.console.status(f"Fetching template {spec}…", spinner="dots"):
+            template_dir = fetch_git_template(spec, DEFAULT_CONFIG_DIR / "template-cache")
+    except TemplateError as exc:
+        return CommandResponse(messages=[("system", f"Template error: {exc}")])
+    target = dest or (Path.cwd() / f"{program_name}-workspace")
+    options = RenderOptions(
+        template="remote",
+        destination=target,
+        program_name=program_name,
+        author_pubkey=author or defaults.get("author_pubkey", "CHANGEME"),
+        program_id=program_id or "replace-with-program-id",
+        cluster=cluster or getattr(getattr(cfg, "config", None), "network", None) or "localnet",
+        overwrite=force,
+        merge=merge,
+        template_path=template_dir,
+        program_keypair=Path(program_keypair) if program_keypair else None,
+        license=license_id,
+        anchor_version=anchor_version,
+    )
+    try:
+        if dry_run:
+            planned = preview_template(options)
+            header = f"Dry run: {spec} would write {len(planned)} files to {target.resolve()} (nothing written)."
+            return CommandResponse(messages=[("system", f"{header}\n{format_preview(planned, target.resolve())}")])
+        output = render_template(options)
+    except TemplateError as exc:
+        return CommandResponse(messages=[("system", f"Template error: {exc}")])
+    app.session_context.metadata.active_project = str(output)
+    app.session_manager.save(app.session_context)
+    message = f"Template {spec} rendered to {output}"
+    id_warning = program_id_diagnostics(output)
+    if id_warning:
+        message = f"{message}\n{id_warning}"
+    version_warning = anchor_version_diagnostics(options)
+    if version_warning:
+        message = f"{message}\n{version_warning}"
+    if verify:
+        try:
+            with app.console.status("Verifying the generated project builds…", spinner="dots"):
+                message = f"{message}\n{format_build_check(verify_build(output), output)}"
+        except TemplateError as exc:
+            message = f"{message}\nVerify error: {exc}"
+    return CommandResponse(messages=[("system", message)])
+
+
+def _scaffold_workspace(
+    app: CLIApp,
+    spec: str,
+    *,
+    dest: Path | None,
+    author: str | None,
+    cluster: str | None,
+    force: bool,
+    dry_run: bool,
+    verify: bool,
+    license_id: str | None = None,
+    anchor_version: str | None = None,
+    namespace_errors: bool = False,
+) -> CommandResponse:
+    """Render `--programs key[:name],...` into one multi-program Anchor workspace."""
+    defaults = app._default_template_metadata()
+    cfg = getattr(app, "config_context", None)
+    target = dest or (Path.cwd() / "anchor-workspace")
+    programs: list[RenderOptions] = []
+    for item in (part.strip() for part in spec.split(",")):
+        if not item:
+            continue
+        key, _, name = item.partition(":")
+        key = key.strip().lower()
+        if key not in KNOWN_KEYS:
+            return CommandResponse(messages=[("system", f"Unknown blueprint '{key}' in --programs. Available: {', '.join(sorted(KNOWN_KEYS))}")])
+        template_path = None
+        entry = next((e for e in load_registry() if e.key == key), None)
+        if entry is not None and entry.template_path:
+            template_path = resolve_registry_template_path(entry.template_path)
+        programs.append(
+            RenderOptions(
+                template=key,
+                destination=target,
+                program_name=name.strip() or key,
+                author_pubkey=author or defaults.get("author_pubkey", "CHANGEME"),
+                cluster=cluster or getattr(getattr(cfg, "config", None), "network", None) or "localnet",
+                template_path=template_path,
+                license=license_id,
+                anchor_version=anchor_version,
+            )
+        )
+    if len(programs) < 2:
+        return CommandResponse(messages=[("system", "--programs expects at least two entries, e.g. --programs token,escrow:my_escrow")])
+    names = ", ".join(options.program_name for options in programs)
+    try:
+        if dry_run:
+            planned = preview_workspace(programs, target)
+            header = f"Dry run: workspace with {names} would write {len(planned)} files to {target.resolve()} (nothing written)."
+            return CommandResponse(messages=[("system", f"{header}\n{format_preview(planned, target.resolve())}")])
+        with app.console.status(f"Scaffolding workspace with {names}…", spinner="dots"):
+            output = render_workspace(programs, target, overwrite=force, namespace_errors=namespace_errors)
+    except TemplateError as exc:
+        return CommandResponse(messages=[("system", f"Template error: {exc}")])
+    app.session_context.metadata.active_project = str(output)
+    app.session_manager.save(app.session_context)
+    message = f"Workspace with {names} rendered to {output}"
+    if namespace_errors:
+        ranges = ", ".join(f"{snake} from {offset}" for snake, offset in error_code_offsets(programs).items())
+        message = f"{message}\nCustom error codes: {ranges}."
+    id_warning = program_id_diagnostics(output)
+    if id_warning:
+        message = f"{message}\n{id_warning}"
+    version_warning = anchor_version_diagnostics(programs[0])
+    if version_warning:
+        message = f"{message}\n{version_warning}"
+    if verify:
+        try:
+            with app.console.status("Verifying the generated project builds…", spinner="dots"):
+                message = f"{message}\n{format_build_check(verify_build(output), output)}"
+        except TemplateError as exc:
+            message = f"{message}\nVerify error: {exc}"
+    return CommandResponse(messages=[("system", message)])
+
+
 def _auto_create_local_metadata(app: "CLIApp", *, name: str, symbol: str) -> str:
     """Create a minimal metadata JSON locally and return a file:// URI.
 
@@ -77,16 +239,28 @@ def register(app: CLIApp, router: CommandRouter) -> None:
     def handle(app: CLIApp, args: list[str]) -> CommandResponse:
         if not args:
             keys = ", ".join(sorted(KNOWN_KEYS))
-            return CommandResponse(messages=[("system", f"Usage: /new <key> [--dir <path>] [--program <name>] [--author <pubkey>] [--cluster <cluster>] [--program-id <id>] [--force]\nAvailable keys: {keys}")])
+            return CommandResponse(messages=[("system", f"Usage: /new <key> [--dir | --output-dir <path>] [--program <name>] [--author <pubkey>] [--cluster <cluster>] [--program-id <id>] [--program-keypair <path>] [--var <key>=<value>] [--license <id>] [--anchor-version <semver>] [--no-input] [--dry-run] [--verify] [--force | --merge]\n       /new --template git+<url>#<ref> [--dir | --output-dir <path>] [--program <name>] [--license <id>] [--anchor-version <semver>] [--dry-run] [--verify] [--force | --merge]\n       /new --programs <key>[:<name>],<key>[:<name>]... [--dir | --output-dir <path>] [--license <id>] [--anchor-version <semver>] [--namespace-errors] [--dry-run] [--verify] [--force]\nAvailable keys: {keys}")])
 
-        key = args[0].strip().lower()
+        # `/new --template git+<url>#<ref>` and `/new --programs ...` take no positional key.
+        key = "" if args[0].startswith("--") else args[0].strip().lower()
+        template_spec: str | None = None
+        programs_spec: str | None = None
         # Parse flags
         dest: Path | None = None
         program_name: str | None = None
         author: str | None = None
         program_id: str | None = None
+        program_keypair: str | None = None
         cluster: str | None = None
         force = False
+        merge = False
+        no_input = False
+        dry_run = False
+        verify = False
+        namespace_errors = False
+        license_id: str | None = None
+        anchor_version: str | None = None
+        var_values: dict[str, str] = {}
         quick_mode = False
         quick_decimals: int | None = None
         quick_supply: str | None = None
@@ -98,10 +272,10 @@ def register(app: CLIApp, router: CommandRouter) -> None:
         quick_meta_collection: str | None = None
         quick_meta_run: bool = False
 
-        i = 1
+        i = 0 if not key else 1
         while i < len(args):
             tok = args[i]
-            if tok == "--dir" and i + 1 < len(args):
+            if tok in {"--dir", "--output-dir"} and i + 1 < len(args):
                 dest = Path(args[i + 1]).expanduser()
                 i += 2
                 continue
@@ -117,6 +291,10 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 program_id = args[i + 1]
                 i += 2
                 continue
+            if tok == "--program-keypair" and i + 1 < len(args):
+                program_keypair = str(Path(args[i + 1]).expanduser())
+                i += 2
+                continue
             if tok == "--cluster" and i + 1 < len(args):
                 cluster = args[i + 1]
                 i += 2
@@ -125,6 +303,49 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 force = True
                 i += 1
                 continue
+            if tok == "--merge":
+                merge = True
+                i += 1
+                continue
+            if tok == "--programs" and i + 1 < len(args):
+                programs_spec = args[i + 1]
+                i += 2
+                continue
+            if tok == "--template" and i + 1 < len(args):
+                template_spec = args[i + 1]
+                i += 2
+                continue
+            if tok == "--dry-run":
+                dry_run = True
+                i += 1
+                continue
+            if tok == "--license" and i + 1 < len(args):
+                license_id = args[i + 1]
+                i += 2
+                continue
+            if tok == "--anchor-version" and i + 1 < len(args):
+                anchor_version = args[i + 1]
+                i += 2
+                continue
+            if tok == "--verify":
+                verify = True
+                i += 1
+                continue
+            if tok == "--namespace-errors":
+                namespace_errors = True
+                i += 1
+                continue
+            if tok == "--no-input":
+                no_input = True
+                i += 1
+                continue
+            if tok == "--var" and i + 1 < len(args):
+                var_key, sep, var_value = args[i + 1].partition("=")
+                if not sep or not var_key.strip():
+                    return CommandResponse(messages=[("system", "--var expects <key>=<value>.")])
+                var_values[var_key.strip()] = var_value
+                i += 2
+                continue
             if tok == "--quick":
                 quick_mode = True
                 i += 1
@@ -176,6 +397,44 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 continue
             return CommandResponse(messages=[("system", f"Unknown or misplaced argument '{tok}'.")])
 
+        if program_id is not None and program_keypair is not None:
+            return CommandResponse(messages=[("system", "Give either --program-id or --program-keypair, not both.")])
+        if programs_spec is not None:
+            if merge:
+                return CommandResponse(messages=[("system", "--merge is not supported with --programs; scaffold into an empty directory or use --force.")])
+            return _scaffold_workspace(
+                app,
+                programs_spec,
+                dest=dest,
+                author=author,
+                cluster=cluster,
+                force=force,
+                dry_run=dry_run,
+                verify=verify,
+                license_id=license_id,
+                anchor_version=anchor_version,
+                namespace_errors=namespace_errors,
+            )
+        if namespace_errors:
+            return CommandResponse(messages=[("system", "--namespace-errors only applies to --programs workspaces.")])
+        if template_spec is not None:
+            return _scaffold_remote_template(
+                app,
+                template_spec,
+                dest=dest,
+                program_name=program_name,
+                author=author,
+                cluster=cluster,
+                program_id=program_id,
+                program_keypair=program_keypair,
+                force=force,
+                dry_run=dry_run,
+                verify=verify,
+                license_id=license_id,
+                merge=merge,
+                anchor_version=anchor_version,
+            )
+
         # Key mapping / selection
         templates = set(available_templates())
         registry = load_registry()
@@ -216,6 +475,8 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                     raise ValueError("invalid llm selection JSON")
             except Exception:
                 options = ", ".join(sorted(KNOWN_KEYS))
+                if no_input:
+                    return CommandResponse(messages=[("system", f"Unknown key '{key}'. Available: {options}")])
                 chosen = _prompt_or_default(app, f"Select a blueprint key ({options})", "counter")
                 key = chosen.strip().lower()
                 if key not in KNOWN_KEYS:
@@ -261,10 +522,23 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 seed.setdefault("token_mode", "quick")
                 seed.setdefault("decimals", 9)
                 seed.setdefault("initial_supply", "0")
-            answers = prompt_wizard(app, wizard_qs, seed)
+            seed.update(var_values)
+            try:
+                answers = prompt_wizard(
+                    app,
+                    wizard_qs,
+                    seed,
+                    interactive=not no_input,
+                    manifest=load_blueprint_manifest(key),
+                )
+            except WizardInputError as exc:
+                return CommandResponse(messages=[("system", str(exc))])
             if key == "token":
                 token_mode = (answers.get("token_mode") or seed.get("token_mode", "program")).strip().lower()
                 if token_mode == "quick":
+                    if no_input:
+                        # Minting spends SOL and needs the wallet passphrase, so it stays interactive.
+                        return CommandResponse(messages=[("system", "The quick token flow is interactive; pass --var token_mode=program or use --quick with --decimals/--supply.")])
                     cluster_hint = answers.get("cluster") or seed.get("cluster")
                     decimals_value = answers.get("decimals", seed.get("decimals"))
                     supply_value = answers.get("initial_supply", seed.get("initial_supply"))
@@ -308,10 +582,12 @@ def register(app: CLIApp, router: CommandRouter) -> None:
             author = answers.get("author_pubkey") or seed["author_pubkey"]
             cluster = answers.get("cluster") or seed["cluster"]
             program_id = answers.get("program_id") or seed["program_id"]
+            # The wizard only returns its own questions; keep --program-id for /blueprint.
+            answers.setdefault("program_id", program_id)
         else:
             if program_name is None:
                 # Default to the blueprint key for program name rather than the global default (counter)
-                program_name = _prompt_or_default(app, "Program name", key)
+                program_name = key if no_input else _prompt_or_default(app, "Program name", key)
             if author is None:
                 author = defaults.get("author_pubkey", "CHANGEME")
             if cluster is None:
@@ -360,6 +636,15 @@ def register(app: CLIApp, router: CommandRouter) -> None:
             )
             return CommandResponse(messages=[("system", msg)])
 
+        if program_keypair:
+            answers = answers or {
+                "program_name": program_name,
+                "author_pubkey": author,
+                "cluster": cluster or "devnet",
+                "program_id": program_id,
+            }
+            answers["program_keypair"] = program_keypair
+
         if workspace_root is not None:
             # Hand off insertion to agent/CLI via /blueprint scaffold
             try:
@@ -386,6 +671,16 @@ def register(app: CLIApp, router: CommandRouter) -> None:
             ]
             if force:
                 cmd_parts.append("--force")
+            if merge:
+                cmd_parts.append("--merge")
+            if dry_run:
+                cmd_parts.append("--dry-run")
+            if verify:
+                cmd_parts.append("--verify")
+            if license_id:
+                cmd_parts.extend(["--license", license_id])
+            if anchor_version:
+                cmd_parts.extend(["--anchor-version", anchor_version])
             dispatch = " ".join(_shlex.quote(p) for p in cmd_parts)
             # Execute immediately for synchronous behavior in CLI/tests
             routed = app.command_router.dispatch(app, dispatch[1:] if dispatch.startswith("/") else dispatch)
@@ -401,6 +696,7 @@ def register(app: CLIApp, router: CommandRouter) -> None:
             program_id=program_id,
             cluster=cluster or "devnet",
             overwrite=force,
+            merge=merge,
             template_path=template_path,
         )
         # Hand off fresh scaffold to agent/CLI via /blueprint scaffold
@@ -427,6 +723,16 @@ def register(app: CLIApp, router: CommandRouter) -> None:
         ]
         if force:
             cmd_parts.append("--force")
+        if merge:
+            cmd_parts.append("--merge")
+        if dry_run:
+            cmd_parts.append("--dry-run")
+        if verify:
+            cmd_parts.append("--verify")
+        if license_id:
+            cmd_parts.extend(["--license", license_id])
+        if anchor_version:
+            cmd_parts.extend(["--anchor-version", anchor_version])
         dispatch = " ".join(_shlex.quote(p) for p in cmd_parts)
         routed = app.command_router.dispatch(app, dispatch[1:] if dispatch.startswith("/") else dispatch)
         return routed
@@ -435,7 +741,7 @@ def register(app: CLIApp, router: CommandRouter) -> None:
         SlashCommand(
             "new",
             handle,
-            "Create a new blueprint: /new <key> [--dir <path>] [--program <name>] [--author <pubkey>] [--cluster <cluster>] [--program-id <id>] [--force]",
+            "Create a new blueprint: /new <key> [--dir | --output-dir <path>] [--program <name>] [--author <pubkey>] [--cluster <cluster>] [--program-id <id>] [--program-keypair <path>] [--var <key>=<value>] [--license <id>] [--anchor-version <semver>] [--no-input] [--dry-run] [--verify] [--force | --merge]",
         )
     )
 
@@ -4,9 +4,18 @@ from __future__ import annotations
 
 from typing import TYPE_CHECKING
 
-from solcoder.cli.template_utils import parse_template_tokens
+from solcoder.cli.template_utils import (
+    anchor_version_diagnostics,
+    format_build_check,
+    format_preview,
+    parse_template_tokens,
+    program_id_diagnostics,
+    rent_report,
+    space_diagnostics,
+)
 from solcoder.cli.types import CommandResponse, CommandRouter, SlashCommand
-from solcoder.core import TemplateError, available_templates, render_template
+from solcoder.core import TemplateError, available_templates, preview_template, render_template
+from solcoder.core.template_verify import verify_build
 
 if TYPE_CHECKING:  # pragma: no cover
     from solcoder.cli.app import CLIApp
@@ -22,7 +31,7 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 messages=[
                     (
                         "system",
-                        "Usage: /template <name> <destination> [--program <name>] [--author <pubkey>] [--program-id <id>] [--cluster <cluster>] [--force]\n"
+                        "Usage: /template <name> <destination | --output-dir <path>> [--program <name>] [--author <pubkey>] [--program-id <id>] [--program-keypair <path>] [--cluster <cluster>] [--license <MIT|Apache-2.0|none>] [--anchor-version <semver>] [--force | --merge] [--dry-run] [--verify]\n"
                         f"Available templates: {templates}",
                     )
                 ]
@@ -38,14 +47,26 @@ def register(app: CLIApp, router: CommandRouter) -> None:
                 )]
             )
 
+        flags = {"--dry-run", "--verify"}
+        tokens = [token for token in args[1:] if token not in flags]
+        dry_run = "--dry-run" in args[1:]
+        verify = "--verify" in args[1:]
         defaults = app._default_template_metadata()
-        options, error = parse_template_tokens(template_name, args[1:], defaults)
+        options, error = parse_template_tokens(template_name, tokens, defaults)
         if error:
             app.log_event("build", f"Template option parsing failed: {error}", severity="error")
             return CommandResponse(messages=[("system", error)])
         if options is None:
             app.log_event("build", "Template option parsing returned no result", severity="error")
             return CommandResponse(messages=[("system", "Unable to parse template options.")])
+        if dry_run:
+            try:
+                planned = preview_template(options)
+            except TemplateError as exc:
+                return CommandResponse(messages=[("system", f"Template error: {exc}")])
+            destination = options.destination.expanduser().resolve()
+            header = f"Dry run: '{template_name}' would write {len(planned)} files to {destination} (nothing written)."
+            return CommandResponse(messages=[("system", f"{header}\n{format_preview(planned, destination)}")])
         try:
             output = render_template(options)
         except TemplateError as exc:
@@ -54,11 +75,36 @@ def register(app: CLIApp, router: CommandRouter) -> None:
 
         message = f"Template '{template_name}' rendered to {output}"
         app.log_event("build", f"Template '{template_name}' rendered to {output}")
+        status = "success"
+        rent = rent_report(options, output)
+        if rent:
+            message = f"{message}\n{rent}"
+        space_warning = space_diagnostics(options, output)
+        if space_warning:
+            message = f"{message}\n{space_warning}"
+            app.log_event("build", f"Account space mismatch in {output}", severity="warning")
+        id_warning = program_id_diagnostics(output)
+        if id_warning:
+            message = f"{message}\n{id_warning}"
+            app.log_event("build", f"Program id mismatch in {output}", severity="warning")
+        version_warning = anchor_version_diagnostics(options)
+        if version_warning:
+            message = f"{message}\n{version_warning}"
+        if verify:
+            try:
+                with app.console.status("Verifying the generated project builds…", spinner="dots"):
+                    check = verify_build(output)
+            except TemplateError as exc:
+                return CommandResponse(messages=[("system", f"{message}\nVerify error: {exc}")])
+            message = f"{message}\n{format_build_check(check, output)}"
+            if not check.ok:
+                status = "error"
+                app.log_event("build", f"Template '{template_name}' failed to build", severity="error")
         tool_calls = [
             {
                 "type": "command",
                 "name": "/template",
-                "status": "success",
+                "status": status,
                 "summary": f"{template_name} → {output}",
             }
         ]
@@ -4,7 +4,14 @@ from __future__ import annotations
 
 from pathlib import Path
 
-from solcoder.core import RenderOptions
+from solcoder.core import PlannedFile, RenderOptions, TemplateError
+from solcoder.core.account_space import LAMPORTS_PER_SOL, diagnose_account_space, rent_summary
+from solcoder.core.program_ids import check_program_ids
+from solcoder.core.template_verify import BuildCheck
+from solcoder.core.templates import anchor_version_warning, resolve_template_dir
+
+# Lines of compiler output kept in a failed --verify report.
+_BUILD_OUTPUT_TAIL = 20
 
 
 def parse_template_tokens(
@@ -16,8 +23,12 @@ def parse_template_tokens(
     program_name = defaults["program_name"]
     author = defaults["author_pubkey"]
     program_id = "replace-with-program-id"
+    program_keypair: Path | None = None
     cluster = "devnet"
+    license_id: str | None = None
+    anchor_version: str | None = None
     overwrite = False
+    merge = False
 
     idx = 0
     while idx < len(tokens):
@@ -26,6 +37,10 @@ def parse_template_tokens(
             overwrite = True
             idx += 1
             continue
+        if option == "--merge":
+            merge = True
+            idx += 1
+            continue
         if option == "--program" and idx + 1 < len(tokens):
             program_name = tokens[idx + 1]
             idx += 2
@@ -50,6 +65,14 @@ def parse_template_tokens(
             program_id = option.split("=", 1)[1]
             idx += 1
             continue
+        if option == "--program-keypair" and idx + 1 < len(tokens):
+            program_keypair = Path(tokens[idx + 1]).expanduser()
+            idx += 2
+            continue
+        if option.startswith("--program-keypair="):
+            program_keypair = Path(option.split("=", 1)[1]).expanduser()
+            idx += 1
+            continue
         if option == "--cluster" and idx + 1 < len(tokens):
             cluster = tokens[idx + 1]
             idx += 2
@@ -58,6 +81,34 @@ def parse_template_tokens(
             cluster = option.split("=", 1)[1]
             idx += 1
             continue
+        if option == "--license" and idx + 1 < len(tokens):
+            license_id = tokens[idx + 1]
+            idx += 2
+            continue
+        if option.startswith("--license="):
+            license_id = option.split("=", 1)[1]
+            idx += 1
+            continue
+        if option == "--anchor-version" and idx + 1 < len(tokens):
+            anchor_version = tokens[idx + 1]
+            idx += 2
+            continue
+        if option.startswith("--anchor-version="):
+            anchor_version = option.split("=", 1)[1]
+            idx += 1
+            continue
+        if option == "--output-dir" and idx + 1 < len(tokens):
+            if destination is not None:
+                return None, "Give the destination either positionally or with --output-dir, not both."
+            destination = Path(tokens[idx + 1])
+            idx += 2
+            continue
+        if option.startswith("--output-dir="):
+            if destination is not None:
+                return None, "Give the destination either positionally or with --output-dir, not both."
+            destination = Path(option.split("=", 1)[1])
+            idx += 1
+            continue
         if option.startswith("-"):
             return None, f"Unknown option '{option}'."
         if destination is None:
@@ -68,6 +119,8 @@ def parse_template_tokens(
 
     if destination is None:
         return None, "Destination path is required."
+    if program_keypair is not None and program_id != "replace-with-program-id":
+        return None, "Give either --program-id or --program-keypair, not both."
 
     options = RenderOptions(
         template=template_name,
@@ -77,8 +130,110 @@ def parse_template_tokens(
         program_id=program_id,
         cluster=cluster,
         overwrite=overwrite,
+        merge=merge,
+        program_keypair=program_keypair,
+        license=license_id,
+        anchor_version=anchor_version,
     )
     return options, None
 
 
-__all__ = ["parse_template_tokens"]
+def format_preview(planned: list[PlannedFile], destination: Path) -> str:
+    """Render a dry-run report: the resulting file tree, then each source -> rendered path."""
+    lines = [f"{destination}/"]
+    seen_dirs: set[Path] = set()
+    for item in planned:
+        parents = list(item.path.parents)[:-1]
+        for depth, parent in enumerate(reversed(parents)):
+            if parent not in seen_dirs:
+                seen_dirs.add(parent)
+                lines.append(f"{'  ' * (depth + 1)}{parent.name}/")
+        lines.append(f"{'  ' * len(item.path.parts)}{item.path.name}")
+    lines.append("")
+    for item in planned:
+        if item.source is None:
+            lines.append(f"+ {item.path.as_posix()} (generated)")
+        elif item.source != item.path:
+            lines.append(f"- {item.source.as_posix()}")
+            lines.append(f"+ {item.path.as_posix()}")
+        else:
+            lines.append(f"  {item.path.as_posix()}")
+    return "\n".join(lines)
+
+
+def format_build_check(check: BuildCheck, root: Path) -> str:
+    """Summarise a --verify build: a single line on success, offending files and output tail otherwise."""
+    command = " ".join(check.command)
+    if check.ok:
+        return f"Verify: `{command}` succeeded in {root}."
+    lines = [f"Verify: `{command}` failed in {root}."]
+    if check.locations:
+        lines.append("Offending files:")
+        lines.extend(f"  {location}" for location in check.locations)
+    tail = check.output.splitlines()[-_BUILD_OUTPUT_TAIL:]
+    if tail:
+        lines.append("Build output (tail):")
+        lines.extend(tail)
+    return "\n".join(lines)
+
+
+def anchor_version_diagnostics(options: RenderOptions) -> str:
+    """Warning for an --anchor-version with breaking macro changes from the templates' release, or ''."""
+    try:
+        warning = anchor_version_warning(options.anchor_version)
+    except TemplateError:
+        return ""
+    return f"Warning: {warning}" if warning else ""
+
+
+def space_diagnostics(options: RenderOptions, root: Path) -> str:
+    """Warnings for `space = ...` constants that disagree with the manifest layouts, or ''."""
+    try:
+        warnings = diagnose_account_space(resolve_template_dir(options), root)
+    except TemplateError:
+        return ""
+    if not warnings:
+        return ""
+    lines = ["Warning: account space does not match the manifest layout:"]
+    lines.extend(f"  {item}" for item in warnings)
+    return "\n".join(lines)
+
+
+def program_id_diagnostics(root: Path) -> str:
+    """Warnings for files whose program id disagrees with the program's `declare_id!`, or ''."""
+    try:
+        mismatches = check_program_ids(root)
+    except TemplateError as exc:
+        return f"Warning: cannot check program ids: {exc}"
+    if not mismatches:
+        return ""
+    lines = ["Warning: program ids disagree:"]
+    lines.extend(f"  {item}" for item in mismatches)
+    return "\n".join(lines)
+
+
+def rent_report(options: RenderOptions, root: Path) -> str:
+    """Table of each manifest account's size and rent-exempt minimum, or ''."""
+    try:
+        summary = rent_summary(resolve_template_dir(options), root)
+    except TemplateError:
+        return ""
+    if not summary:
+        return ""
+    width = max(len(item.name) for item in summary)
+    lines = ["Account rent (rent-exempt minimum per account):"]
+    for item in summary:
+        sol = item.lamports / LAMPORTS_PER_SOL
+        lines.append(f"  {item.name:<{width}}  {item.size:>6} bytes  ~{sol:.6f} SOL ({item.lamports} lamports)")
+    return "\n".join(lines)
+
+
+__all__ = [
+    "anchor_version_diagnostics",
+    "format_build_check",
+    "format_preview",
+    "parse_template_tokens",
+    "program_id_diagnostics",
+    "rent_report",
+    "space_diagnostics",
+]
@@ -18,7 +18,7 @@ from .agent import (
 from .env_diag import DiagnosticResult, ToolRequirement, collect_environment_diagnostics
 from .exec_ua import build_exec_ua_header, clear_exec_ua_cache
 from .knowledge_base import KnowledgeBaseAnswer, KnowledgeBaseClient, KnowledgeBaseError
-from .templates import RenderOptions, TemplateError, TemplateExistsError, TemplateNotFoundError, available_templates, render_template
+from .templates import SUPPORTED_LICENSES, InvalidProgramIdError, PlannedFile, RenderOptions, TemplateError, TemplateExistsError, TemplateInfo, TemplateNotFoundError, UnrenderedPlaceholderError, available_templates, list_templates, preview_template, render_template
 from .tool_registry import (
     ToolRegistry,
     ToolRegistryError,
@@ -62,11 +62,18 @@ __all__ = [
     "build_exec_ua_header",
     "clear_exec_ua_cache",
     "render_template",
+    "preview_template",
+    "PlannedFile",
     "available_templates",
+    "list_templates",
+    "TemplateInfo",
     "RenderOptions",
     "TemplateError",
+    "InvalidProgramIdError",
     "TemplateExistsError",
     "TemplateNotFoundError",
+    "SUPPORTED_LICENSES",
+    "UnrenderedPlaceholderError",
     "ContextManager",
     "HistoryCompactionStrategy",
     "RollingHistoryStrategy",
@@ -0,0 +1,322 @@
+"""Compute Anchor account space from manifest layouts and check generated constants."""
+
+from __future__ import annotations
+
+import ast
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Mapping
+
+from .template_manifest import (
+    AccountField,
+    AccountLayout,
+    ManifestError,
+    load_manifest,
+)
+
+DISCRIMINATOR_SIZE = 8
+
+# Rent parameters of every public cluster: an account is rent-exempt once it holds
+# two years of rent for its data plus a fixed 128-byte storage overhead.
+RENT_LAMPORTS_PER_BYTE_YEAR = 3480
+RENT_EXEMPTION_YEARS = 2
+ACCOUNT_STORAGE_OVERHEAD = 128
+LAMPORTS_PER_SOL = 1_000_000_000
+
+# Borsh sizes of the fixed-width types used in account structs.
+PRIMITIVE_SIZES = {
+    "bool": 1,
+    "u8": 1,
+    "i8": 1,
+    "u16": 2,
+    "i16": 2,
+    "u32": 4,
+    "i32": 4,
+    "f32": 4,
+    "u64": 8,
+    "i64": 8,
+    "f64": 8,
+    "u128": 16,
+    "i128": 16,
+    "Pubkey": 32,
+}
+
+_IMPL_PATTERN = re.compile(r"\bimpl\s+(\w+)\s*\{")
+_CONST_PATTERN = re.compile(r"\bconst\s+(\w+)\s*:\s*usize\s*=\s*(.+?);", re.DOTALL)
+_PATH_PATTERN = re.compile(r"\b(Self|[A-Z]\w*)::([A-Z][A-Z0-9_]*)\b")
+_ACCOUNT_FIELD_PATTERN = re.compile(
+    r"(?:\s*///[^\n]*)*\s*pub\s+(\w+)\s*:\s*(?:Box<)?Account<'info,\s*(\w+)>"
+)
+_SPACE_PATTERN = re.compile(r"\bspace\s*=\s*([^,]+)")
+
+
+class SpaceError(ManifestError):
+    """Raised when a layout cannot be sized: an unknown type or a missing bound."""
+
+
+@dataclass(frozen=True)
+class AccountRent:
+    """On-chain size of an ``#[account]`` type and its rent-exempt minimum balance."""
+
+    name: str
+    size: int
+    lamports: int
+
+
+def rent_exempt_lamports(size: int) -> int:
+    """Minimum balance for ``size`` data bytes, matching ``Rent::minimum_balance``."""
+    return (
+        (ACCOUNT_STORAGE_OVERHEAD + size)
+        * RENT_LAMPORTS_PER_BYTE_YEAR
+        * RENT_EXEMPTION_YEARS
+    )
+
+
+def field_size(account_field: AccountField, constants: Mapping[str, int]) -> int:
+    """Serialized size of one field.
+
+    String/Vec fields take ``max_len`` elements plus a 4-byte length prefix.
+    """
+    return _type_size(account_field.type.replace(" ", ""), account_field, constants)
+
+
+def account_space(
+    layout: AccountLayout, constants: Mapping[str, int] | None = None
+) -> int:
+    """Bytes to allocate for ``layout``, including the 8-byte discriminator.
+
+    ``constants`` resolves ``max_len`` names (e.g. ``MAX_LABEL``) against the
+    account's ``impl`` block.
+    """
+    constants = constants or {}
+    return DISCRIMINATOR_SIZE + sum(
+        field_size(item, constants) for item in layout.fields
+    )
+
+
+def program_constants(source: str) -> dict[str, dict[str, int]]:
+    """Evaluate the ``const NAME: usize`` items of each ``impl Type`` in ``source``."""
+    found: dict[str, dict[str, int]] = {}
+    for match in _IMPL_PATTERN.finditer(source):
+        body = _block(source, match.end() - 1)
+        constants = found.setdefault(match.group(1), {})
+        for const in _CONST_PATTERN.finditer(body):
+            value = _evaluate(const.group(2), match.group(1), found)
+            if value is not None:
+                constants[const.group(1)] = value
+    return found
+
+
+def check_account_space(
+    root: Path, layouts: Mapping[str, AccountLayout]
+) -> list[str]:
+    """Compare each ``#[account(init, space = ...)]`` under ``root`` with ``layouts``.
+
+    Returns ``path:line: message`` warnings; an empty list means every sized
+    account matches its layout. Accounts sized at runtime (a ``space`` that
+    calls a function, e.g. ``Record::space_for(key.len(), ...)``) are skipped.
+    """
+    warnings: list[str] = []
+    for path in sorted(root.glob("programs/*/src/**/*.rs")):
+        source = path.read_text()
+        constants = program_constants(source)
+        relative = path.relative_to(root).as_posix()
+        for offset, attrs, field_name, type_name in _sized_accounts(source):
+            layout = layouts.get(type_name)
+            if layout is None:
+                continue
+            space = _SPACE_PATTERN.search(attrs)
+            if space and "(" in space.group(1):
+                continue
+            line = source.count("\n", 0, offset) + 1
+            try:
+                expected = account_space(layout, constants.get(type_name, {}))
+            except SpaceError as exc:
+                warnings.append(
+                    f"{relative}:{line}: cannot size {type_name}: {exc}"
+                )
+                continue
+            actual = (
+                _evaluate(space.group(1), type_name, constants) if space else None
+            )
+            if actual is None:
+                warnings.append(
+                    f"{relative}:{line}: cannot evaluate space for `{field_name}`."
+                )
+            elif actual != expected:
+                data = expected - DISCRIMINATOR_SIZE
+                warnings.append(
+                    f"{relative}:{line}: `{field_name}` allocates {actual} bytes "
+                    f"but {type_name} needs {expected} "
+                    f"({DISCRIMINATOR_SIZE}-byte discriminator + {data})."
+                )
+    return warnings
+
+
+def diagnose_account_space(template_dir: Path, root: Path) -> list[str]:
+    """Check a rendered project against its template manifest's account layouts."""
+    try:
+        manifest = load_manifest(template_dir)
+    except ManifestError as exc:
+        return [str(exc)]
+    if manifest is None or not manifest.accounts:
+        return []
+    return check_account_space(root, manifest.accounts)
+
+
+def rent_summary(template_dir: Path, root: Path) -> list[AccountRent]:
+    """Size and rent of each account layout in the template's manifest.
+
+    Constants are read from the programs under ``root``. Layouts that cannot be
+    sized (see :class:`SpaceError`) are left out; the space check reports them.
+    """
+    try:
+        manifest = load_manifest(template_dir)
+    except ManifestError:
+        return []
+    if manifest is None or not manifest.accounts:
+        return []
+    constants: dict[str, dict[str, int]] = {}
+    for path in sorted(root.glob("programs/*/src/**/*.rs")):
+        for owner, values in program_constants(path.read_text()).items():
+            constants.setdefault(owner, {}).update(values)
+    summary = []
+    for name, layout in manifest.accounts.items():
+        try:
+            size = account_space(layout, constants.get(name, {}))
+        except SpaceError:
+            continue
+        summary.append(
+            AccountRent(name=name, size=size, lamports=rent_exempt_lamports(size))
+        )
+    return summary
+
+
+def _type_size(
+    type_name: str, account_field: AccountField, constants: Mapping[str, int]
+) -> int:
+    if type_name in PRIMITIVE_SIZES:
+        return PRIMITIVE_SIZES[type_name]
+    if type_name == "String":
+        return 4 + _max_len(account_field, constants)
+    inner = re.fullmatch(r"Vec<(.+)>", type_name)
+    if inner:
+        element = _type_size(inner.group(1), account_field, constants)
+        return 4 + _max_len(account_field, constants) * element
+    inner = re.fullmatch(r"Option<(.+)>", type_name)
+    if inner:
+        return 1 + _type_size(inner.group(1), account_field, constants)
+    array = re.fullmatch(r"\[(.+);(\d+)\]", type_name)
+    if array:
+        element = _type_size(array.group(1), account_field, constants)
+        return int(array.group(2)) * element
+    raise SpaceError(
+        f"field `{account_field.name}` has unsupported type {account_field.type}."
+    )
+
+
+def _max_len(account_field: AccountField, constants: Mapping[str, int]) -> int:
+    bound = account_field.max_len
+    if bound is None:
+        raise SpaceError(
+            f"field `{account_field.name}` ({account_field.type}) needs max_len."
+        )
+    if isinstance(bound, int):
+        return bound
+    if bound.isdigit():
+        return int(bound)
+    if bound not in constants:
+        raise SpaceError(
+            f"field `{account_field.name}` references unknown constant {bound}."
+        )
+    return constants[bound]
+
+
+def _block(source: str, start: int) -> str:
+    """Return the text between the brace at ``start`` and its matching close brace."""
+    depth = 0
+    for index in range(start, len(source)):
+        if source[index] == "{":
+            depth += 1
+        elif source[index] == "}":
+            depth -= 1
+            if depth == 0:
+                return source[start + 1 : index]
+    return source[start + 1 :]
+
+
+def _sized_accounts(source: str):
+    """Yield (offset, attrs, field, type) for each ``#[account(init...)]`` field."""
+    for match in re.finditer(r"#\[account\(", source):
+        depth = 0
+        end = match.end() - 1
+        for end in range(match.end() - 1, len(source)):
+            if source[end] == "(":
+                depth += 1
+            elif source[end] == ")":
+                depth -= 1
+                if depth == 0:
+                    break
+        attrs = source[match.end() : end]
+        if not re.search(r"\binit(_if_needed)?\b", attrs):
+            continue
+        declaration = _ACCOUNT_FIELD_PATTERN.match(source, end + 2)
+        if declaration is not None:
+            yield match.start(), attrs, declaration.group(1), declaration.group(2)
+
+
+def _evaluate(
+    expression: str, owner: str, constants: Mapping[str, Mapping[str, int]]
+) -> int | None:
+    """Evaluate a usize expression such as ``8 + Self::MAX_LABEL * 32``.
+
+    Returns None when a constant is unknown or the expression is not arithmetic.
+    """
+
+    def substitute(match: re.Match[str]) -> str:
+        scope = owner if match.group(1) == "Self" else match.group(1)
+        value = constants.get(scope, {}).get(match.group(2))
+        if value is None:
+            raise KeyError(match.group(0))
+        return str(value)
+
+    try:
+        text = _PATH_PATTERN.sub(substitute, expression.strip())
+        # Parenthesised so rustfmt's one-operand-per-line layout parses too.
+        tree = ast.parse("(" + re.sub(r"(?<=\d)_(?=\d)", "", text) + ")", mode="eval")
+    except (KeyError, SyntaxError):
+        return None
+    return _fold(tree.body)
+
+
+def _fold(node: ast.AST) -> int | None:
+    if isinstance(node, ast.Constant) and isinstance(node.value, int):
+        return node.value
+    if isinstance(node, ast.BinOp):
+        left, right = _fold(node.left), _fold(node.right)
+        if left is None or right is None:
+            return None
+        if isinstance(node.op, ast.Add):
+            return left + right
+        if isinstance(node.op, ast.Sub):
+            return left - right
+        if isinstance(node.op, ast.Mult):
+            return left * right
+    return None
+
+
+__all__ = [
+    "DISCRIMINATOR_SIZE",
+    "LAMPORTS_PER_SOL",
+    "PRIMITIVE_SIZES",
+    "AccountRent",
+    "SpaceError",
+    "account_space",
+    "check_account_space",
+    "diagnose_account_space",
+    "field_size",
+    "program_constants",
+    "rent_exempt_lamports",
+    "rent_summary",
+]
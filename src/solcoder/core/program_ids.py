@@ -0,0 +1,151 @@
+"""Check that every file naming a program's id in a scaffolded workspace agrees on it."""
+
+from __future__ import annotations
+
+import re
+from collections.abc import Sequence
+from dataclasses import dataclass
+from pathlib import Path
+
+try:  # Python 3.11+
+    import tomllib  # type: ignore[attr-defined]
+except ModuleNotFoundError:  # pragma: no cover - fallback for older interpreters
+    import tomli as tomllib  # type: ignore[no-redef]
+
+from .templates import TemplateError, program_keypair_path, read_program_id
+
+_PUBKEY = r"[1-9A-HJ-NP-Za-km-z]{32,44}"
+_DECLARE_ID_PATTERN = re.compile(rf'\bdeclare_id!\(\s*"({_PUBKEY})"\s*\)')
+# The `ID` constant of a program's `cpi` crate.
+_CPI_ID_PATTERN = re.compile(rf'\bpub\s+const\s+ID\s*:\s*Pubkey\s*=\s*pubkey!\(\s*"({_PUBKEY})"\s*\)')
+# `const PROGRAM_ID = new anchor.web3.PublicKey("...")` and asserts on `program.programId`.
+_TS_ID_PATTERNS = (
+    re.compile(rf'\bPROGRAM_ID\s*=\s*new\s+(?:anchor\.web3\.)?PublicKey\(\s*"({_PUBKEY})"\s*\)'),
+    re.compile(rf'\bprogramId\.toBase58\(\),\s*"({_PUBKEY})"'),
+)
+_TS_DIRS = ("app", "migrations", "scripts", "tests")
+
+
+@dataclass(frozen=True)
+class ProgramIdReference:
+    program: str
+    # File relative to the workspace root, plus the table for Anchor.toml entries.
+    location: str
+    value: str
+
+
+@dataclass(frozen=True)
+class ProgramIdMismatch:
+    reference: ProgramIdReference
+    # The `declare_id!` the reference disagrees with (or the first reference found).
+    expected: ProgramIdReference
+
+    def __str__(self) -> str:
+        return (
+            f"{self.reference.location}: {self.reference.program} is {self.reference.value}, "
+            f"but {self.expected.location} has {self.expected.value}"
+        )
+
+
+def program_id_references(root: Path) -> dict[str, list[ProgramIdReference]]:
+    """Every program id in the workspace at ``root``, grouped by program, ``declare_id!`` first.
+
+    Sources are the program's ``declare_id!``, its ``[programs.<cluster>]`` entries in
+    Anchor.toml, its deploy keypair in ``target/deploy``, the ``ID`` of a nested
+    ``cpi`` crate, and ``PROGRAM_ID`` constants or ``programId`` asserts in the
+    TypeScript under app/, migrations/, scripts/ and tests/. Raises TemplateError
+    when Anchor.toml or a keypair cannot be read.
+    """
+    programs = sorted(path.parent.parent.name for path in root.glob("programs/*/src/lib.rs"))
+    found: dict[str, list[ProgramIdReference]] = {snake: [] for snake in programs}
+    for snake in programs:
+        crate = root / "programs" / snake
+        found[snake] += _matches(root, snake, crate / "src" / "lib.rs", [_DECLARE_ID_PATTERN])
+        for cpi in sorted(crate.glob("*/src/lib.rs")):
+            found[snake] += _matches(root, snake, cpi, [_CPI_ID_PATTERN])
+    for snake, table, value in _anchor_toml_entries(root / "Anchor.toml"):
+        if snake in found:
+            found[snake].append(ProgramIdReference(snake, f"Anchor.toml [programs.{table}]", value))
+    for snake in programs:
+        keypair = program_keypair_path(root, snake)
+        if keypair.exists():
+            location = keypair.relative_to(root).as_posix()
+            found[snake].append(ProgramIdReference(snake, location, read_program_id(keypair)))
+    for path in sorted(path for folder in _TS_DIRS for path in (root / folder).glob("**/*.ts")):
+        if "node_modules" in path.parts:
+            continue
+        snake = _ts_program(path, programs)
+        if snake is not None:
+            found[snake] += _matches(root, snake, path, _TS_ID_PATTERNS)
+    return found
+
+
+def check_program_ids(root: Path) -> list[ProgramIdMismatch]:
+    """References that disagree with their program's ``declare_id!``; empty when all agree."""
+    mismatches: list[ProgramIdMismatch] = []
+    for references in program_id_references(root).values():
+        if not references:
+            continue
+        expected = references[0]
+        mismatches.extend(
+            ProgramIdMismatch(reference=reference, expected=expected)
+            for reference in references[1:]
+            if reference.value != expected.value
+        )
+    return mismatches
+
+
+def _matches(root: Path, snake: str, path: Path, patterns: Sequence[re.Pattern[str]]) -> list[ProgramIdReference]:
+    if not path.is_file():
+        return []
+    text = path.read_text()
+    location = path.relative_to(root).as_posix()
+    references = []
+    for pattern in patterns:
+        for match in pattern.finditer(text):
+            line = text.count("\n", 0, match.start()) + 1
+            references.append(ProgramIdReference(snake, f"{location}:{line}", match.group(1)))
+    return references
+
+
+def _anchor_toml_entries(path: Path) -> list[tuple[str, str, str]]:
+    """``(program, cluster, id)`` for every ``[programs.<cluster>]`` entry."""
+    if not path.exists():
+        return []
+    try:
+        data = tomllib.loads(path.read_text())
+    except tomllib.TOMLDecodeError as exc:
+        raise TemplateError(f"Failed to parse {path}: {exc}") from exc
+    entries = []
+    for cluster, table in (data.get("programs") or {}).items():
+        if not isinstance(table, dict):
+            continue
+        for snake, value in table.items():
+            # Programs may also be `{ address = "...", idl = "..." }` tables.
+            address = value.get("address") if isinstance(value, dict) else value
+            if isinstance(address, str):
+                entries.append((snake, cluster, address))
+    return entries
+
+
+def _ts_program(path: Path, programs: list[str]) -> str | None:
+    """The program a TypeScript file belongs to: by its name, its IDL import, then any mention."""
+    stem = path.stem.removesuffix("_client")
+    if stem in programs:
+        return stem
+    if len(programs) == 1:
+        return programs[0]
+    text = path.read_text()
+    for pattern in (r"\btarget/types/{}\b", r"\b{}\b"):
+        named = [snake for snake in programs if re.search(pattern.format(re.escape(snake)), text)]
+        if len(named) == 1:
+            return named[0]
+    return None
+
+
+__all__ = [
+    "ProgramIdMismatch",
+    "ProgramIdReference",
+    "check_program_ids",
+    "program_id_references",
+]
@@ -0,0 +1,171 @@
+"""Rename a scaffolded program after the fact: module, crates, paths and client references."""
+
+from __future__ import annotations
+
+import re
+import shutil
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from .templates import (
+    TemplateError,
+    TemplateExistsError,
+    new_program_keypair,
+    normalise_program_name,
+    program_keypair_path,
+    read_program_id,
+    to_camel_case,
+    to_pascal_case,
+)
+
+# Build output and dependencies are regenerated by `anchor build` / `yarn install`.
+_SKIPPED_DIRS = {"target", "node_modules", ".anchor", "test-ledger", ".git"}
+_DECLARE_ID_PATTERN = re.compile(r'declare_id!\("([^"]+)"\)')
+
+
+@dataclass(frozen=True)
+class RenameResult:
+    old: str
+    new: str
+    # Files rewritten or moved, relative to the workspace root, in the new layout.
+    changed: list[str] = field(default_factory=list)
+    # Set when a fresh program keypair replaced the old program id.
+    program_id: str | None = None
+
+
+@dataclass(frozen=True)
+class _Rule:
+    # Matches the name, preceded by a ``pre`` group that is kept as-is.
+    pattern: re.Pattern[str]
+    value: str
+    toml_only: bool = False
+
+    def apply(self, text: str) -> str:
+        return self.pattern.sub(lambda match: match.group("pre") + self.value, text)
+
+
+def rename_program(root: Path, old: str, new: str, *, new_keypair: bool = False) -> RenameResult:
+    """Rename program ``old`` in the Anchor workspace at ``root`` to ``new``.
+
+    Only the places a template puts the program name are rewritten: the
+    ``pub mod``, crate names, workspace members, ``[programs.*]`` entries, IDL
+    type imports, ``anchor.workspace.<Pascal>``, the ``<camel>Client`` helper and
+    the program, test, client and keypair paths. Names that merely coincide, such
+    as a ``b"counter"`` seed or a ``Counter`` account in a program called
+    ``counter``, are left alone.
+
+    With ``new_keypair`` a new program keypair is written for ``new`` and the old
+    program id is replaced everywhere; the old keypair file is kept so an
+    existing deployment can still be managed. Otherwise the keypair moves with
+    the program and its id is unchanged.
+    """
+    root = root.expanduser().resolve()
+    old_snake, new_snake = normalise_program_name(old), normalise_program_name(new)
+    if old_snake == new_snake:
+        raise TemplateError(f"Program is already named '{new_snake}'.")
+    if not (root / "programs" / old_snake).is_dir():
+        raise TemplateError(f"No program '{old_snake}' under {root / 'programs'}.")
+    if (root / "programs" / new_snake).exists():
+        raise TemplateExistsError(f"Program '{new_snake}' already exists under {root / 'programs'}.")
+
+    rules = _name_rules(old_snake, new_snake)
+    program_id = None
+    if new_keypair:
+        old_id = _current_program_id(root, old_snake)
+        program_id = new_program_keypair(program_keypair_path(root, new_snake))
+        if old_id is not None:
+            rules.append(_Rule(re.compile(rf"(?P<pre>){re.escape(old_id)}"), program_id))
+
+    changed: set[Path] = set()
+    for path in sorted(root.rglob("*")):
+        relative = path.relative_to(root)
+        if not path.is_file() or _SKIPPED_DIRS.intersection(relative.parts):
+            continue
+        try:
+            text = path.read_text()
+        except UnicodeDecodeError:
+            continue
+        updated = text
+        for rule in rules:
+            if path.suffix == ".toml" or not rule.toml_only:
+                updated = rule.apply(updated)
+        if updated != text:
+            path.write_text(updated)
+            changed.add(relative)
+
+    moves = [
+        (Path("programs") / old_snake, Path("programs") / new_snake),
+        (Path("tests") / f"{old_snake}.ts", Path("tests") / f"{new_snake}.ts"),
+        (Path("app") / f"{old_snake}_client.ts", Path("app") / f"{new_snake}_client.ts"),
+    ]
+    if not new_keypair:
+        moves.append((program_keypair_path(Path(), old_snake), program_keypair_path(Path(), new_snake)))
+    for source, target in moves:
+        if not (root / source).exists():
+            continue
+        shutil.move(str(root / source), str(root / target))
+        changed = {_moved(item, source, target) for item in changed}
+        if (root / target).is_file():
+            changed.add(target)
+    return RenameResult(
+        old=old_snake,
+        new=new_snake,
+        changed=sorted(item.as_posix() for item in changed),
+        program_id=program_id,
+    )
+
+
+def _name_rules(old_snake: str, new_snake: str) -> list[_Rule]:
+    """Replacement rules for every template site of the snake, Pascal, camel and title names."""
+    snake = re.escape(old_snake)
+    pascal = re.escape(to_pascal_case(old_snake))
+    camel = re.escape(to_camel_case(old_snake))
+    title = re.escape(" ".join(word.capitalize() for word in old_snake.split("_")))
+    new_title = " ".join(word.capitalize() for word in new_snake.split("_"))
+    snake_sites = [
+        rf"(?P<pre>\bpub mod ){snake}\b",
+        rf"(?P<pre>\b){snake}(?=::|[-_]cpi\b)",
+        rf'(?P<pre>\bname = "){snake}(?=")',
+        rf"(?P<pre>\bprograms/){snake}(?![\w-])",
+        rf"(?P<pre>\btarget/(?:types|idl|deploy)/){snake}(?=[.\-\"`])",
+        rf"(?P<pre>\btests/){snake}(?=\.ts\b)",
+        rf"(?P<pre>\bapp/){snake}(?=_client\b)",
+        rf'(?P<pre>\bdescribe\("){snake}(?=")',
+        rf"(?P<pre>`){snake}(?=`| is (?:not )?deployed)",
+        rf"(?P<pre>\b(?:calling|every) ){snake}(?= (?:via CPI|instruction)\b)",
+    ]
+    rules = [_Rule(re.compile(site), new_snake) for site in snake_sites]
+    # `<snake> = ...` keys: Anchor.toml program ids and path dependencies on the crate.
+    rules.append(_Rule(re.compile(rf"(?m)(?P<pre>^[ \t]*){snake}(?=[ \t]*=)"), new_snake, toml_only=True))
+    pascal_sites = [
+        rf"(?P<pre>\banchor\.workspace\.){pascal}\b",
+        rf"(?P<pre>\bProgram<){pascal}(?=>)",
+        rf'(?P<pre>\bimport \{{ ){pascal}(?= \}} from "[./]*target/types/)',
+    ]
+    rules.extend(_Rule(re.compile(site), to_pascal_case(new_snake)) for site in pascal_sites)
+    rules.append(_Rule(re.compile(rf"(?P<pre>\b){camel}(?=Client\b)"), to_camel_case(new_snake)))
+    rules.append(_Rule(re.compile(rf"(?m)(?P<pre>^# ){title}\b"), new_title))
+    return rules
+
+
+def _current_program_id(root: Path, snake: str) -> str | None:
+    keypair = program_keypair_path(root, snake)
+    if keypair.exists():
+        return read_program_id(keypair)
+    lib_rs = root / "programs" / snake / "src" / "lib.rs"
+    match = _DECLARE_ID_PATTERN.search(lib_rs.read_text()) if lib_rs.exists() else None
+    return match.group(1) if match else None
+
+
+def _moved(path: Path, source: Path, target: Path) -> Path:
+    if path == source:
+        return target
+    if source in path.parents:
+        return target / path.relative_to(source)
+    return path
+
+
+__all__ = [
+    "RenameResult",
+    "rename_program",
+]
@@ -0,0 +1,131 @@
+"""Fetch templates from Git repositories referenced as ``git+<url>#<ref>``."""
+
+from __future__ import annotations
+
+import hashlib
+import re
+import shutil
+import subprocess
+from dataclasses import dataclass
+from pathlib import Path
+
+from .template_manifest import MANIFEST_NAME
+from .templates import TemplateError
+
+GIT_PREFIX = "git+"
+
+_COMMIT_PATTERN = re.compile(r"[0-9a-fA-F]{7,40}")
+
+
+class RemoteTemplateError(TemplateError):
+    """Raised when a remote template cannot be fetched or is not a valid template."""
+
+
+@dataclass(frozen=True)
+class GitTemplateSource:
+    url: str
+    ref: str
+
+    @property
+    def cache_key(self) -> str:
+        return hashlib.sha256(f"{self.url}#{self.ref}".encode()).hexdigest()[:16]
+
+
+def is_git_template(spec: str) -> bool:
+    return spec.startswith(GIT_PREFIX)
+
+
+def parse_git_template(spec: str) -> GitTemplateSource:
+    """Parse ``git+https://host/repo#ref``; the ref is mandatory.
+
+    Requiring a branch, tag or commit keeps scaffolds reproducible instead of
+    following whatever the remote's default branch points at today.
+    """
+    if not is_git_template(spec):
+        raise RemoteTemplateError(f"'{spec}' is not a git template; expected git+<url>#<ref>.")
+    url, sep, ref = spec[len(GIT_PREFIX):].partition("#")
+    if not url:
+        raise RemoteTemplateError(f"'{spec}' has no repository URL.")
+    if not sep or not ref.strip():
+        raise RemoteTemplateError(f"'{spec}' must pin a branch, tag or commit with #<ref>.")
+    return GitTemplateSource(url=url, ref=ref.strip())
+
+
+def fetch_git_template(spec: str, cache_dir: Path) -> Path:
+    """Shallow-fetch ``spec`` into ``cache_dir`` and return its template directory.
+
+    The repository must use the blueprint layout: ``solcoder.toml`` at the root
+    next to a ``template/`` directory holding the files to render. Cached clones
+    are refreshed on every call, so moving branches pick up new commits while
+    commit refs stay fixed.
+    """
+    source = parse_git_template(spec)
+    checkout = cache_dir.expanduser() / source.cache_key
+    if not (checkout / ".git").exists():
+        shutil.rmtree(checkout, ignore_errors=True)
+        checkout.mkdir(parents=True)
+        _git(checkout, "init", "--quiet")
+        _git(checkout, "remote", "add", "origin", source.url)
+    _git(checkout, "fetch", "--quiet", "--depth", "1", "origin", source.ref)
+    _git(checkout, "checkout", "--quiet", "--force", "--detach", "FETCH_HEAD")
+    _git(checkout, "clean", "--quiet", "-fdx")
+
+    if _COMMIT_PATTERN.fullmatch(source.ref):
+        head = _git(checkout, "rev-parse", "HEAD")
+        if not head.startswith(source.ref.lower()):
+            raise RemoteTemplateError(f"{source.url} resolved {source.ref} to {head}.")
+
+    if not (checkout / MANIFEST_NAME).exists():
+        raise RemoteTemplateError(f"{source.url}@{source.ref} has no {MANIFEST_NAME} at its root.")
+    template_dir = checkout / "template"
+    if not template_dir.is_dir():
+        raise RemoteTemplateError(f"{source.url}@{source.ref} has no template/ directory.")
+    _check_symlinks(checkout, source)
+    return template_dir
+
+
+def _check_symlinks(checkout: Path, source: GitTemplateSource) -> None:
+    """Reject symlinks that leave ``checkout``.
+
+    Rendering copies what links point at, so a link to e.g. ``~/.ssh`` would pull
+    local files into the generated project.
+    """
+    root = checkout.resolve()
+    for path in sorted(checkout.rglob("*")):
+        if not path.is_symlink() or ".git" in path.relative_to(checkout).parts:
+            continue
+        target = path.resolve()
+        if not target.exists() or not target.is_relative_to(root):
+            name = path.relative_to(checkout).as_posix()
+            raise RemoteTemplateError(
+                f"{source.url}@{source.ref} has a symlink that points outside the repository: {name}."
+            )
+
+
+def _git(cwd: Path, *args: str) -> str:
+    try:
+        result = subprocess.run(
+            ["git", *args],
+            cwd=cwd,
+            capture_output=True,
+            text=True,
+            check=False,
+            timeout=120,
+        )
+    except FileNotFoundError:
+        raise RemoteTemplateError("git is required for remote templates but was not found in PATH.") from None
+    except subprocess.TimeoutExpired:
+        raise RemoteTemplateError(f"git {args[0]} timed out.") from None
+    if result.returncode != 0:
+        detail = (result.stderr or result.stdout).strip().splitlines()
+        raise RemoteTemplateError(f"git {args[0]} failed: {detail[-1] if detail else 'unknown error'}")
+    return result.stdout.strip()
+
+
+__all__ = [
+    "GitTemplateSource",
+    "RemoteTemplateError",
+    "fetch_git_template",
+    "is_git_template",
+    "parse_git_template",
+]
@@ -0,0 +1,42 @@
+"""Lightweight scanning of Anchor program source, shared by the template checks."""
+
+from __future__ import annotations
+
+import re
+from collections.abc import Iterator
+
+_PROGRAM_PATTERN = re.compile(r"#\[program\]\s*pub\s+mod\s+[\w{}]+\s*\{")
+INSTRUCTION_PATTERN = re.compile(r"\bpub\s+fn\s+(\w+)\s*(?:<[^>]*>)?\s*\(")
+_COMMENT_PATTERN = re.compile(r"//[^\n]*|/\*.*?\*/", re.DOTALL)
+
+
+def program_modules(source: str) -> Iterator[tuple[int, str]]:
+    """Yield ``(offset, body)`` for each ``#[program]`` module; ``offset`` is where ``body`` starts."""
+    for program in _PROGRAM_PATTERN.finditer(source):
+        start = program.end()
+        yield start, block(source, start - 1)
+
+
+def block(source: str, start: int, open_char: str = "{", close_char: str = "}") -> str:
+    """Return the text between the bracket at ``start`` and its matching close bracket."""
+    depth = 0
+    for index in range(start, len(source)):
+        if source[index] == open_char:
+            depth += 1
+        elif source[index] == close_char:
+            depth -= 1
+            if depth == 0:
+                return source[start + 1 : index]
+    return source[start + 1 :]
+
+
+def strip_comments(source: str) -> str:
+    return _COMMENT_PATTERN.sub("", source)
+
+
+__all__ = [
+    "INSTRUCTION_PATTERN",
+    "block",
+    "program_modules",
+    "strip_comments",
+]
@@ -0,0 +1,247 @@
+"""Per-template manifests declaring the placeholders a template may reference."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Mapping
+
+try:  # Python 3.11+
+    import tomllib  # type: ignore[attr-defined]
+except ModuleNotFoundError:  # pragma: no cover - fallback for older interpreters
+    import tomli as tomllib  # type: ignore[no-redef]
+
+MANIFEST_NAME = "solcoder.toml"
+
+_PLACEHOLDER_PATTERN = re.compile(r"\{\{([A-Z0-9_]+)\}\}")
+_U64_MAX = 2**64 - 1
+
+
+class ManifestError(ValueError):
+    """Raised when a manifest is malformed or a variable fails validation."""
+
+
+@dataclass(frozen=True)
+class TemplateVariable:
+    name: str
+    description: str = ""
+    default: str | None = None
+    pattern: str | None = None
+    minimum: int | None = None
+    maximum: int | None = None
+    # Variable holding the decimals this whole-token amount is scaled by; the
+    # amount in base units must fit in a u64.
+    decimals: str | None = None
+
+    def validate(self, value: str) -> str:
+        """Return the normalised value or raise ManifestError."""
+        if self.pattern and not re.fullmatch(self.pattern, value):
+            raise ManifestError(f"{self.name} '{value}' does not match {self.pattern}.")
+        if self.minimum is not None or self.maximum is not None:
+            try:
+                number = int(value)
+            except ValueError:
+                raise ManifestError(f"{self.name} must be an integer, got '{value}'.") from None
+            if (self.minimum is not None and number < self.minimum) or (
+                self.maximum is not None and number > self.maximum
+            ):
+                raise ManifestError(
+                    f"{self.name} must be between {self.minimum} and {self.maximum}, got {number}."
+                )
+            return str(number)
+        return value
+
+
+@dataclass(frozen=True)
+class AccountField:
+    name: str
+    type: str
+    # Element bound for String/Vec fields: a number, or a constant on the account's impl.
+    max_len: int | str | None = None
+
+
+@dataclass(frozen=True)
+class AccountLayout:
+    """Field layout of an ``#[account]`` struct, in declaration order."""
+
+    name: str
+    fields: list[AccountField] = field(default_factory=list)
+
+
+@dataclass(frozen=True)
+class ClonedAccount:
+    """An account `anchor test` copies from ``url`` into the local validator."""
+
+    address: str
+    description: str = ""
+
+
+@dataclass(frozen=True)
+class TemplateManifest:
+    path: Path
+    variables: dict[str, TemplateVariable] = field(default_factory=dict)
+    accounts: dict[str, AccountLayout] = field(default_factory=dict)
+    # Instructions whose empty bodies are intentional, so the stub check skips them.
+    noop_instructions: frozenset[str] = frozenset()
+    # [validator]: accounts the tests depend on and the cluster they are cloned from.
+    clone_accounts: list[ClonedAccount] = field(default_factory=list)
+    validator_url: str | None = None
+    # [anchor] features: anchor-lang features the program crate needs, e.g. "init-if-needed".
+    anchor_features: list[str] = field(default_factory=list)
+
+    def resolve(self, values: Mapping[str, str], referenced: set[str]) -> dict[str, str]:
+        """Validate ``values`` against the manifest and fill in declared defaults.
+
+        ``referenced`` is the set of placeholders the template actually uses; any
+        that the manifest does not declare are rejected.
+        """
+        undeclared = sorted(referenced - self.variables.keys())
+        if undeclared:
+            raise ManifestError(
+                f"Template references undeclared placeholders: {', '.join(undeclared)}. "
+                f"Declare them in {self.path}."
+            )
+        resolved = dict(values)
+        for name, variable in self.variables.items():
+            value = resolved.get(name)
+            if value is None:
+                value = variable.default
+            if value is None:
+                raise ManifestError(f"{name} is required by {self.path} but has no value.")
+            resolved[name] = variable.validate(str(value))
+        for name, variable in self.variables.items():
+            if variable.decimals is not None:
+                resolved[name] = _base_units_checked(name, resolved[name], resolved.get(variable.decimals))
+        return resolved
+
+
+def manifest_path(template_dir: Path) -> Path:
+    """Manifests live next to wizard.json, outside the files copied into projects."""
+    return template_dir.parent / MANIFEST_NAME
+
+
+def load_manifest(template_dir: Path) -> TemplateManifest | None:
+    """Load the manifest for ``template_dir``; templates without one return None."""
+    path = manifest_path(template_dir)
+    if not path.exists():
+        return None
+    try:
+        data = tomllib.loads(path.read_text())
+    except tomllib.TOMLDecodeError as exc:
+        raise ManifestError(f"Failed to parse {path}: {exc}") from exc
+    section = data.get("variables") or {}
+    if not isinstance(section, dict):
+        raise ManifestError(f"{path}: [variables] must be a table.")
+    variables = {name: _parse_variable(path, name, spec) for name, spec in section.items()}
+    accounts_section = data.get("accounts") or {}
+    if not isinstance(accounts_section, dict):
+        raise ManifestError(f"{path}: [accounts] must be a table.")
+    accounts = {name: _parse_account(path, name, spec) for name, spec in accounts_section.items()}
+    instructions = data.get("instructions") or {}
+    noop = instructions.get("noop", []) if isinstance(instructions, dict) else None
+    if not isinstance(noop, list) or not all(isinstance(name, str) for name in noop):
+        raise ManifestError(f"{path}: [instructions] noop must be a list of instruction names.")
+    validator = data.get("validator") or {}
+    if not isinstance(validator, dict):
+        raise ManifestError(f"{path}: [validator] must be a table.")
+    url = validator.get("url")
+    if url is not None and not isinstance(url, str):
+        raise ManifestError(f"{path}: validator.url must be a string.")
+    anchor = data.get("anchor") or {}
+    features = anchor.get("features", []) if isinstance(anchor, dict) else None
+    if not isinstance(features, list) or not all(isinstance(name, str) for name in features):
+        raise ManifestError(f"{path}: [anchor] features must be a list of anchor-lang feature names.")
+    return TemplateManifest(
+        path=path,
+        variables=variables,
+        accounts=accounts,
+        noop_instructions=frozenset(noop),
+        clone_accounts=_parse_clones(path, validator.get("clone", [])),
+        validator_url=url,
+        anchor_features=features,
+    )
+
+
+def find_placeholders(template_dir: Path) -> set[str]:
+    """Return every ``{{NAME}}`` token used in file contents or path segments."""
+    found: set[str] = set()
+    for path in template_dir.rglob("*"):
+        found.update(_PLACEHOLDER_PATTERN.findall(path.name))
+        if path.is_file():
+            found.update(_PLACEHOLDER_PATTERN.findall(path.read_text()))
+    return found
+
+
+def _base_units_checked(name: str, amount: str, decimals: str | None) -> str:
+    """Return ``amount`` normalised, or raise unless its base units fit in a u64."""
+    if not amount.isdigit():
+        raise ManifestError(f"{name} must be a whole number of tokens, got '{amount}'.")
+    base_units = int(amount) * 10 ** int(decimals or 0)
+    if base_units > _U64_MAX:
+        raise ManifestError(
+            f"{name} {int(amount)} with {decimals} decimals is {base_units} base units, "
+            f"more than a u64 holds ({_U64_MAX})."
+        )
+    return str(int(amount))
+
+
+def _parse_variable(path: Path, name: str, spec: Any) -> TemplateVariable:
+    if not isinstance(spec, dict):
+        raise ManifestError(f"{path}: variables.{name} must be a table.")
+    default = spec.get("default")
+    pattern = spec.get("pattern")
+    if pattern is not None:
+        try:
+            re.compile(pattern)
+        except re.error as exc:
+            raise ManifestError(f"{path}: variables.{name} has an invalid pattern: {exc}") from exc
+    return TemplateVariable(
+        name=name,
+        description=str(spec.get("description", "")),
+        default=None if default is None else str(default),
+        pattern=pattern,
+        minimum=spec.get("min"),
+        maximum=spec.get("max"),
+        decimals=spec.get("decimals"),
+    )
+
+
+def _parse_account(path: Path, name: str, spec: Any) -> AccountLayout:
+    fields = spec.get("fields") if isinstance(spec, dict) else None
+    if not isinstance(fields, list):
+        raise ManifestError(f"{path}: accounts.{name} needs a fields = [...] list.")
+    parsed = []
+    for entry in fields:
+        if not isinstance(entry, dict) or not entry.get("name") or not entry.get("type"):
+            raise ManifestError(f"{path}: every accounts.{name} field needs a name and a type.")
+        max_len = entry.get("max_len")
+        if max_len is not None and not isinstance(max_len, (int, str)):
+            raise ManifestError(f"{path}: accounts.{name}.{entry['name']} max_len must be a number or constant name.")
+        parsed.append(AccountField(name=str(entry["name"]), type=str(entry["type"]), max_len=max_len))
+    return AccountLayout(name=name, fields=parsed)
+
+
+def _parse_clones(path: Path, entries: Any) -> list[ClonedAccount]:
+    if not isinstance(entries, list):
+        raise ManifestError(f"{path}: validator.clone must be a list of {{ address, description }} tables.")
+    clones = []
+    for entry in entries:
+        if not isinstance(entry, dict) or not isinstance(entry.get("address"), str):
+            raise ManifestError(f"{path}: every validator.clone entry needs an address.")
+        clones.append(ClonedAccount(address=entry["address"], description=str(entry.get("description", ""))))
+    return clones
+
+
+__all__ = [
+    "AccountField",
+    "AccountLayout",
+    "ClonedAccount",
+    "MANIFEST_NAME",
+    "ManifestError",
+    "TemplateManifest",
+    "TemplateVariable",
+    "find_placeholders",
+    "load_manifest",
+    "manifest_path",
+]
@@ -0,0 +1,271 @@
+"""Document a rendered program's instructions in its README, with TypeScript examples."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Mapping
+
+from .rust_source import INSTRUCTION_PATTERN, block, program_modules, strip_comments
+
+# Replaced by the generated section; templates without it get the section appended.
+README_MARKER = "<!-- solcoder:instructions -->"
+
+_DOC_PATTERN = re.compile(r"(?:^[ \t]*///[^\n]*\n)+\Z", re.MULTILINE)
+_ACCOUNTS_PATTERN = re.compile(r"\bpub\s+struct\s+(\w+)\s*<'info>\s*\{")
+_CONTEXT_PATTERN = re.compile(r"(\w+)\s*(?:<\s*'\w+\s*>)?\s*>$")
+# Accounts the Anchor client fills in by itself.
+_RESOLVED_ACCOUNT_PATTERN = re.compile(r"^(?:Program|Interface|Sysvar)<")
+_NUMBER_TYPES = {"u8", "u16", "u32", "i8", "i16", "i32", "f32", "f64"}
+_BN_TYPES = {"u64", "i64", "u128", "i128"}
+
+
+@dataclass(frozen=True)
+class AccountDoc:
+    name: str
+    type: str
+
+    @property
+    def optional(self) -> bool:
+        return self.type.startswith("Option<")
+
+    @property
+    def signer(self) -> bool:
+        return "Signer<" in self.type
+
+    @property
+    def resolved(self) -> bool:
+        return bool(_RESOLVED_ACCOUNT_PATTERN.match(self.type))
+
+
+@dataclass(frozen=True)
+class InstructionDoc:
+    name: str
+    # ``(name, rust type)`` of every argument after the context.
+    args: list[tuple[str, str]] = field(default_factory=list)
+    accounts: list[AccountDoc] = field(default_factory=list)
+    doc: str = ""
+
+
+def program_instructions(source: str) -> list[InstructionDoc]:
+    """Every ``#[program]`` instruction in ``source``, with its arguments and accounts."""
+    accounts = _accounts_structs(source)
+    found: list[InstructionDoc] = []
+    for _, module in program_modules(source):
+        for instruction in INSTRUCTION_PATTERN.finditer(module):
+            params = _split(block(module, instruction.end() - 1, "(", ")"))
+            context = _CONTEXT_PATTERN.search(params[0].split(":", 1)[-1].strip()) if params else None
+            args = []
+            for param in params[1:]:
+                name, _, rust_type = param.partition(":")
+                # `_key`-style names only silence unused warnings; clients see `key`.
+                args.append((name.strip().lstrip("_"), " ".join(rust_type.split())))
+            line_start = module.rfind("\n", 0, instruction.start()) + 1
+            doc = _DOC_PATTERN.search(module, 0, line_start)
+            lines = [line.strip()[3:].strip() for line in doc.group(0).splitlines()] if doc else []
+            found.append(
+                InstructionDoc(
+                    name=instruction.group(1),
+                    args=args,
+                    accounts=accounts.get(context.group(1), []) if context else [],
+                    doc=" ".join(line for line in lines if line),
+                )
+            )
+    return found
+
+
+def instructions_section(instructions: list[InstructionDoc], replacements: Mapping[str, str]) -> str:
+    """Markdown section with a runnable setup and one example per instruction."""
+    snake = replacements["PROGRAM_NAME_SNAKE"]
+    pascal = replacements["PROGRAM_NAME_PASCAL"]
+    lines = [
+        _heading(snake),
+        "",
+        f"Generated from `programs/{snake}/src`. Every example below runs against this setup, "
+        "from a script or test in this workspace:",
+        "",
+        "```ts",
+        'import * as anchor from "@coral-xyz/anchor";',
+        'import { Program } from "@coral-xyz/anchor";',
+        f'import {{ {pascal} }} from "../target/types/{snake}";',
+        "",
+        "anchor.setProvider(anchor.AnchorProvider.env());",
+        f"const program = anchor.workspace.{pascal} as Program<{pascal}>;",
+        f'// program.programId is {replacements["PROGRAM_ID"]}',
+        "```",
+    ]
+    for instruction in instructions:
+        lines += ["", f"### `{instruction.name}`", ""]
+        if instruction.doc:
+            lines += [instruction.doc, ""]
+        if instruction.args:
+            lines.append("- Arguments: " + ", ".join(f"`{name}: {rust}`" for name, rust in instruction.args))
+        if instruction.accounts:
+            accounts = ", ".join(_describe_account(account) for account in instruction.accounts)
+            lines.append(f"- Accounts: {accounts}")
+        if instruction.args or instruction.accounts:
+            lines.append("")
+        lines += ["```ts", *_example(instruction), "```"]
+    return "\n".join(lines) + "\n"
+
+
+def write_instructions_readme(root: Path, replacements: Mapping[str, str], template: str) -> Path | None:
+    """Add the instructions section to ``root/README.md``, creating the README if needed.
+
+    The section replaces README_MARKER when the template's README has one and is
+    appended otherwise. Returns None when the program has no instructions.
+    """
+    snake = replacements["PROGRAM_NAME_SNAKE"]
+    instructions: list[InstructionDoc] = []
+    for path in sorted((root / "programs" / snake / "src").glob("**/*.rs")):
+        instructions.extend(program_instructions(path.read_text()))
+    readme = root / "README.md"
+    if not instructions:
+        if readme.exists() and README_MARKER in readme.read_text():
+            readme.write_text(readme.read_text().replace(README_MARKER + "\n", ""))
+        return None
+    section = instructions_section(instructions, replacements)
+    if not readme.exists():
+        title = replacements["PROGRAM_NAME_TITLE"]
+        readme.write_text(
+            f"# {title}\n\n"
+            f"Anchor program `{snake}`, scaffolded from the `{template}` template.\n\n"
+            f"- Program ID: `{replacements['PROGRAM_ID']}` (keypair in `target/deploy/{snake}-keypair.json`)\n"
+            f"- Cluster: `{replacements['CLUSTER']}`\n\n"
+            "## Build and test\n\n"
+            "```bash\nanchor build\nyarn install\nanchor test\n```\n\n" + section
+        )
+        return readme
+    text = readme.read_text()
+    if README_MARKER in text:
+        readme.write_text(text.replace(README_MARKER, section.rstrip("\n")))
+    else:
+        readme.write_text(text.rstrip("\n") + "\n\n" + section)
+    return readme
+
+
+def merge_instructions_readme(staged: Path, root: Path, snake: str) -> None:
+    """Append program ``snake``'s section from the README in ``staged`` to the one in ``root``."""
+    source, target = staged / "README.md", root / "README.md"
+    if not source.exists() or not target.exists():
+        return
+    lines = source.read_text().splitlines()
+    if _heading(snake) not in lines:
+        return
+    start = lines.index(_heading(snake))
+    # Code blocks hold no `## ` lines, so the next one ends the section.
+    end = next((i for i in range(start + 1, len(lines)) if lines[i].startswith("## ")), len(lines))
+    section = "\n".join(lines[start:end]).rstrip("\n")
+    target.write_text(target.read_text().rstrip("\n") + "\n\n" + section + "\n")
+
+
+def _heading(snake: str) -> str:
+    return f"## `{snake}` instructions"
+
+
+def _accounts_structs(source: str) -> dict[str, list[AccountDoc]]:
+    found: dict[str, list[AccountDoc]] = {}
+    for match in _ACCOUNTS_PATTERN.finditer(source):
+        body = _strip_attributes(strip_comments(block(source, match.end() - 1)))
+        fields = []
+        for item in _split(body):
+            name, _, rust_type = item.partition(":")
+            name = name.strip()
+            if name.startswith("pub "):
+                fields.append(AccountDoc(name=name[4:].strip(), type=" ".join(rust_type.split())))
+        found[match.group(1)] = fields
+    return found
+
+
+def _strip_attributes(source: str) -> str:
+    """Drop ``#[...]`` attributes, whose seeds and constraints contain commas."""
+    result = []
+    index = 0
+    while index < len(source):
+        if source.startswith("#[", index):
+            index += len(block(source, index + 1, "[", "]")) + 3
+            continue
+        result.append(source[index])
+        index += 1
+    return "".join(result)
+
+
+def _split(source: str) -> list[str]:
+    """Split on commas outside ``<>``, ``()`` and ``[]``, dropping empty items."""
+    items, depth, current = [], 0, []
+    for char in source:
+        if char in "<([":
+            depth += 1
+        elif char in ">)]":
+            depth -= 1
+        if char == "," and depth == 0:
+            items.append("".join(current))
+            current = []
+        else:
+            current.append(char)
+    items.append("".join(current))
+    return [item.strip() for item in items if item.strip()]
+
+
+def _describe_account(account: AccountDoc) -> str:
+    notes = [note for note, flag in (("signer", account.signer), ("optional", account.optional)) if flag]
+    return f"`{account.name}`" + (f" ({', '.join(notes)})" if notes else "")
+
+
+def _example(instruction: InstructionDoc) -> list[str]:
+    lines = [f"// {_camel(name)}: {_ts_type(rust)}" for name, rust in instruction.args]
+    args = ", ".join(_camel(name) for name, _ in instruction.args)
+    lines += ["await program.methods", f"  .{_camel(instruction.name)}({args})"]
+    accounts = [
+        f"{_camel(account.name)}: null" if account.optional else _camel(account.name)
+        for account in instruction.accounts
+        if not account.resolved
+    ]
+    single = f"  .accountsPartial({{ {', '.join(accounts)} }})"
+    if accounts and len(single) <= 80:
+        lines.append(single)
+    elif accounts:
+        lines += ["  .accountsPartial({", *(f"    {account}," for account in accounts), "  })"]
+    lines.append("  .rpc();")
+    return lines
+
+
+def _ts_type(rust: str) -> str:
+    """TypeScript type the Anchor client expects for a Rust argument type."""
+    rust = rust.replace(" ", "")
+    if rust in _NUMBER_TYPES:
+        return "number"
+    if rust in _BN_TYPES:
+        return "anchor.BN"
+    if rust == "bool":
+        return "boolean"
+    if rust in ("String", "&str"):
+        return "string"
+    if rust == "Pubkey":
+        return "anchor.web3.PublicKey"
+    if rust in ("Vec<u8>", "&[u8]"):
+        return "Buffer"
+    if re.fullmatch(r"\[u8;\w+\]", rust):
+        return "number[]"
+    inner = re.fullmatch(r"(Option|Vec)<(.+)>", rust)
+    if inner:
+        kind, element = inner.groups()
+        return f"{_ts_type(element)} | null" if kind == "Option" else f"{_ts_type(element)}[]"
+    return rust
+
+
+def _camel(name: str) -> str:
+    head, *rest = name.lstrip("_").split("_")
+    return head + "".join(word.capitalize() for word in rest)
+
+
+__all__ = [
+    "README_MARKER",
+    "AccountDoc",
+    "InstructionDoc",
+    "instructions_section",
+    "merge_instructions_readme",
+    "program_instructions",
+    "write_instructions_readme",
+]
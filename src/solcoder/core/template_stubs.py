@@ -0,0 +1,64 @@
+"""Find program instructions that a template ships without an implementation."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+
+from .rust_source import INSTRUCTION_PATTERN, block, program_modules, strip_comments
+from .template_manifest import load_manifest
+
+# Statements that do not touch any account: logging and discarding arguments.
+_INERT_PATTERN = re.compile(r"\bmsg!\s*\(.*?\)\s*;|\blet\s+_\w*\s*(?::[^=;]+)?=[^;]*;", re.DOTALL)
+_STUB_BODY_PATTERN = re.compile(r"(?:Ok\(\(\)\)|(?:todo|unimplemented)!\([^)]*\))?")
+
+
+@dataclass(frozen=True)
+class StubInstruction:
+    # Source file relative to the template directory, placeholders unrendered.
+    path: str
+    line: int
+    name: str
+
+
+def find_stub_instructions(source: str) -> list[tuple[int, str]]:
+    """``(line, name)`` of every ``#[program]`` instruction whose body does nothing.
+
+    A body does nothing when, ignoring comments, ``msg!`` calls and ``let _ = ...``
+    bindings, it is empty, ``Ok(())``, ``todo!()`` or ``unimplemented!()``.
+    """
+    found: list[tuple[int, str]] = []
+    for start, module in program_modules(source):
+        for instruction in INSTRUCTION_PATTERN.finditer(module):
+            brace = module.find("{", instruction.end())
+            if brace == -1:
+                continue
+            body = _INERT_PATTERN.sub("", strip_comments(block(module, brace)))
+            if _STUB_BODY_PATTERN.fullmatch("".join(body.split())):
+                line = source.count("\n", 0, start + instruction.start()) + 1
+                found.append((line, instruction.group(1)))
+    return found
+
+
+def stub_instructions(template_dir: Path) -> list[StubInstruction]:
+    """Stub instructions in a template's programs, minus the manifest's ``[instructions] noop``.
+
+    Raises ManifestError when the template's solcoder.toml is malformed.
+    """
+    manifest = load_manifest(template_dir)
+    intentional = manifest.noop_instructions if manifest is not None else frozenset()
+    stubs: list[StubInstruction] = []
+    for path in sorted(template_dir.glob("programs/*/src/**/*.rs")):
+        relative = path.relative_to(template_dir).as_posix()
+        for line, name in find_stub_instructions(path.read_text()):
+            if name not in intentional:
+                stubs.append(StubInstruction(path=relative, line=line, name=name))
+    return stubs
+
+
+__all__ = [
+    "StubInstruction",
+    "find_stub_instructions",
+    "stub_instructions",
+]
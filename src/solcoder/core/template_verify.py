@@ -0,0 +1,59 @@
+"""Optional build check for freshly scaffolded projects (``--verify``)."""
+
+from __future__ import annotations
+
+import re
+import shutil
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from solcoder.solana.deploy import DeployError, run_anchor_command
+
+from .templates import TemplateError
+
+# rustc points at the offending source with ` --> path:line:col`.
+_DIAGNOSTIC_LOCATION = re.compile(r"^\s*--> (?P<path>[^:\n]+):(?P<line>\d+):\d+", re.MULTILINE)
+
+
+@dataclass(frozen=True)
+class BuildCheck:
+    command: list[str]
+    ok: bool
+    output: str
+    # ``path:line`` locations from compiler diagnostics, in first-seen order.
+    locations: list[str] = field(default_factory=list)
+
+
+def build_command() -> list[str]:
+    """Prefer ``anchor build`` so the IDL is checked too; plain cargo otherwise."""
+    if shutil.which("anchor") is not None:
+        return ["anchor", "build"]
+    return ["cargo", "build"]
+
+
+def verify_build(root: Path, *, timeout: int = 900) -> BuildCheck:
+    """Build the scaffolded project at ``root`` and collect the failing locations."""
+    command = build_command()
+    try:
+        result = run_anchor_command(command, cwd=root, timeout=timeout)
+    except DeployError as exc:
+        raise TemplateError(f"Cannot verify {root}: {exc}") from exc
+    output = "\n".join(part for part in (result.stdout, result.stderr) if part).strip()
+    return BuildCheck(
+        command=command,
+        ok=result.success,
+        output=output,
+        locations=_diagnostic_locations(output) if not result.success else [],
+    )
+
+
+def _diagnostic_locations(output: str) -> list[str]:
+    locations: list[str] = []
+    for match in _DIAGNOSTIC_LOCATION.finditer(output):
+        location = f"{match.group('path').strip()}:{match.group('line')}"
+        if location not in locations:
+            locations.append(location)
+    return locations
+
+
+__all__ = ["BuildCheck", "build_command", "verify_build"]
@@ -2,11 +2,37 @@
 
 from __future__ import annotations
 
+import json
+import os
 import re
 import shutil
-from dataclasses import dataclass
+import tempfile
+from dataclasses import dataclass, field, replace
+from datetime import date
 from pathlib import Path
-from typing import Dict
+from typing import Any, Dict
+
+from cryptography.hazmat.primitives.asymmetric import ed25519
+from cryptography.hazmat.primitives.serialization import (
+    Encoding,
+    NoEncryption,
+    PrivateFormat,
+    PublicFormat,
+)
+
+try:  # Python 3.11+
+    import tomllib  # type: ignore[attr-defined]
+except ModuleNotFoundError:  # pragma: no cover - fallback for older interpreters
+    import tomli as tomllib  # type: ignore[no-redef]
+
+from .template_manifest import (
+    ClonedAccount,
+    ManifestError,
+    TemplateVariable,
+    find_placeholders,
+    load_manifest,
+)
+from .template_readme import program_instructions, write_instructions_readme
 
 
 class TemplateError(RuntimeError):
@@ -20,6 +46,95 @@ class TemplateNotFoundError(TemplateError):
 class TemplateExistsError(TemplateError):
     """Raised when attempting to render into an existing directory without force."""
 
+    def __init__(self, message: str, conflicts: list[str] | None = None) -> None:
+        self.conflicts = conflicts or []
+        super().__init__(message)
+
+
+class InvalidProgramIdError(TemplateError):
+    """Raised when the program id is not a base58-encoded 32-byte public key."""
+
+
+class InvalidProgramNameError(TemplateError):
+    """Raised when a program name cannot be turned into a Rust module identifier."""
+
+
+class UnrenderedPlaceholderError(TemplateError):
+    """Raised when rendered output still contains ``{{`` or ``}}`` tokens."""
+
+    def __init__(self, leftovers: list[str]) -> None:
+        self.leftovers = leftovers
+        listing = "\n".join(f"  {item}" for item in leftovers)
+        super().__init__(
+            f"Rendered output still contains unreplaced placeholders:\n{listing}"
+        )
+
+
+# Default program id; when left in place a fresh program keypair is generated.
+PLACEHOLDER_PROGRAM_ID = "replace-with-program-id"
+
+# SPDX identifiers with a bundled LICENSE text under core/licenses/.
+SUPPORTED_LICENSES = ("MIT", "Apache-2.0")
+
+_LICENSE_ROOT = Path(__file__).resolve().parent / "licenses"
+_LICENSED_SUFFIXES = {".rs", ".ts"}
+
+# Build output, local ledgers and deploy keys that must stay out of version control.
+GITIGNORE_ENTRIES = (
+    "target/",
+    ".anchor/",
+    "node_modules/",
+    "test-ledger/",
+    "**/*-keypair.json",
+)
+
+# Cluster `anchor test` clones a manifest's [validator] accounts from, unless the
+# manifest names another.
+DEFAULT_CLONE_URL = "https://api.mainnet-beta.solana.com"
+
+# Anchor release the blueprints are written against; their Cargo.toml and
+# package.json pin it.
+TEMPLATE_ANCHOR_VERSION = "0.30.1"
+
+# (major, minor) releases whose macro or client changes the templates' code depends on.
+_ANCHOR_BREAKING_RELEASES = (
+    ((0, 29), "`ctx.bumps` became a struct"),
+    (
+        (0, 30),
+        "the IDL moved to the `idl-build` feature and clients resolve accounts "
+        "via `accountsPartial`",
+    ),
+    (
+        (0, 31),
+        "account discriminators became slices and the Solana crates moved to 2.x",
+    ),
+)
+
+_SEMVER_PATTERN = re.compile(
+    r"\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?"
+)
+_CARGO_ANCHOR_PATTERN = re.compile(
+    r'(?m)^(anchor-(?:lang|spl)\s*=\s*(?:\{[^}\n]*\bversion\s*=\s*)?)"[^"]*"'
+)
+# `anchor-lang = "x.y.z"` or `anchor-lang = { ... }` on one line.
+_CARGO_ANCHOR_LANG_PATTERN = re.compile(
+    r'(?m)^(anchor-lang\s*=\s*)("[^"\n]*"|\{[^}\n]*\})[ \t]*$'
+)
+_CARGO_FEATURES_PATTERN = re.compile(r"\bfeatures\s*=\s*\[([^\]]*)\]")
+
+# Strict and reserved Rust keywords; `pub mod {{PROGRAM_NAME_SNAKE}}` cannot use them.
+RUST_KEYWORDS = frozenset(
+    """
+    abstract as async await become box break const continue crate do dyn else enum
+    extern false final fn for gen if impl in let loop macro match mod move mut override
+    priv pub ref return self static struct super trait true try type typeof union unsafe
+    unsized use virtual where while yield
+    """.split()
+)
+
+# Base58 alphabet constant mirrors solana.wallet to avoid import cycles.
+_BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
 
 @dataclass
 class RenderOptions:
@@ -27,12 +142,24 @@ class RenderOptions:
     destination: Path
     program_name: str = "counter"
     author_pubkey: str = "CHANGEME"
-    program_id: str = "replace-with-program-id"
-    cluster: str = "devnet"
+    program_id: str = PLACEHOLDER_PROGRAM_ID
+    cluster: str = "localnet"
+    wallet_path: str = "~/.config/solana/id.json"
     overwrite: bool = False
+    # Into a non-empty destination, only add the files that are missing.
+    merge: bool = False
     # Optional absolute/relative path to the template root directory.
     # When provided, this path takes precedence over the default templates/ lookup.
     template_path: Path | None = None
+    # Wizard answers; questions that declare a `placeholder` feed template variables.
+    answers: dict[str, Any] = field(default_factory=dict)
+    # Existing program keypair to use instead of generating one.
+    program_keypair: Path | None = None
+    # SPDX id from SUPPORTED_LICENSES; None or "none" skips headers and LICENSE.
+    license: str | None = None
+    # Semver pinned for anchor-lang/anchor-spl and @coral-xyz/anchor; None keeps
+    # TEMPLATE_ANCHOR_VERSION.
+    anchor_version: str | None = None
 
 
 _TEMPLATE_ROOT = Path(__file__).resolve().parents[3] / "templates"
@@ -41,8 +168,8 @@ _TEMPLATE_ROOT = Path(__file__).resolve().parents[3] / "templates"
 def available_templates() -> list[str]:
     """Return a list of available template keys.
 
-    Prefer the blueprint registry (src/solcoder/anchor/blueprints/registry.json). Fallback to
-    legacy templates/ directory if the registry is unavailable (dev-only).
+    Prefer the blueprint registry (src/solcoder/anchor/blueprints/registry.json).
+    Fallback to legacy templates/ directory if the registry is unavailable (dev-only).
     """
     try:
         # Lazy import to avoid heavy CLI deps at module import time
@@ -59,6 +186,89 @@ def available_templates() -> list[str]:
     return []
 
 
+@dataclass(frozen=True)
+class PlannedFile:
+    """A file render_template would write, relative to the destination."""
+
+    path: Path
+    # Template file it comes from; None for files generated at render time.
+    source: Path | None = None
+
+
+@dataclass(frozen=True)
+class TemplateInfo:
+    key: str
+    name: str
+    description: str
+    path: Path | None
+    variables: list[TemplateVariable] = field(default_factory=list)
+    # Complete templates ship program source and a solcoder.toml; stubs lack one
+    # or both.
+    complete: bool = False
+
+    @property
+    def required_variables(self) -> list[str]:
+        return [
+            variable.name for variable in self.variables if variable.default is None
+        ]
+
+
+def list_templates() -> list[TemplateInfo]:
+    """Describe each bundled blueprint and legacy templates/ directory, by key."""
+    found: dict[str, TemplateInfo] = {}
+    try:
+        from solcoder.cli.blueprints import (  # type: ignore
+            BLUEPRINTS_ROOT,
+            load_registry,
+            resolve_registry_template_path,
+        )
+
+        entries = {entry.key: entry for entry in load_registry() if entry.key}
+        keys = set(entries) | {p.name for p in BLUEPRINTS_ROOT.iterdir() if p.is_dir()}
+        for key in keys:
+            entry = entries.get(key)
+            template_dir = None
+            if entry is not None and entry.template_path:
+                template_dir = resolve_registry_template_path(entry.template_path)
+            if template_dir is None and (BLUEPRINTS_ROOT / key / "template").is_dir():
+                template_dir = BLUEPRINTS_ROOT / key / "template"
+            found[key] = _describe_template(
+                key,
+                entry.name if entry is not None else key,
+                entry.description if entry is not None else "",
+                template_dir,
+            )
+    except Exception:
+        pass
+    if _TEMPLATE_ROOT.exists():
+        for path in _TEMPLATE_ROOT.iterdir():
+            if path.is_dir() and path.name not in found:
+                found[path.name] = _describe_template(path.name, path.name, "", path)
+    return [found[key] for key in sorted(found)]
+
+
+def _describe_template(
+    key: str, name: str, description: str, template_dir: Path | None
+) -> TemplateInfo:
+    manifest = None
+    if template_dir is not None and template_dir.is_dir():
+        try:
+            manifest = load_manifest(template_dir)
+        except ManifestError:
+            manifest = None
+    has_source = template_dir is not None and any(
+        template_dir.glob("programs/*/src/lib.rs")
+    )
+    return TemplateInfo(
+        key=key,
+        name=name,
+        description=description,
+        path=template_dir,
+        variables=list(manifest.variables.values()) if manifest is not None else [],
+        complete=manifest is not None and has_source,
+    )
+
+
 def render_template(options: RenderOptions) -> Path:
     """Render a template into the destination directory.
 
@@ -66,7 +276,393 @@ def render_template(options: RenderOptions) -> Path:
     1) Explicit options.template_path (if provided)
     2) Registry template_path for the given template key
     3) Legacy templates/<key> directory (dev-only fallback)
+
+    A non-empty destination is refused with the conflicting paths unless
+    ``overwrite`` (replace those files) or ``merge`` (keep them, add the rest)
+    is set. Files the template does not produce are never removed.
+    """
+    destination = options.destination.expanduser().resolve()
+    populated = destination.exists() and any(destination.iterdir())
+    options = _reuse_existing_keypair(options, destination) if populated else options
+    template_dir, program_snake, replacements, keypair = _prepare_render(options)
+    license_id = resolve_license(options.license)
+    anchor_version = resolve_anchor_version(options.anchor_version)
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        staging = Path(tmpdir) / "render"
+        shutil.copytree(template_dir, staging)
+        _apply_replacements(staging, replacements)
+        _rename_placeholder_paths(staging, replacements)
+        _rename_paths(staging, program_snake)
+        leftovers = find_unrendered_placeholders(staging)
+        if leftovers:
+            # A half-substituted crate fails later with confusing compiler errors;
+            # never copy it out.
+            raise UnrenderedPlaceholderError(leftovers)
+        if keypair is not None:
+            _write_program_keypair(
+                program_keypair_path(staging, program_snake), keypair
+            )
+        if license_id is not None:
+            holder = f"{replacements['PROGRAM_NAME_TITLE']} contributors"
+            _apply_license(staging, license_id, holder)
+        if anchor_version is not None:
+            pin_anchor_version(staging, anchor_version)
+        _apply_validator_clones(staging, template_dir)
+        _apply_anchor_features(staging, template_dir, program_snake)
+        write_instructions_readme(staging, replacements, options.template)
+
+        if not populated:
+            # Fill an existing empty directory in place; it may be the working
+            # directory (`.`).
+            shutil.copytree(staging, destination, dirs_exist_ok=True)
+            ensure_gitignore(destination)
+            return destination
+        staged = sorted(path for path in staging.rglob("*") if path.is_file())
+        conflicts = [
+            path.relative_to(staging).as_posix()
+            for path in staged
+            if (destination / path.relative_to(staging)).exists()
+        ]
+        if not options.overwrite and not options.merge:
+            raise TemplateExistsError(
+                _conflict_message(destination, conflicts), conflicts
+            )
+        for source in staged:
+            target = destination / source.relative_to(staging)
+            if options.merge and target.exists():
+                continue
+            target.parent.mkdir(parents=True, exist_ok=True)
+            shutil.copy2(source, target)
+    ensure_gitignore(destination)
+    return destination
+
+
+def ensure_gitignore(root: Path) -> Path:
+    """Make ``root/.gitignore`` cover GITIGNORE_ENTRIES, appending missing lines."""
+    path = root / ".gitignore"
+    text = path.read_text() if path.exists() else ""
+    present = {line.strip() for line in text.splitlines()}
+    missing = [entry for entry in GITIGNORE_ENTRIES if entry not in present]
+    if missing:
+        if text and not text.endswith("\n"):
+            text += "\n"
+        path.write_text(text + "".join(f"{entry}\n" for entry in missing))
+    return path
+
+
+def _conflict_message(destination: Path, conflicts: list[str], limit: int = 10) -> str:
+    if not conflicts:
+        return (
+            f"Destination '{destination}' is not empty. "
+            "Re-run with --force to write into it or --merge to add the template files."
+        )
+    listing = "\n".join(f"  {item}" for item in conflicts[:limit])
+    if len(conflicts) > limit:
+        listing += f"\n  … {len(conflicts) - limit} more"
+    return (
+        f"Destination '{destination}' already contains files the template would "
+        f"write:\n{listing}\n"
+        "Re-run with --force to overwrite them or --merge to keep them and add only "
+        "missing files."
+    )
+
+
+def _reuse_existing_keypair(options: RenderOptions, destination: Path) -> RenderOptions:
+    """Keep the program keypair already in ``destination`` unless one was requested.
+
+    Overwriting or merging must not silently change the program id of a project
+    that may already be deployed.
+    """
+    if (
+        options.program_keypair is not None
+        or options.program_id != PLACEHOLDER_PROGRAM_ID
+    ):
+        return options
+    existing = program_keypair_path(
+        destination, normalise_program_name(options.program_name)
+    )
+    if not existing.exists():
+        return options
+    return replace(options, program_keypair=existing)
+
+
+def resolve_license(value: str | None) -> str | None:
+    """Return the canonical SPDX id for ``value``, or None for no license."""
+    if value is None or value.strip().lower() == "none":
+        return None
+    for license_id in SUPPORTED_LICENSES:
+        if value.strip().lower() == license_id.lower():
+            return license_id
+    raise TemplateError(
+        f"Unsupported license '{value}'. "
+        f"Use one of: {', '.join(SUPPORTED_LICENSES)}, none."
+    )
+
+
+def resolve_anchor_version(value: str | None) -> str | None:
+    """Return ``value`` as a bare semver (a leading ``v`` is dropped).
+
+    None means keep the template's version.
+    """
+    if value is None or not value.strip():
+        return None
+    version = value.strip().removeprefix("v")
+    if not _SEMVER_PATTERN.fullmatch(version):
+        raise TemplateError(
+            f"Anchor version '{value}' is not a semver such as "
+            f"{TEMPLATE_ANCHOR_VERSION}."
+        )
+    return version
+
+
+def anchor_version_warning(value: str | None) -> str | None:
+    """Describe breaking macro changes between ``value`` and TEMPLATE_ANCHOR_VERSION.
+
+    Returns None when there are none.
+    """
+    version = resolve_anchor_version(value)
+    if version is None:
+        return None
+    requested = _release(version)
+    baseline = _release(TEMPLATE_ANCHOR_VERSION)
+    low, high = sorted((requested, baseline))
+    changes = [
+        note for release, note in _ANCHOR_BREAKING_RELEASES if low < release <= high
+    ]
+    if not changes:
+        return None
+    return (
+        f"Anchor {version} crosses breaking changes from {TEMPLATE_ANCHOR_VERSION}, "
+        f"which the templates target ({'; '.join(changes)}); the generated code "
+        "may need updates to build."
+    )
+
+
+def pin_anchor_version(root: Path, version: str) -> None:
+    """Pin the anchor-lang/anchor-spl and @coral-xyz/anchor versions in ``root``.
+
+    Every Cargo.toml under ``root`` and its package.json are updated.
+    """
+    for path in sorted(root.rglob("Cargo.toml")):
+        text = path.read_text()
+        pinned = _CARGO_ANCHOR_PATTERN.sub(
+            lambda match: f'{match.group(1)}"{version}"', text
+        )
+        if pinned != text:
+            path.write_text(pinned)
+    package_json = root / "package.json"
+    if not package_json.exists():
+        return
+    package = json.loads(package_json.read_text())
+    changed = False
+    for section in ("dependencies", "devDependencies"):
+        deps = package.get(section)
+        if isinstance(deps, dict) and "@coral-xyz/anchor" in deps:
+            deps["@coral-xyz/anchor"] = f"^{version}"
+            changed = True
+    if changed:
+        package_json.write_text(json.dumps(package, indent=2) + "\n")
+
+
+def add_validator_clones(
+    text: str, clones: list[ClonedAccount], url: str | None = None
+) -> str:
+    """Add a ``[[test.validator.clone]]`` to Anchor.toml ``text`` per missing account.
+
+    A missing ``[test.validator]`` table is created with ``url`` (DEFAULT_CLONE_URL
+    by default); an existing one keeps its own.
     """
+    validator = tomllib.loads(text).get("test", {}).get("validator", {})
+    present = {entry.get("address") for entry in validator.get("clone", [])}
+    missing = [clone for clone in clones if clone.address not in present]
+    if not missing:
+        return text
+    text = text.rstrip() + "\n"
+    if not validator:
+        text += (
+            "\n# `anchor test` clones these accounts from `url` into the local "
+            "validator.\n"
+            f'[test.validator]\nurl = "{url or DEFAULT_CLONE_URL}"\n'
+        )
+    for clone in missing:
+        comment = f"# {clone.description}\n" if clone.description else ""
+        text += f'\n{comment}[[test.validator.clone]]\naddress = "{clone.address}"\n'
+    return text
+
+
+def merge_validator_clones(source: Path, target: Path) -> None:
+    """Copy the clone entries of Anchor.toml ``source`` that ``target`` lacks."""
+    if not source.exists() or not target.exists():
+        return
+    validator = tomllib.loads(source.read_text()).get("test", {}).get("validator", {})
+    clones = [
+        ClonedAccount(address=entry["address"])
+        for entry in validator.get("clone", [])
+        if isinstance(entry.get("address"), str)
+    ]
+    if clones:
+        text = target.read_text()
+        merged = add_validator_clones(text, clones, validator.get("url"))
+        if merged != text:
+            target.write_text(merged)
+
+
+def _apply_validator_clones(root: Path, template_dir: Path) -> None:
+    anchor_toml = root / "Anchor.toml"
+    manifest = load_manifest(template_dir)
+    if manifest is None or not manifest.clone_accounts or not anchor_toml.exists():
+        return
+    text = anchor_toml.read_text()
+    anchor_toml.write_text(
+        add_validator_clones(text, manifest.clone_accounts, manifest.validator_url)
+    )
+
+
+def enable_anchor_features(text: str, features: list[str]) -> str:
+    """Add ``features`` to the anchor-lang dependency in Cargo.toml ``text``.
+
+    Features the dependency already enables are kept.
+    """
+    match = _CARGO_ANCHOR_LANG_PATTERN.search(text)
+    if match is None or not features:
+        return text
+    spec = match.group(2).strip()
+    if spec.startswith('"'):
+        spec = f"{{ version = {spec} }}"
+    present = _CARGO_FEATURES_PATTERN.search(spec)
+    current = re.findall(r'"([^"]*)"', present.group(1)) if present else []
+    wanted = current + [feature for feature in features if feature not in current]
+    listing = "features = [" + ", ".join(f'"{feature}"' for feature in wanted) + "]"
+    if present:
+        spec = spec[: present.start()] + listing + spec[present.end() :]
+    else:
+        spec = spec[:-1].rstrip().rstrip(",") + f", {listing} }}"
+    return text[: match.start(2)] + spec + text[match.end(2) :]
+
+
+def _apply_anchor_features(root: Path, template_dir: Path, program_snake: str) -> None:
+    """Enable the manifest's [anchor] features on the program crate's anchor-lang."""
+    cargo_toml = root / "programs" / program_snake / "Cargo.toml"
+    manifest = load_manifest(template_dir)
+    if manifest is None or not manifest.anchor_features or not cargo_toml.exists():
+        return
+    text = cargo_toml.read_text()
+    enabled = enable_anchor_features(text, manifest.anchor_features)
+    if enabled != text:
+        cargo_toml.write_text(enabled)
+
+
+def _release(version: str) -> tuple[int, int]:
+    major, minor = version.split(".")[:2]
+    return int(major), int(minor)
+
+
+def _apply_license(root: Path, license_id: str, holder: str) -> None:
+    """Prepend an SPDX header to generated .rs/.ts files and write LICENSE."""
+    header = f"// SPDX-License-Identifier: {license_id}\n"
+    for path in sorted(root.rglob("*")):
+        if not path.is_file() or path.suffix not in _LICENSED_SUFFIXES:
+            continue
+        text = path.read_text()
+        if any("SPDX-License-Identifier:" in line for line in text.split("\n", 2)[:2]):
+            continue
+        if text.startswith("#!"):
+            shebang, _, rest = text.partition("\n")
+            path.write_text(f"{shebang}\n{header}{rest}")
+        else:
+            path.write_text(header + text)
+    text = (_LICENSE_ROOT / f"{license_id}.txt").read_text()
+    text = text.replace("{year}", str(date.today().year)).replace("{holder}", holder)
+    (root / "LICENSE").write_text(text)
+
+
+def find_unrendered_placeholders(root: Path) -> list[str]:
+    """List ``path:line: text`` for every ``{{`` / ``}}`` left under ``root``.
+
+    Path segments are checked too, so a directory that kept its ``{{NAME}}``
+    shows up as the bare relative path.
+    """
+    leftovers: list[str] = []
+    for path in sorted(root.rglob("*")):
+        relative = path.relative_to(root).as_posix()
+        if "{{" in path.name or "}}" in path.name:
+            leftovers.append(relative)
+        if not path.is_file():
+            continue
+        try:
+            text = path.read_text()
+        except (OSError, UnicodeDecodeError):
+            continue
+        for number, line in enumerate(text.splitlines(), start=1):
+            if "{{" in line or "}}" in line:
+                leftovers.append(f"{relative}:{number}: {line.strip()}")
+    return leftovers
+
+
+def preview_template(options: RenderOptions) -> list[PlannedFile]:
+    """Return the files render_template would write, without touching the filesystem.
+
+    Validation matches a real render, except that an existing destination is not
+    an error: previewing before an overwrite is the point.
+    """
+    template_dir, program_snake, replacements, keypair = _prepare_render(options)
+    resolve_anchor_version(options.anchor_version)
+    planned = []
+    for source in template_dir.rglob("*"):
+        if source.is_file():
+            relative = source.relative_to(template_dir)
+            path = _rendered_path(relative, replacements, program_snake)
+            planned.append(PlannedFile(path=path, source=relative))
+    if keypair is not None:
+        planned.append(PlannedFile(path=program_keypair_path(Path(), program_snake)))
+    planned_paths = {item.path for item in planned}
+    licensed = resolve_license(options.license) is not None
+    if licensed and Path("LICENSE") not in planned_paths:
+        planned.append(PlannedFile(path=Path("LICENSE")))
+    if Path(".gitignore") not in planned_paths:
+        planned.append(PlannedFile(path=Path(".gitignore")))
+    if Path("README.md") not in planned_paths and any(
+        program_instructions(path.read_text())
+        for path in template_dir.glob("programs/*/src/**/*.rs")
+    ):
+        planned.append(PlannedFile(path=Path("README.md")))
+    return sorted(planned, key=lambda item: item.path.as_posix())
+
+
+def _prepare_render(
+    options: RenderOptions,
+) -> tuple[Path, str, Dict[str, str], bytes | None]:
+    """Resolve the template and validate every input shared by render and preview."""
+    template_dir = resolve_template_dir(options)
+
+    keypair = _resolve_program_keypair(options)
+    program_id = _public_key(keypair) if keypair is not None else options.program_id
+    # Catch malformed ids here; otherwise `declare_id!` fails the build with a
+    # macro error.
+    validate_program_id(program_id)
+
+    program_snake = normalise_program_name(options.program_name)
+    replacements: Dict[str, str] = {
+        "PROGRAM_NAME_SNAKE": program_snake,
+        "PROGRAM_NAME_PASCAL": to_pascal_case(options.program_name),
+        "PROGRAM_NAME_CAMEL": to_camel_case(options.program_name),
+        "PROGRAM_NAME_TITLE": " ".join(
+            word.capitalize() for word in program_snake.split("_")
+        ),
+        "PROGRAM_NAME_RAW": options.program_name,
+        "AUTHOR_PUBKEY": options.author_pubkey,
+        "PROGRAM_ID": program_id,
+        "CLUSTER": options.cluster,
+        "WALLET_PATH": options.wallet_path,
+    }
+    replacements.update(_resolve_template_variables(options))
+    replacements = _apply_manifest(template_dir, replacements)
+    return template_dir, program_snake, replacements, keypair
+
+
+def resolve_template_dir(options: RenderOptions) -> Path:
+    """Return the directory render_template copies from (see its resolution order)."""
     template_dir: Path | None = options.template_path
     if template_dir is None:
         # Try registry
@@ -76,7 +672,9 @@ def render_template(options: RenderOptions) -> Path:
                 resolve_registry_template_path,  # type: ignore
             )
 
-            entry = next((e for e in load_registry() if e.key == options.template), None)
+            entry = next(
+                (e for e in load_registry() if e.key == options.template), None
+            )
             if entry is not None and entry.template_path:
                 resolved = resolve_registry_template_path(entry.template_path)
                 if resolved is not None and resolved.exists():
@@ -89,41 +687,183 @@ def render_template(options: RenderOptions) -> Path:
             template_dir = candidate
     if template_dir is None or not template_dir.exists():
         raise TemplateNotFoundError(f"Template '{options.template}' not found.")
+    return template_dir
 
-    destination = options.destination.expanduser().resolve()
-    if destination.exists():
-        if not options.overwrite:
-            raise TemplateExistsError(f"Destination '{destination}' already exists.")
-        if any(destination.iterdir()):
-            raise TemplateExistsError(
-                f"Destination '{destination}' must be empty when overwriting."
+
+def program_keypair_path(root: Path, program_snake: str) -> Path:
+    """Return where Anchor expects the program keypair inside a workspace."""
+    return root / "target" / "deploy" / f"{program_snake}-keypair.json"
+
+
+def read_program_id(keypair_path: Path) -> str:
+    """Return the base58 public key of a solana-keygen style keypair file."""
+    return _public_key(_load_program_keypair(keypair_path))
+
+
+def _resolve_program_keypair(options: RenderOptions) -> bytes | None:
+    """Return the 64-byte keypair backing the program id, if solcoder manages it.
+
+    An explicit program id is used as-is and no keypair is written; it cannot be
+    combined with a program keypair, whose public key already is the program id.
+    """
+    if options.program_keypair is not None:
+        if options.program_id != PLACEHOLDER_PROGRAM_ID:
+            raise InvalidProgramIdError(
+                "Give either a program id or a program keypair, not both "
+                f"(program id '{options.program_id}', "
+                f"keypair {options.program_keypair})."
             )
-        shutil.rmtree(destination)
+        return _load_program_keypair(options.program_keypair)
+    if options.program_id != PLACEHOLDER_PROGRAM_ID:
+        return None
+    return _generate_keypair()
 
-    program_snake = _normalise_program_name(options.program_name)
-    program_pascal = "".join(part.capitalize() for part in re.split(r"[_\\-\\s]+", program_snake) if part)
-    program_title = program_pascal.replace("_", " ")
-    replacements: Dict[str, str] = {
-        "PROGRAM_NAME_SNAKE": program_snake,
-        "PROGRAM_NAME_PASCAL": program_pascal,
-        "PROGRAM_NAME_TITLE": program_title,
-        "PROGRAM_NAME_RAW": options.program_name,
-        "AUTHOR_PUBKEY": options.author_pubkey,
-        "PROGRAM_ID": options.program_id,
-        "CLUSTER": options.cluster,
-    }
 
-    shutil.copytree(template_dir, destination)
-    _apply_replacements(destination, replacements)
-    _rename_placeholder_paths(destination, replacements)
-    _rename_paths(destination, program_snake)
-    return destination
+def new_program_keypair(path: Path) -> str:
+    """Write a freshly generated program keypair to ``path``; return its program id."""
+    keypair = _generate_keypair()
+    _write_program_keypair(path, keypair)
+    return _public_key(keypair)
+
+
+def _generate_keypair() -> bytes:
+    private = ed25519.Ed25519PrivateKey.generate()
+    private_bytes = private.private_bytes(
+        Encoding.Raw, PrivateFormat.Raw, NoEncryption()
+    )
+    public_bytes = private.public_key().public_bytes(Encoding.Raw, PublicFormat.Raw)
+    return private_bytes + public_bytes
+
 
+def _load_program_keypair(path: Path) -> bytes:
+    try:
+        raw = json.loads(path.expanduser().read_text())
+        data = bytes(int(b) & 0xFF for b in raw)
+    except (OSError, ValueError, TypeError) as exc:
+        raise TemplateError(f"Failed to read program keypair {path}: {exc}") from exc
+    if len(data) != 64:
+        raise TemplateError(f"Program keypair {path} must contain 64 bytes.")
+    return data
+
+
+def _public_key(keypair: bytes) -> str:
+    private = ed25519.Ed25519PrivateKey.from_private_bytes(keypair[:32])
+    return _b58encode(private.public_key().public_bytes(Encoding.Raw, PublicFormat.Raw))
+
+
+def _write_program_keypair(path: Path, keypair: bytes) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(list(keypair)))
+    try:
+        os.chmod(path, 0o600)
+    except PermissionError:
+        pass
 
-def _normalise_program_name(name: str) -> str:
-    base = re.sub(r"[^0-9a-zA-Z]+", "_", name.strip())
-    base = base.lower().strip("_")
-    return base or "counter"
+
+def validate_program_id(program_id: str) -> None:
+    """Ensure ``program_id`` decodes to a 32-byte public key."""
+    value = program_id.strip()
+    invalid = [char for char in value if char not in _BASE58_ALPHABET]
+    if not value or invalid:
+        raise InvalidProgramIdError(
+            f"Program id '{program_id}' is not valid base58"
+            + (f" (unexpected character '{invalid[0]}')." if invalid else ".")
+        )
+    decoded = _b58decode(value)
+    if len(decoded) != 32:
+        raise InvalidProgramIdError(
+            f"Program id '{program_id}' decodes to {len(decoded)} bytes; "
+            "a Solana public key is 32 bytes."
+        )
+
+
+def _b58encode(data: bytes) -> str:
+    num = int.from_bytes(data, "big")
+    encoded = ""
+    while num > 0:
+        num, remainder = divmod(num, 58)
+        encoded = _BASE58_ALPHABET[remainder] + encoded
+    zeros = len(data) - len(data.lstrip(b"\x00"))
+    return ("1" * zeros) + encoded
+
+
+def _b58decode(value: str) -> bytes:
+    num = 0
+    for char in value:
+        num = num * 58 + _BASE58_ALPHABET.index(char)
+    full_bytes = num.to_bytes((num.bit_length() + 7) // 8, "big")
+    zeros = len(value) - len(value.lstrip("1"))
+    return b"\x00" * zeros + full_bytes
+
+
+def _resolve_template_variables(options: RenderOptions) -> Dict[str, str]:
+    try:
+        from solcoder.cli.blueprints import (  # type: ignore
+            load_wizard_schema,
+            resolve_placeholder_values,
+        )
+    except Exception:
+        return {}
+    try:
+        return resolve_placeholder_values(
+            load_wizard_schema(options.template), options.answers
+        )
+    except ValueError as exc:
+        raise TemplateError(str(exc)) from exc
+
+
+def to_pascal_case(name: str) -> str:
+    """Return ``name`` in PascalCase, e.g. ``my-token`` -> ``MyToken``.
+
+    Words come from the snake_case program name, which keeps the result in line
+    with the type names Anchor generates from the IDL.
+    """
+    words = normalise_program_name(name).split("_")
+    return "".join(word.capitalize() for word in words)
+
+
+def to_camel_case(name: str) -> str:
+    """Return ``name`` in camelCase, e.g. ``my-token`` -> ``myToken``."""
+    pascal = to_pascal_case(name)
+    return pascal[:1].lower() + pascal[1:]
+
+
+def _apply_manifest(template_dir: Path, replacements: Dict[str, str]) -> Dict[str, str]:
+    """Check placeholders against the template's solcoder.toml, when it has one."""
+    try:
+        manifest = load_manifest(template_dir)
+        if manifest is None:
+            return replacements
+        return manifest.resolve(replacements, find_placeholders(template_dir))
+    except ManifestError as exc:
+        raise TemplateError(str(exc)) from exc
+
+
+def normalise_program_name(name: str) -> str:
+    """Return ``name`` as a snake_case Rust identifier: ``My-Token`` -> ``my_token``.
+
+    camelCase words are split and other characters become underscores; a name
+    with no letters or digits falls back to ``counter``. Raises
+    InvalidProgramNameError when the result starts with a digit (so purely
+    numeric names can never work) or is a Rust keyword.
+    """
+    base = re.sub(r"(?<=[a-z0-9])(?=[A-Z])", "_", name.strip())
+    base = re.sub(r"[^0-9a-zA-Z]+", "_", base).lower().strip("_")
+    if not base:
+        return "counter"
+    if base[0].isdigit():
+        raise InvalidProgramNameError(
+            f"Program name '{name}' gives PROGRAM_NAME_SNAKE '{base}', "
+            "which starts with a digit; "
+            "Rust module names must start with a letter."
+        )
+    if base in RUST_KEYWORDS:
+        raise InvalidProgramNameError(
+            f"Program name '{name}' gives PROGRAM_NAME_SNAKE '{base}', "
+            f"the Rust keyword `{base}`; "
+            "choose another name."
+        )
+    return base
 
 
 def _apply_replacements(root: Path, replacements: Dict[str, str]) -> None:
@@ -146,6 +886,22 @@ def _rename_paths(root: Path, program_snake: str) -> None:
             source.rename(target)
 
 
+def _rendered_path(
+    relative: Path, replacements: Dict[str, str], program_snake: str
+) -> Path:
+    """Where a template file lands after placeholder and legacy renames."""
+    parts = list(relative.parts)
+    for index, part in enumerate(parts):
+        for key, value in replacements.items():
+            part = part.replace(f"{{{{{key}}}}}", value)
+        parts[index] = part
+    if parts[:2] == ["programs", "counter"]:
+        parts[1] = program_snake
+    if parts == ["tests", "counter.ts"]:
+        parts[1] = f"{program_snake}.ts"
+    return Path(*parts)
+
+
 def _rename_placeholder_paths(root: Path, replacements: Dict[str, str]) -> None:
     """Rename any files or directories whose names contain {{PLACEHOLDER}} tokens.
 
@@ -154,7 +910,9 @@ def _rename_placeholder_paths(root: Path, replacements: Dict[str, str]) -> None:
     """
     # Collect all paths (files and dirs), sort by descending path length so we rename
     # deepest items first to avoid breaking parent traversal.
-    all_paths = sorted((p for p in root.rglob("*")), key=lambda p: len(str(p)), reverse=True)
+    all_paths = sorted(
+        (p for p in root.rglob("*")), key=lambda p: len(str(p)), reverse=True
+    )
     for path in all_paths:
         name = path.name
         if "{{" not in name or "}}" not in name:
@@ -0,0 +1,292 @@
+"""Render several templates into a single multi-program Anchor workspace."""
+
+from __future__ import annotations
+
+import json
+import re
+import shutil
+import tempfile
+from dataclasses import replace
+from pathlib import Path
+
+try:  # Python 3.11+
+    import tomllib  # type: ignore[attr-defined]
+except ModuleNotFoundError:  # pragma: no cover - fallback for older interpreters
+    import tomli as tomllib  # type: ignore[no-redef]
+
+from .templates import (
+    PLACEHOLDER_PROGRAM_ID,
+    PlannedFile,
+    RenderOptions,
+    TemplateError,
+    merge_validator_clones,
+    normalise_program_name,
+    preview_template,
+    program_keypair_path,
+    render_template,
+)
+from .template_readme import merge_instructions_readme
+
+_MEMBERS_PATTERN = re.compile(r"(?s)(members\s*=\s*\[)(.*?)(\])")
+_ERROR_CODE_PATTERN = re.compile(r"#\[error_code\]")
+
+# Anchor numbers custom errors from 6000; namespaced workspaces give each program
+# its own block of ERROR_CODE_RANGE codes above that.
+ERROR_CODE_BASE = 6000
+ERROR_CODE_RANGE = 1000
+
+CLIENT_PATH = Path("app") / "client.ts"
+
+
+def render_workspace(
+    programs: list[RenderOptions],
+    destination: Path,
+    *,
+    overwrite: bool = False,
+    namespace_errors: bool = False,
+) -> Path:
+    """Render ``programs`` into one workspace at ``destination``.
+
+    The first template provides the workspace files (Anchor.toml, Cargo.toml,
+    package.json, ...). Every later one is rendered on its own and merged in:
+    its program crate, tests, scripts and program keypair are copied over, and
+    the shared manifests gain its workspace member, program ids and any
+    missing workspace dependencies. Each render generates its own keypair, so
+    every program ends up with a distinct ``declare_id!``.
+
+    With ``namespace_errors`` each program's ``#[error_code]`` enum is given the
+    offset from :func:`error_code_offsets`, so custom error codes never overlap.
+    """
+    snakes = _check_programs(programs)
+    first, *rest = programs
+    target = destination.expanduser().resolve()
+    existed = target.exists()
+    before = _workspace_entries(target)
+    root = render_template(replace(first, destination=destination, overwrite=overwrite))
+    try:
+        for options, snake in zip(rest, snakes[1:]):
+            with tempfile.TemporaryDirectory() as tmpdir:
+                # Re-rendering over a workspace keeps each program's id, as a single render does.
+                keypair = program_keypair_path(root, snake)
+                if (
+                    options.program_keypair is None
+                    and options.program_id == PLACEHOLDER_PROGRAM_ID
+                    and keypair.exists()
+                ):
+                    options = replace(options, program_keypair=keypair)
+                staged = render_template(replace(options, destination=Path(tmpdir) / snake, overwrite=False))
+                _merge_program(staged, root, snake, overwrite=overwrite)
+        if namespace_errors:
+            for snake, offset in error_code_offsets(programs).items():
+                _offset_error_codes(root / "programs" / snake, offset)
+    except TemplateError:
+        # Do not leave a workspace that is missing some of the requested programs,
+        # but only remove what this call created: the destination may be a project.
+        if not existed:
+            shutil.rmtree(root, ignore_errors=True)
+        else:
+            for path in sorted(_workspace_entries(root) - before, reverse=True):
+                if path.is_dir():
+                    shutil.rmtree(path, ignore_errors=True)
+                else:
+                    path.unlink(missing_ok=True)
+        raise
+    return root
+
+
+def preview_workspace(programs: list[RenderOptions], destination: Path) -> list[PlannedFile]:
+    """Return the files render_workspace would write, without touching the filesystem."""
+    snakes = _check_programs(programs)
+    first, *rest = programs
+    planned = {item.path: item for item in preview_template(replace(first, destination=destination))}
+    for options, snake in zip(rest, snakes[1:]):
+        for item in preview_template(replace(options, destination=destination)):
+            if item.path == CLIENT_PATH and CLIENT_PATH in planned:
+                item = PlannedFile(path=client_path(snake), source=item.source)
+            if _is_program_file(item.path, snake) and item.path not in planned:
+                planned[item.path] = item
+    return sorted(planned.values(), key=lambda item: item.path.as_posix())
+
+
+def error_code_offsets(programs: list[RenderOptions]) -> dict[str, int]:
+    """First custom error code of each program in a namespaced workspace, by crate name.
+
+    Program ``i`` (in ``programs`` order) owns codes ``6000 + i * 1000`` up to the
+    next block, so a code maps back to program ``(code - 6000) // 1000``.
+    """
+    return {
+        snake: ERROR_CODE_BASE + index * ERROR_CODE_RANGE
+        for index, snake in enumerate(_check_programs(programs))
+    }
+
+
+def _offset_error_codes(crate: Path, offset: int) -> None:
+    """Rewrite every plain ``#[error_code]`` in ``crate`` to start at ``offset``."""
+    for path in sorted((crate / "src").rglob("*.rs")):
+        text = path.read_text()
+        if _ERROR_CODE_PATTERN.search(text):
+            path.write_text(_ERROR_CODE_PATTERN.sub(f"#[error_code(offset = {offset})]", text))
+
+
+def _check_programs(programs: list[RenderOptions]) -> list[str]:
+    if not programs:
+        raise TemplateError("A workspace needs at least one program.")
+    snakes = [normalise_program_name(options.program_name) for options in programs]
+    duplicates = sorted({snake for snake in snakes if snakes.count(snake) > 1})
+    if duplicates:
+        raise TemplateError(
+            f"Program names must be unique within a workspace: {', '.join(duplicates)}."
+        )
+    return snakes
+
+
+def _is_program_file(path: Path, snake: str) -> bool:
+    """Files a later program contributes: its crate, keypair, tests and scripts."""
+    parts = path.parts
+    if parts[:2] == ("programs", snake):
+        return True
+    if path in {program_keypair_path(Path(), snake), CLIENT_PATH, client_path(snake)}:
+        return True
+    return len(parts) == 2 and parts[0] in {"tests", "scripts"}
+
+
+def _workspace_entries(root: Path) -> set[Path]:
+    """Top-level entries and program crates of ``root``, to tell what a render added."""
+    if not root.is_dir():
+        return set()
+    entries = set(root.iterdir())
+    if (root / "programs").is_dir():
+        entries.update((root / "programs").iterdir())
+    return entries
+
+
+def _merge_program(staged: Path, root: Path, snake: str, *, overwrite: bool = False) -> None:
+    try:
+        shutil.copytree(
+            staged / "programs" / snake, root / "programs" / snake, dirs_exist_ok=overwrite
+        )
+    except OSError as exc:
+        raise TemplateError(f"Could not add programs/{snake} to {root}: {exc}") from exc
+    keypair = program_keypair_path(staged, snake)
+    if keypair.exists():
+        target = program_keypair_path(root, snake)
+        target.parent.mkdir(parents=True, exist_ok=True)
+        shutil.copy2(keypair, target)
+    for folder in ("tests", "scripts"):
+        for source in sorted((staged / folder).glob("*")):
+            target = root / folder / source.name
+            # Helper files shared between blueprints (e.g. a common script name) keep the first copy.
+            if source.is_file() and not target.exists():
+                target.parent.mkdir(parents=True, exist_ok=True)
+                shutil.copy2(source, target)
+    merge_program_client(staged, root, snake)
+    merge_instructions_readme(staged, root, snake)
+
+    member = f"programs/{snake}"
+    staged_cargo = tomllib.loads((staged / "Cargo.toml").read_text())
+    # Crates nested in the program directory (e.g. its `cpi` client) join the Cargo workspace only.
+    nested = [
+        item
+        for item in staged_cargo.get("workspace", {}).get("members", [])
+        if item.startswith(f"{member}/")
+    ]
+    anchor_toml = root / "Anchor.toml"
+    if anchor_toml.exists():
+        staged_anchor = tomllib.loads((staged / "Anchor.toml").read_text())
+        text = _add_member(anchor_toml.read_text(), member)
+        for cluster, entries in staged_anchor.get("programs", {}).items():
+            if snake in entries:
+                text = _add_table_entry(text, f"programs.{cluster}", f'{snake} = "{entries[snake]}"')
+        anchor_toml.write_text(text)
+        merge_validator_clones(staged / "Anchor.toml", anchor_toml)
+
+    cargo_toml = root / "Cargo.toml"
+    if cargo_toml.exists():
+        text = cargo_toml.read_text()
+        for item in (member, *nested):
+            text = _add_member(text, item)
+        existing = tomllib.loads(text).get("workspace", {}).get("dependencies", {})
+        for name, version in staged_cargo.get("workspace", {}).get("dependencies", {}).items():
+            if name not in existing and isinstance(version, str):
+                text = _add_table_entry(text, "workspace.dependencies", f'{name} = "{version}"')
+        cargo_toml.write_text(text)
+
+    package_json = root / "package.json"
+    staged_package = staged / "package.json"
+    if package_json.exists() and staged_package.exists():
+        package = json.loads(package_json.read_text())
+        for section, deps in json.loads(staged_package.read_text()).items():
+            if section in {"dependencies", "devDependencies"} and isinstance(deps, dict):
+                merged = package.setdefault(section, {})
+                for name, version in deps.items():
+                    merged.setdefault(name, version)
+        package_json.write_text(json.dumps(package, indent=2) + "\n")
+
+
+def client_path(snake: str) -> Path:
+    """Where a program's app/client.ts goes when the workspace already has one."""
+    return Path("app") / f"{snake}_client.ts"
+
+
+def merge_program_client(staged: Path, root: Path, snake: str) -> None:
+    """Copy ``app/client.ts`` from a staged render into ``root``.
+
+    The first program keeps ``app/client.ts``; later ones get ``app/<snake>_client.ts``
+    and their test suite is pointed at it, so every suite still type-checks its own client.
+    """
+    source = staged / CLIENT_PATH
+    if not source.exists():
+        return
+    target = root / CLIENT_PATH
+    if target.exists():
+        target = root / client_path(snake)
+        suite = root / "tests" / f"{snake}.ts"
+        if suite.exists():
+            suite.write_text(
+                suite.read_text().replace('"../app/client"', f'"../app/{snake}_client"')
+            )
+    target.parent.mkdir(parents=True, exist_ok=True)
+    shutil.copy2(source, target)
+
+
+def _add_member(text: str, member: str) -> str:
+    """Append ``member`` to the first ``members = [...]`` array, keeping its indentation."""
+    match = _MEMBERS_PATTERN.search(text)
+    if match is None:
+        return text.rstrip() + f'\n\n[workspace]\nmembers = [\n    "{member}"\n]\n'
+    items = re.findall(r'"([^"]+)"', match.group(2))
+    if member in items:
+        return text
+    indent = re.search(r"\n([ \t]*)\"", match.group(2))
+    prefix = indent.group(1) if indent else "    "
+    body = ",\n".join(f'{prefix}"{item}"' for item in [*items, member])
+    return text[: match.start()] + f"{match.group(1)}\n{body}\n{match.group(3)}" + text[match.end() :]
+
+
+def _add_table_entry(text: str, table: str, line: str) -> str:
+    """Insert ``line`` at the end of ``[table]``, creating the table if needed.
+
+    A line for the same key already in the table is replaced instead.
+    """
+    header = re.search(rf"(?m)^\[{re.escape(table)}\][ \t]*$", text)
+    if header is None:
+        return text.rstrip() + f"\n\n[{table}]\n{line}\n"
+    following = re.search(r"(?m)^\[", text[header.end() :])
+    end = header.end() + following.start() if following else len(text)
+    body = text[header.end() : end].rstrip()
+    key = re.escape(line.split("=", 1)[0].strip())
+    if re.search(rf"(?m)^{key}[ \t]*=", body):
+        body = re.sub(rf"(?m)^{key}[ \t]*=.*$", lambda _: line, body)
+        return text[: header.end()] + f"{body}\n" + ("\n" if following else "") + text[end:]
+    return text[: header.end()] + f"{body}\n{line}\n" + ("\n" if following else "") + text[end:]
+
+
+__all__ = [
+    "ERROR_CODE_BASE",
+    "ERROR_CODE_RANGE",
+    "client_path",
+    "error_code_offsets",
+    "merge_program_client",
+    "preview_workspace",
+    "render_workspace",
+]
@@ -150,6 +150,10 @@ def ensure_program_keypair(project_root: Path, program_name: str) -> tuple[Path,
     key_dir = project_root / ".solcoder" / "keys" / "programs"
     key_dir.mkdir(parents=True, exist_ok=True)
     key_path = key_dir / f"{program_name}.json"
+    scaffolded = project_root / "target" / "deploy" / f"{program_name}-keypair.json"
+    if not key_path.exists() and scaffolded.exists():
+        # Reuse the keypair written at scaffold time so declare_id! stays valid.
+        key_path = scaffolded
 
     if key_path.exists():
         try:
@@ -0,0 +1,114 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+import pytest
+
+from solcoder.cli.blueprints import (
+    WizardInputError,
+    load_blueprint_manifest,
+    load_wizard_schema,
+    prompt_wizard,
+)
+
+
+class _ScriptedApp:
+    """Answers prompts from a fixed script and records what was asked."""
+
+    def __init__(self, responses: list[str]) -> None:
+        self._responses = iter(responses)
+        self.prompts: list[str] = []
+        self.printed: list[str] = []
+        self.console = SimpleNamespace(print=self.printed.append)
+
+    def _prompt_text(self, message: str) -> str:
+        self.prompts.append(message)
+        return next(self._responses)
+
+
+def test_prompts_each_question_with_defaults() -> None:
+    app = _ScriptedApp(["my_registry", "", "", "512"])
+
+    answers = prompt_wizard(
+        app,
+        load_wizard_schema("registry"),
+        {"program_name": "registry"},
+        manifest=load_blueprint_manifest("registry"),
+    )
+
+    assert answers == {
+        "program_name": "my_registry",
+        "key_type": "string",
+        "value_type": "string",
+        "max_value": "512",
+    }
+    assert app.prompts[0] == "Program name [registry]"
+
+
+def test_reprompts_until_the_manifest_accepts_the_value() -> None:
+    # "2fa" passes the wizard pattern but not the manifest's PROGRAM_NAME_SNAKE rule,
+    # and 20000 exceeds REGISTRY_MAX_VALUE's max.
+    app = _ScriptedApp(["2fa", "vault", "", "", "20000", "64"])
+
+    answers = prompt_wizard(
+        app,
+        load_wizard_schema("registry"),
+        {},
+        manifest=load_blueprint_manifest("registry"),
+    )
+
+    assert answers["program_name"] == "vault"
+    assert answers["max_value"] == "64"
+    assert len(app.prompts) == 6
+    assert any("program_name" in line for line in app.printed)
+
+
+def test_falls_back_to_default_after_repeated_invalid_input() -> None:
+    app = _ScriptedApp(["bad!", "bad!", "bad!"])
+    questions = [{"key": "program_name", "prompt": "Program name", "default": "demo", "pattern": "^[a-z]+$"}]
+
+    assert prompt_wizard(app, questions, {}) == {"program_name": "demo"}
+
+
+def test_rejects_a_required_value_after_repeated_invalid_input() -> None:
+    app = _ScriptedApp(["bad!", "", "bad!"])
+    questions = [{"key": "beneficiary", "prompt": "Beneficiary", "pattern": "^[a-z]+$"}]
+
+    with pytest.raises(WizardInputError, match="beneficiary after 3 attempts"):
+        prompt_wizard(app, questions, {})
+    assert len(app.prompts) == 3
+
+
+def test_no_input_uses_flags_and_defaults_without_prompting() -> None:
+    app = _ScriptedApp([])
+
+    answers = prompt_wizard(
+        app,
+        load_wizard_schema("registry"),
+        {"program_name": "ci_registry", "max_value": "1024"},
+        interactive=False,
+        manifest=load_blueprint_manifest("registry"),
+    )
+
+    assert answers["program_name"] == "ci_registry"
+    assert answers["max_value"] == "1024"
+    assert answers["key_type"] == "string"
+    assert app.prompts == []
+
+
+def test_no_input_rejects_missing_required_value() -> None:
+    questions = [{"key": "beneficiary", "prompt": "Beneficiary"}]
+
+    with pytest.raises(WizardInputError, match="--var beneficiary="):
+        prompt_wizard(_ScriptedApp([]), questions, {}, interactive=False)
+
+
+def test_no_input_rejects_invalid_value() -> None:
+    with pytest.raises(WizardInputError, match="max_value"):
+        prompt_wizard(
+            _ScriptedApp([]),
+            load_wizard_schema("registry"),
+            {"max_value": "0"},
+            interactive=False,
+            manifest=load_blueprint_manifest("registry"),
+        )
@@ -6,6 +6,7 @@ from types import SimpleNamespace
 from rich.console import Console
 
 from solcoder.cli.app import CLIApp
+from solcoder.core.templates import read_program_id
 from solcoder.session.manager import SessionManager
 from solcoder.solana.wallet import WalletManager, WalletStatus
 from solcoder.solana.constants import TOKEN_2022_PROGRAM_ID
@@ -59,7 +60,8 @@ def test_new_inserts_program_into_existing_anchor_workspace(tmp_path: Path, monk
     # Anchor.toml patched with programs.devnet entry
     anchor_text = (ws / "Anchor.toml").read_text()
     assert "[programs.devnet]" in anchor_text
-    assert "my_token = \"replace-with-program-id\"" in anchor_text
+    program_id = read_program_id(ws / "target" / "deploy" / "my_token-keypair.json")
+    assert f"my_token = \"{program_id}\"" in anchor_text
     assert 'anchor_version = "0.32.1"' in anchor_text
     # Cargo workspace includes member
     cargo_text = (ws / "Cargo.toml").read_text()
@@ -68,6 +70,25 @@ def test_new_inserts_program_into_existing_anchor_workspace(tmp_path: Path, monk
     assert any(p.name.endswith(".ts") for p in (ws / "tests").glob("*.ts"))
 
 
+def test_new_merge_into_existing_workspace_keeps_program_files(tmp_path: Path, monkeypatch) -> None:
+    app = _make_app(tmp_path)
+    _patch_anchor_version(monkeypatch)
+    monkeypatch.chdir(tmp_path)
+    ws = tmp_path / "ws"
+    ws.mkdir(parents=True, exist_ok=True)
+    (ws / "Anchor.toml").write_text("[workspace]\n\n[provider]\ncluster = \"devnet\"\n")
+    (ws / "Cargo.toml").write_text("[workspace]\nmembers = [\n]\n")
+    app.handle_line(f"/new counter --dir {ws} --program my_counter")
+    lib_rs = ws / "programs" / "my_counter" / "src" / "lib.rs"
+    lib_rs.write_text(lib_rs.read_text() + "\n// local change\n")
+    program_id = read_program_id(ws / "target" / "deploy" / "my_counter-keypair.json")
+
+    app.handle_line(f"/new counter --dir {ws} --program my_counter --merge")
+
+    assert lib_rs.read_text().endswith("// local change\n")
+    assert read_program_id(ws / "target" / "deploy" / "my_counter-keypair.json") == program_id
+
+
 def test_new_scaffolds_workspace_when_no_anchor_detected(tmp_path: Path, monkeypatch) -> None:
     app = _make_app(tmp_path)
     _patch_anchor_version(monkeypatch)
@@ -106,6 +127,50 @@ def test_new_uses_active_workspace_when_no_dir_specified(tmp_path: Path, monkeyp
     assert 'anchor_version = "0.32.1"' in anchor_text
 
 
+def test_new_no_input_scaffolds_from_flags(tmp_path: Path, monkeypatch) -> None:
+    app = _make_app(tmp_path)
+    _patch_anchor_version(monkeypatch)
+    monkeypatch.chdir(tmp_path)
+
+    def _no_prompts(message: str) -> str:
+        raise AssertionError(f"unexpected prompt: {message}")
+
+    app._prompt_text = _no_prompts  # type: ignore[assignment]
+    dest = tmp_path / "ci"
+    app.handle_line(f"/new registry --dir {dest} --program ci_reg --var max_value=128 --no-input --force")
+
+    lib_rs = (dest / "programs" / "ci_reg" / "src" / "lib.rs").read_text()
+    assert "pub const MAX_VALUE: usize = 128;" in lib_rs
+
+
+def test_new_no_input_reports_invalid_variable(tmp_path: Path, monkeypatch) -> None:
+    app = _make_app(tmp_path)
+    _patch_anchor_version(monkeypatch)
+    monkeypatch.chdir(tmp_path)
+    dest = tmp_path / "ci"
+
+    response = app.handle_line(f"/new registry --dir {dest} --var max_value=0 --no-input")
+
+    combined = "\n".join(message for _, message in response.messages)
+    assert "Invalid value for max_value" in combined
+    assert not dest.exists()
+
+
+def test_new_dry_run_lists_files_without_writing(tmp_path: Path, monkeypatch) -> None:
+    app = _make_app(tmp_path)
+    _patch_anchor_version(monkeypatch)
+    monkeypatch.chdir(tmp_path)
+    dest = tmp_path / "preview"
+
+    response = app.handle_line(f"/new escrow --dir {dest} --program my_escrow --no-input --dry-run")
+
+    combined = "\n".join(message for _, message in response.messages)
+    assert "nothing written" in combined
+    assert "+ programs/my_escrow/src/lib.rs" in combined
+    assert "+ tests/my_escrow.ts" in combined
+    assert not dest.exists()
+
+
 def test_new_token_quick_flow(monkeypatch, tmp_path: Path) -> None:
     app = _make_app(tmp_path)
     _patch_anchor_version(monkeypatch)
@@ -0,0 +1,83 @@
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.account_space import (
+    SpaceError,
+    account_space,
+    diagnose_account_space,
+    program_constants,
+    rent_exempt_lamports,
+    rent_summary,
+)
+from solcoder.core.template_manifest import AccountField, AccountLayout, load_manifest
+from solcoder.core.templates import RenderOptions, render_template, resolve_template_dir
+
+
+def _render(tmp_path: Path, template: str, **answers: str) -> tuple[Path, Path]:
+    options = RenderOptions(
+        template=template, destination=tmp_path / template, program_name="demo", answers=answers
+    )
+    return resolve_template_dir(options), render_template(options)
+
+
+def _lib_rs(root: Path) -> Path:
+    return root / "programs" / "demo" / "src" / "lib.rs"
+
+
+def test_counter_layout_matches_generated_space(tmp_path: Path) -> None:
+    template_dir, root = _render(tmp_path, "counter")
+    layout = load_manifest(template_dir).accounts["Counter"]
+    constants = program_constants(_lib_rs(root).read_text())["Counter"]
+
+    # discriminator + authority, count, min, max + label + bump, saturating, paused + 5 delegates
+    # + last_updated + transfer_delay, pending_authority, transfer_eligible_at
+    assert account_space(layout, constants) == 8 + 32 + 24 + (4 + 32) + 3 + (4 + 5 * 32) + 8 + 8 + 33 + 8
+    assert constants["SIZE"] == account_space(layout, constants) - 8
+    assert diagnose_account_space(template_dir, root) == []
+
+
+def test_rent_summary_reports_each_account_size(tmp_path: Path) -> None:
+    template_dir, root = _render(tmp_path, "counter")
+
+    summary = {item.name: item for item in rent_summary(template_dir, root)}
+
+    # The counter has grown past `authority` + `count` (8 + 40): it is 8 + Counter::SIZE.
+    counter = summary["Counter"]
+    assert counter.size == 8 + 32 + 24 + (4 + 32) + 3 + (4 + 5 * 32) + 8 + 8 + 33 + 8 == 324
+    assert counter.lamports == rent_exempt_lamports(324) == (128 + 324) * 3480 * 2
+
+
+@pytest.mark.parametrize("max_value", ["256", "1024"])
+def test_registry_layout_follows_max_value(tmp_path: Path, max_value: str) -> None:
+    template_dir, root = _render(tmp_path, "registry", max_value=max_value)
+    layout = load_manifest(template_dir).accounts["Record"]
+    constants = program_constants(_lib_rs(root).read_text())["Record"]
+
+    assert constants["MAX_VALUE"] == int(max_value)
+    assert account_space(layout, constants) == 8 + 32 + (4 + 64) + (4 + int(max_value)) + 1 + (4 + 32) + 25
+    assert diagnose_account_space(template_dir, root) == []
+
+
+def test_diagnostic_flags_a_size_that_drifted_from_the_layout(tmp_path: Path) -> None:
+    template_dir, root = _render(tmp_path, "counter")
+    lib_rs = _lib_rs(root)
+    source = lib_rs.read_text()
+    # Simulate adding a field to the struct without updating the SIZE arithmetic.
+    lib_rs.write_text(source.replace("+ (1 + 32)\n        + 8;", "+ (1 + 32);"))
+
+    warnings = diagnose_account_space(template_dir, root)
+
+    attribute_line = source[: source.index("#[account(\n        init,")].count("\n") + 1
+    assert len(warnings) == 1
+    assert warnings[0].startswith(
+        f"programs/demo/src/lib.rs:{attribute_line}: `counter` allocates 316 bytes"
+    )
+    assert "needs 324" in warnings[0]
+
+
+def test_unbounded_vec_needs_max_len() -> None:
+    layout = AccountLayout(name="Bag", fields=[AccountField(name="items", type="Vec<u64>")])
+
+    with pytest.raises(SpaceError, match="needs max_len"):
+        account_space(layout)
@@ -0,0 +1,67 @@
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.program_ids import check_program_ids, program_id_references
+from solcoder.core.templates import RenderOptions, program_keypair_path, read_program_id, render_template
+from solcoder.core.workspace_templates import render_workspace
+
+OTHER_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+
+@pytest.mark.parametrize(
+    "template", ["counter", "token", "nft", "registry", "escrow", "staking", "multisig", "vesting", "voting"]
+)
+def test_scaffolded_program_ids_agree(tmp_path: Path, template: str) -> None:
+    destination = tmp_path / template
+    render_template(RenderOptions(template=template, destination=destination, program_name="my_app"))
+
+    assert check_program_ids(destination) == []
+    locations = [reference.location for reference in program_id_references(destination)["my_app"]]
+    assert locations[0].startswith("programs/my_app/src/lib.rs:")
+    assert "Anchor.toml [programs.localnet]" in locations
+    assert "target/deploy/my_app-keypair.json" in locations
+    assert any(location.startswith("migrations/deploy.ts:") for location in locations)
+
+
+def test_mismatched_anchor_toml_id_is_reported(tmp_path: Path) -> None:
+    destination = tmp_path / "counter"
+    render_template(RenderOptions(template="counter", destination=destination, program_name="my_app"))
+    program_id = read_program_id(program_keypair_path(destination, "my_app"))
+    anchor_toml = destination / "Anchor.toml"
+    anchor_toml.write_text(
+        anchor_toml.read_text().replace(f'my_app = "{program_id}"', f'my_app = "{OTHER_ID}"', 1)
+    )
+
+    mismatches = check_program_ids(destination)
+
+    assert len(mismatches) == 1
+    mismatch = mismatches[0]
+    assert mismatch.reference.location == "Anchor.toml [programs.localnet]"
+    assert mismatch.reference.value == OTHER_ID
+    assert mismatch.expected.value == program_id
+    assert str(mismatch) == (
+        f"Anchor.toml [programs.localnet]: my_app is {OTHER_ID}, "
+        f"but {mismatch.expected.location} has {program_id}"
+    )
+
+
+def test_workspace_ids_are_checked_per_program(tmp_path: Path) -> None:
+    destination = tmp_path / "ws"
+    render_workspace(
+        [
+            RenderOptions(template="counter", destination=destination, program_name="counter"),
+            RenderOptions(template="escrow", destination=destination, program_name="my_escrow"),
+        ],
+        destination,
+    )
+    assert check_program_ids(destination) == []
+
+    escrow_id = read_program_id(program_keypair_path(destination, "my_escrow"))
+    program_keypair_path(destination, "my_escrow").unlink()
+    lib_rs = destination / "programs" / "my_escrow" / "src" / "lib.rs"
+    lib_rs.write_text(lib_rs.read_text().replace(escrow_id, OTHER_ID))
+
+    mismatches = check_program_ids(destination)
+    assert {mismatch.reference.program for mismatch in mismatches} == {"my_escrow"}
+    assert all(mismatch.reference.value == escrow_id for mismatch in mismatches)
@@ -0,0 +1,94 @@
+import re
+import tomllib
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.account_space import diagnose_account_space
+from solcoder.core.program_rename import rename_program
+from solcoder.core.templates import (
+    RenderOptions,
+    TemplateError,
+    find_unrendered_placeholders,
+    program_keypair_path,
+    read_program_id,
+    render_template,
+    resolve_template_dir,
+)
+
+
+def _declared_id(root: Path, snake: str) -> str:
+    lib_rs = (root / "programs" / snake / "src" / "lib.rs").read_text()
+    return re.search(r'declare_id!\("([^"]+)"\)', lib_rs).group(1)
+
+
+def _assert_consistent(root: Path, snake: str, pascal: str, camel: str) -> None:
+    """What `anchor build` and `anchor test` rely on: members, crate, ids and client names agree."""
+    cargo = tomllib.loads((root / "Cargo.toml").read_text())
+    for member in cargo["workspace"]["members"]:
+        assert (root / member / "Cargo.toml").exists(), member
+    anchor = tomllib.loads((root / "Anchor.toml").read_text())
+    assert anchor["workspace"]["members"] == [f"programs/{snake}"]
+    program_id = read_program_id(program_keypair_path(root, snake))
+    assert anchor["programs"]["localnet"] == {snake: program_id}
+    assert _declared_id(root, snake) == program_id
+    crate = tomllib.loads((root / "programs" / snake / "Cargo.toml").read_text())
+    assert crate["package"]["name"] == crate["lib"]["name"] == snake
+    assert f"pub mod {snake} {{" in (root / "programs" / snake / "src" / "lib.rs").read_text()
+    suite = (root / "tests" / f"{snake}.ts").read_text()
+    assert f'import {{ {pascal} }} from "../target/types/{snake}";' in suite
+    assert f"anchor.workspace.{pascal} as Program<{pascal}>" in suite
+    assert f"export function {camel}Client(" in (root / "app" / "client.ts").read_text()
+    assert find_unrendered_placeholders(root) == []
+
+
+def test_rename_updates_every_reference_to_the_program(tmp_path: Path) -> None:
+    options = RenderOptions(template="counter", destination=tmp_path / "ws", program_name="zeta_qux")
+    root = render_template(options)
+    program_id = read_program_id(program_keypair_path(root, "zeta_qux"))
+
+    result = rename_program(root, "zeta_qux", "TallyBook")
+
+    assert result.new == "tally_book"
+    assert "programs/tally_book/src/lib.rs" in result.changed
+    assert "tests/tally_book.ts" in result.changed
+    assert not (root / "programs" / "zeta_qux").exists()
+    _assert_consistent(root, "tally_book", "TallyBook", "tallyBook")
+    assert read_program_id(program_keypair_path(root, "tally_book")) == program_id
+    crate = tomllib.loads((root / "programs" / "tally_book" / "cpi" / "Cargo.toml").read_text())
+    assert crate["package"]["name"] == "tally_book-cpi"
+    assert crate["lib"]["name"] == "tally_book_cpi"
+    for path in root.rglob("*"):
+        if path.is_file() and "target" not in path.relative_to(root).parts:
+            assert not re.search(r"zeta_qux|ZetaQux|zetaQux|Zeta Qux", path.read_text()), path
+    rendered = options.__class__(template="counter", destination=root, program_name="tally_book")
+    assert diagnose_account_space(resolve_template_dir(rendered), root) == []
+
+
+def test_rename_leaves_names_that_only_coincide_with_the_program(tmp_path: Path) -> None:
+    # The default program name matches the counter's seed and account type.
+    root = render_template(RenderOptions(template="counter", destination=tmp_path / "ws"))
+    before = (root / "programs" / "counter" / "src" / "lib.rs").read_text()
+
+    rename_program(root, "counter", "tally", new_keypair=True)
+
+    lib_rs = (root / "programs" / "tally" / "src" / "lib.rs").read_text()
+    assert "pub mod tally {" in lib_rs
+    assert lib_rs.count('b"counter"') == before.count('b"counter"') > 0
+    assert lib_rs.count("pub struct Counter {") == 1
+    assert "program.account.counter" in (root / "tests" / "tally.ts").read_text()
+    _assert_consistent(root, "tally", "Tally", "tally")
+    # The old keypair stays for the existing deployment; nothing references its id any more.
+    old_id = read_program_id(program_keypair_path(root, "counter"))
+    assert _declared_id(root, "tally") != old_id
+    assert old_id not in (root / "Anchor.toml").read_text()
+
+
+def test_rename_refuses_an_existing_or_missing_program(tmp_path: Path) -> None:
+    root = render_template(RenderOptions(template="counter", destination=tmp_path / "ws", program_name="demo"))
+
+    with pytest.raises(TemplateError, match="No program 'other'"):
+        rename_program(root, "other", "next")
+    (root / "programs" / "taken").mkdir()
+    with pytest.raises(TemplateError, match="'taken' already exists"):
+        rename_program(root, "demo", "taken")
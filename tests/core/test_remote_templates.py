@@ -0,0 +1,113 @@
+import subprocess
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.remote_templates import RemoteTemplateError, fetch_git_template, parse_git_template
+from solcoder.core.templates import RenderOptions, render_template
+
+
+def _git(cwd: Path, *args: str) -> str:
+    return subprocess.run(
+        ["git", "-c", "user.name=test", "-c", "user.email=test@example.com", *args],
+        cwd=cwd,
+        check=True,
+        capture_output=True,
+        text=True,
+    ).stdout.strip()
+
+
+def _publish(
+    tmp_path: Path, files: dict[str, str], links: dict[str, str] | None = None
+) -> tuple[Path, str]:
+    """Push ``files`` (and symlinks ``links``) to a local bare repository on branch main.
+
+    Returns the repository and the commit.
+    """
+    work = tmp_path / "work"
+    work.mkdir()
+    _git(work, "init", "--quiet", "--initial-branch", "main")
+    for name, content in files.items():
+        path = work / name
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+    for name, target in (links or {}).items():
+        (work / name).symlink_to(target)
+    _git(work, "add", "-A")
+    _git(work, "commit", "--quiet", "-m", "template")
+    bare = tmp_path / "remote.git"
+    _git(tmp_path, "clone", "--quiet", "--bare", str(work), str(bare))
+    return bare, _git(work, "rev-parse", "HEAD")
+
+
+_TEMPLATE = {
+    "solcoder.toml": '[variables.PROGRAM_NAME_SNAKE]\npattern = "^[a-z][a-z0-9_]*$"\n\n[variables.PROGRAM_ID]\n',
+    "template/Anchor.toml": '[programs.localnet]\n{{PROGRAM_NAME_SNAKE}} = "{{PROGRAM_ID}}"\n',
+    "template/programs/{{PROGRAM_NAME_SNAKE}}/src/lib.rs": 'declare_id!("{{PROGRAM_ID}}");\n',
+}
+
+
+def test_parse_requires_a_ref() -> None:
+    assert parse_git_template("git+https://example.com/t.git#v1").ref == "v1"
+    with pytest.raises(RemoteTemplateError, match="#<ref>"):
+        parse_git_template("git+https://example.com/t.git")
+
+
+def test_fetch_and_render_remote_template(tmp_path: Path) -> None:
+    bare, _ = _publish(tmp_path, _TEMPLATE)
+    cache = tmp_path / "cache"
+
+    template_dir = fetch_git_template(f"git+file://{bare}#main", cache)
+    output = render_template(
+        RenderOptions(
+            template="remote",
+            destination=tmp_path / "out",
+            program_name="shared",
+            template_path=template_dir,
+        )
+    )
+
+    lib_rs = (output / "programs" / "shared" / "src" / "lib.rs").read_text()
+    assert "{{" not in lib_rs
+    assert "shared = " in (output / "Anchor.toml").read_text()
+    # A second fetch reuses the cached clone.
+    assert fetch_git_template(f"git+file://{bare}#main", cache) == template_dir
+    assert len(list(cache.iterdir())) == 1
+
+
+def test_fetch_pins_commit_refs(tmp_path: Path) -> None:
+    bare, commit = _publish(tmp_path, _TEMPLATE)
+
+    template_dir = fetch_git_template(f"git+file://{bare}#{commit}", tmp_path / "cache")
+
+    assert _git(template_dir.parent, "rev-parse", "HEAD") == commit
+
+
+def test_fetch_rejects_unknown_ref(tmp_path: Path) -> None:
+    bare, _ = _publish(tmp_path, _TEMPLATE)
+    with pytest.raises(RemoteTemplateError, match="fetch failed"):
+        fetch_git_template(f"git+file://{bare}#no-such-branch", tmp_path / "cache")
+
+
+def test_fetch_requires_manifest(tmp_path: Path) -> None:
+    files = {name: content for name, content in _TEMPLATE.items() if name != "solcoder.toml"}
+    bare, _ = _publish(tmp_path, files)
+    with pytest.raises(RemoteTemplateError, match="solcoder.toml"):
+        fetch_git_template(f"git+file://{bare}#main", tmp_path / "cache")
+
+
+def test_fetch_rejects_symlinks_outside_the_repository(tmp_path: Path) -> None:
+    secret = tmp_path / "secret.txt"
+    secret.write_text("do not copy\n")
+    bare, _ = _publish(tmp_path, _TEMPLATE, links={"template/notes.txt": str(secret)})
+
+    with pytest.raises(RemoteTemplateError, match="symlink.*template/notes.txt"):
+        fetch_git_template(f"git+file://{bare}#main", tmp_path / "cache")
+
+
+def test_fetch_allows_symlinks_within_the_repository(tmp_path: Path) -> None:
+    bare, _ = _publish(tmp_path, _TEMPLATE, links={"template/manifest.toml": "../solcoder.toml"})
+
+    template_dir = fetch_git_template(f"git+file://{bare}#main", tmp_path / "cache")
+
+    assert (template_dir / "manifest.toml").is_symlink()
@@ -0,0 +1,67 @@
+from pathlib import Path
+
+import pytest
+
+from solcoder.cli.blueprints import load_registry, resolve_registry_template_path
+from solcoder.core.template_manifest import find_placeholders, load_manifest
+from solcoder.core.templates import RenderOptions, TemplateError, render_template
+
+
+def _write_template(root: Path, manifest: str, anchor_toml: str) -> Path:
+    template = root / "custom" / "template"
+    template.mkdir(parents=True)
+    (template / "Anchor.toml").write_text(anchor_toml)
+    (root / "custom" / "solcoder.toml").write_text(manifest)
+    return template
+
+
+def _render(template: Path, destination: Path, program_name: str = "demo") -> Path:
+    return render_template(
+        RenderOptions(
+            template="custom",
+            destination=destination,
+            program_name=program_name,
+            template_path=template,
+        )
+    )
+
+
+def test_every_blueprint_declares_the_placeholders_it_uses() -> None:
+    for entry in load_registry():
+        template = resolve_registry_template_path(entry.template_path)
+        assert template is not None
+        manifest = load_manifest(template)
+        assert manifest is not None, f"{entry.key} has no solcoder.toml"
+        assert find_placeholders(template) <= manifest.variables.keys(), entry.key
+
+
+def test_undeclared_placeholder_aborts_before_writing(tmp_path: Path) -> None:
+    template = _write_template(
+        tmp_path,
+        '[variables.PROGRAM_NAME_SNAKE]\ndescription = "name"\n',
+        "name = \"{{PROGRAM_NAME_SNAKE}}\"\nsize = {{MYSTERY_SIZE}}\n",
+    )
+    destination = tmp_path / "out"
+
+    with pytest.raises(TemplateError, match="MYSTERY_SIZE"):
+        _render(template, destination)
+    assert not destination.exists()
+
+
+def test_manifest_default_fills_template_variable(tmp_path: Path) -> None:
+    template = _write_template(
+        tmp_path,
+        '[variables.GREETING]\ndefault = "gm"\n',
+        'greeting = "{{GREETING}}"\n',
+    )
+
+    output = _render(template, tmp_path / "out")
+
+    assert 'greeting = "gm"' in (output / "Anchor.toml").read_text()
+
+
+def test_manifest_pattern_rejects_program_name(tmp_path: Path) -> None:
+    destination = tmp_path / "out"
+    with pytest.raises(TemplateError, match="PROGRAM_NAME_SNAKE"):
+        render_template(RenderOptions(template="counter", destination=destination, program_name="2fa"))
+    assert not destination.exists()
@@ -0,0 +1,99 @@
+import re
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.template_readme import program_instructions
+from solcoder.core.templates import RenderOptions, program_keypair_path, read_program_id, render_template
+from solcoder.core.workspace_templates import render_workspace
+
+TEMPLATES = ["counter", "token", "nft", "registry", "escrow", "staking", "multisig", "vesting", "voting"]
+
+SOURCE = '''
+#[program]
+pub mod demo {
+    use super::*;
+
+    /// Stores `value`.
+    /// Fails when paused.
+    pub fn set_value(ctx: Context<SetValue>, value: u64, _note: String) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetValue<'info> {
+    #[account(mut, seeds = [b"value", authority.key().as_ref()], bump)]
+    pub value: Account<'info, Value>,
+    pub authority: Signer<'info>,
+    pub history: Option<Account<'info, History>>,
+    pub system_program: Program<'info, System>,
+}
+'''
+
+
+def test_program_instructions_reads_arguments_accounts_and_docs() -> None:
+    [instruction] = program_instructions(SOURCE)
+
+    assert instruction.name == "set_value"
+    assert instruction.args == [("value", "u64"), ("note", "String")]
+    assert [account.name for account in instruction.accounts] == [
+        "value",
+        "authority",
+        "history",
+        "system_program",
+    ]
+    assert [account.name for account in instruction.accounts if account.signer] == ["authority"]
+    assert [account.name for account in instruction.accounts if account.optional] == ["history"]
+    assert instruction.doc == "Stores `value`. Fails when paused."
+
+
+@pytest.mark.parametrize("template", TEMPLATES)
+def test_readme_documents_every_public_instruction(tmp_path: Path, template: str) -> None:
+    destination = render_template(
+        RenderOptions(template=template, destination=tmp_path / "out", program_name="demo_app")
+    )
+
+    readme = (destination / "README.md").read_text()
+    lib_rs = (destination / "programs" / "demo_app" / "src" / "lib.rs").read_text()
+    names = [instruction.name for instruction in program_instructions(lib_rs)]
+    assert names
+    for name in names:
+        camel = re.sub(r"_(\w)", lambda match: match.group(1).upper(), name)
+        assert f"### `{name}`" in readme
+        assert f".{camel}(" in readme
+    assert read_program_id(program_keypair_path(destination, "demo_app")) in readme
+    assert "anchor.workspace.DemoApp as Program<DemoApp>" in readme
+    assert "<!-- solcoder:instructions -->" not in readme
+
+
+def test_counter_readme_keeps_its_overview_around_the_generated_section(tmp_path: Path) -> None:
+    destination = render_template(
+        RenderOptions(
+            template="counter",
+            destination=tmp_path / "out",
+            program_name="demo",
+            program_id="Demo111111111111111111111111111111111111111",
+        )
+    )
+
+    readme = (destination / "README.md").read_text()
+    assert readme.index("## Features") < readme.index("## `demo` instructions") < readme.index("## Build")
+    assert "// program.programId is Demo111111111111111111111111111111111111111" in readme
+    assert ".initialize(label, min, max, saturating, transferDelay)" in readme
+    assert ".accountsPartial({ counter, authority })" in readme
+
+
+def test_workspace_readme_documents_each_program(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    programs = [
+        RenderOptions(template="counter", destination=destination, program_name="my_counter"),
+        RenderOptions(template="escrow", destination=destination, program_name="my_escrow"),
+    ]
+
+    render_workspace(programs, destination)
+
+    readme = (destination / "README.md").read_text()
+    assert "## `my_counter` instructions" in readme
+    assert "## `my_escrow` instructions" in readme
+    assert ".initEscrow(" in readme
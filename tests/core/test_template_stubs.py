@@ -0,0 +1,63 @@
+import shutil
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.template_stubs import find_stub_instructions, stub_instructions
+from solcoder.core.templates import list_templates
+
+BLUEPRINTS = Path(__file__).resolve().parents[2] / "src" / "solcoder" / "anchor" / "blueprints"
+
+
+def _stubbed_escrow(tmp_path: Path) -> Path:
+    """Copy of the escrow blueprint with `cancel` reduced to an empty body."""
+    shutil.copytree(BLUEPRINTS / "escrow", tmp_path / "escrow")
+    template_dir = tmp_path / "escrow" / "template"
+    lib_rs = next(template_dir.glob("programs/*/src/lib.rs"))
+    source = lib_rs.read_text()
+    start = source.index("    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {")
+    end = source.index("    pub fn reclaim_expired(")
+    stub = "    pub fn cancel(_ctx: Context<Cancel>) -> Result<()> {\n        // TODO\n        Ok(())\n    }\n\n"
+    lib_rs.write_text(source[:start] + stub + source[end:])
+    return template_dir
+
+
+def test_escrow_with_an_empty_instruction_is_flagged(tmp_path: Path) -> None:
+    template_dir = _stubbed_escrow(tmp_path)
+
+    stubs = stub_instructions(template_dir)
+
+    assert [(stub.path, stub.name) for stub in stubs] == [
+        ("programs/{{PROGRAM_NAME_SNAKE}}/src/lib.rs", "cancel")
+    ]
+    lines = next(template_dir.glob("programs/*/src/lib.rs")).read_text().splitlines()
+    assert "pub fn cancel(" in lines[stubs[0].line - 1]
+
+
+def test_manifest_noop_annotation_marks_a_stub_intentional(tmp_path: Path) -> None:
+    template_dir = _stubbed_escrow(tmp_path)
+    manifest = tmp_path / "escrow" / "solcoder.toml"
+    manifest.write_text(manifest.read_text() + '\n[instructions]\nnoop = ["cancel"]\n')
+
+    assert stub_instructions(template_dir) == []
+
+
+@pytest.mark.parametrize(
+    "body",
+    ["Ok(())", 'msg!("not yet");\n        Ok(())', "let _ = amount;\n        todo!()", "unimplemented!()"],
+)
+def test_bodies_that_do_nothing_are_stubs(body: str) -> None:
+    source = (
+        "#[program]\npub mod demo {\n    use super::*;\n\n"
+        f"    pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {{\n        {body}\n    }}\n\n"
+        "    pub fn close(ctx: Context<Close>) -> Result<()> {\n"
+        "        ctx.accounts.vault.closed = true;\n        Ok(())\n    }\n}\n"
+    )
+
+    assert find_stub_instructions(source) == [(5, "settle")]
+
+
+def test_bundled_templates_implement_every_instruction() -> None:
+    for info in list_templates():
+        if info.complete:
+            assert stub_instructions(info.path) == [], info.key
@@ -0,0 +1,41 @@
+from pathlib import Path
+
+from solcoder.core import template_verify
+from solcoder.solana.deploy import CommandResult
+
+RUSTC_FAILURE = """\
+   Compiling demo v0.1.0 (/tmp/demo/programs/demo)
+error[E0425]: cannot find value `UNMAPPED` in this scope
+ --> programs/demo/src/lib.rs:7:23
+  |
+7 | const LIMIT: usize = UNMAPPED;
+  |                      ^^^^^^^^ not found in this scope
+error[E0308]: mismatched types
+  --> programs/demo/src/lib.rs:7:23
+error: could not compile `demo` (lib) due to 2 previous errors
+"""
+
+
+def test_verify_build_reports_offending_files(tmp_path: Path, monkeypatch) -> None:
+    calls = []
+
+    def fake_run(command, *, cwd, timeout):
+        calls.append((command, cwd))
+        return CommandResult(
+            command=command,
+            cwd=cwd,
+            duration_secs=0.1,
+            returncode=101,
+            stdout="",
+            stderr=RUSTC_FAILURE,
+        )
+
+    monkeypatch.setattr(template_verify.shutil, "which", lambda name: None)
+    monkeypatch.setattr(template_verify, "run_anchor_command", fake_run)
+
+    check = template_verify.verify_build(tmp_path)
+
+    assert calls == [(["cargo", "build"], tmp_path)]
+    assert not check.ok
+    assert check.locations == ["programs/demo/src/lib.rs:7"]
+    assert "could not compile" in check.output
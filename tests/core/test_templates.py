@@ -1,9 +1,30 @@
 import json
+import re
+import tomllib
 from pathlib import Path
 
 import pytest
 
-from solcoder.core.templates import RenderOptions, TemplateExistsError, available_templates, render_template
+from solcoder.core.templates import (
+    GITIGNORE_ENTRIES,
+    InvalidProgramIdError,
+    InvalidProgramNameError,
+    RenderOptions,
+    TemplateError,
+    TemplateExistsError,
+    UnrenderedPlaceholderError,
+    anchor_version_warning,
+    available_templates,
+    ensure_gitignore,
+    list_templates,
+    normalise_program_name,
+    preview_template,
+    program_keypair_path,
+    read_program_id,
+    render_template,
+    to_camel_case,
+    to_pascal_case,
+)
 
 
 def test_available_templates_includes_counter() -> None:
@@ -16,8 +37,8 @@ def test_render_template_writes_placeholder_values(tmp_path: Path) -> None:
         template="counter",
         destination=destination,
         program_name="demo_counter",
-        author_pubkey="Auth1111111111111111111111111111111111",
-        program_id="Demo1111111111111111111111111111111111",
+        author_pubkey="Auth111111111111111111111111111111111111111",
+        program_id="Demo111111111111111111111111111111111111111",
         cluster="devnet",
     )
 
@@ -25,7 +46,7 @@ def test_render_template_writes_placeholder_values(tmp_path: Path) -> None:
 
     anchor_toml = (destination / "Anchor.toml").read_text()
     assert "demo_counter" in anchor_toml
-    assert "Demo1111111111111111111111111111111111" in anchor_toml
+    assert "Demo111111111111111111111111111111111111111" in anchor_toml
 
     lib_rs = (destination / "programs" / "demo_counter" / "src" / "lib.rs").read_text()
     assert "demo_counter" in lib_rs
@@ -34,6 +55,166 @@ def test_render_template_writes_placeholder_values(tmp_path: Path) -> None:
     assert test_ts.exists()
 
 
+@pytest.mark.parametrize(
+    "program_id",
+    ["Demo1111111111111111111111111111111111", "Demo0OIl111111111111111111111111111111111", ""],
+)
+def test_render_template_rejects_invalid_program_id(tmp_path: Path, program_id: str) -> None:
+    destination = tmp_path / "demo"
+    options = RenderOptions(template="counter", destination=destination, program_id=program_id)
+
+    with pytest.raises(InvalidProgramIdError):
+        render_template(options)
+    assert not destination.exists()
+
+
+def test_render_template_generates_program_keypair(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    render_template(RenderOptions(template="counter", destination=destination, program_name="demo"))
+
+    keypair = destination / "target" / "deploy" / "demo-keypair.json"
+    assert keypair.exists()
+    assert len(json.loads(keypair.read_text())) == 64
+    program_id = read_program_id(keypair)
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    assert f'declare_id!("{program_id}");' in lib_rs
+    assert f'demo = "{program_id}"' in (destination / "Anchor.toml").read_text()
+    assert "replace-with-program-id" not in lib_rs
+
+
+def test_render_template_uses_supplied_program_keypair(tmp_path: Path) -> None:
+    first = render_template(
+        RenderOptions(template="counter", destination=tmp_path / "first", program_name="demo")
+    )
+    supplied = program_keypair_path(first, "demo")
+
+    destination = tmp_path / "second"
+    render_template(
+        RenderOptions(
+            template="counter",
+            destination=destination,
+            program_name="demo",
+            program_keypair=supplied,
+        )
+    )
+
+    program_id = read_program_id(supplied)
+    assert read_program_id(program_keypair_path(destination, "demo")) == program_id
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    assert f'declare_id!("{program_id}");' in lib_rs
+
+
+def test_render_template_with_fixed_program_id_writes_no_keypair(tmp_path: Path) -> None:
+    program_id = "Demo111111111111111111111111111111111111111"
+    destination = tmp_path / "demo"
+
+    render_template(
+        RenderOptions(template="counter", destination=destination, program_name="demo", program_id=program_id)
+    )
+
+    assert not program_keypair_path(destination, "demo").exists()
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    assert f'declare_id!("{program_id}");' in lib_rs
+    cpi_rs = (destination / "programs" / "demo" / "cpi" / "src" / "lib.rs").read_text()
+    assert f'pubkey!("{program_id}")' in cpi_rs
+    anchor = tomllib.loads((destination / "Anchor.toml").read_text())
+    assert {programs["demo"] for programs in anchor["programs"].values()} == {program_id}
+
+
+def test_render_template_rejects_program_id_with_keypair(tmp_path: Path) -> None:
+    first = render_template(
+        RenderOptions(template="counter", destination=tmp_path / "first", program_name="demo")
+    )
+    supplied = program_keypair_path(first, "demo")
+
+    destination = tmp_path / "second"
+    with pytest.raises(InvalidProgramIdError, match="not both"):
+        render_template(
+            RenderOptions(
+                template="counter",
+                destination=destination,
+                program_name="demo",
+                program_id=read_program_id(supplied),
+                program_keypair=supplied,
+            )
+        )
+    assert not destination.exists()
+
+
+def test_anchor_toml_program_id_matches_declare_id(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+
+    render_template(RenderOptions(template="counter", destination=destination, program_name="demo"))
+
+    anchor = tomllib.loads((destination / "Anchor.toml").read_text())
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    declared = re.search(r'declare_id!\("([^"]+)"\)', lib_rs)
+    assert declared is not None
+    assert anchor["programs"]["localnet"]["demo"] == declared.group(1)
+    assert read_program_id(program_keypair_path(destination, "demo")) == declared.group(1)
+    assert anchor["provider"] == {"cluster": "localnet", "wallet": "~/.config/solana/id.json"}
+
+
+def test_nft_anchor_toml_clones_the_manifest_accounts(tmp_path: Path) -> None:
+    destination = render_template(
+        RenderOptions(template="nft", destination=tmp_path / "demo", program_name="demo")
+    )
+
+    validator = tomllib.loads((destination / "Anchor.toml").read_text())["test"]["validator"]
+    assert validator["url"] == "https://api.mainnet-beta.solana.com"
+    assert validator["clone"] == [{"address": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"}]
+    counter = render_template(RenderOptions(template="counter", destination=tmp_path / "counter"))
+    assert "test" not in tomllib.loads((counter / "Anchor.toml").read_text())
+
+
+def test_init_if_needed_feature_follows_the_manifest(tmp_path: Path) -> None:
+    registry = render_template(
+        RenderOptions(template="registry", destination=tmp_path / "registry", program_name="demo")
+    )
+    counter = render_template(
+        RenderOptions(template="counter", destination=tmp_path / "counter", program_name="demo")
+    )
+
+    registry_deps = tomllib.loads((registry / "programs" / "demo" / "Cargo.toml").read_text())["dependencies"]
+    assert registry_deps["anchor-lang"] == {"workspace": True, "features": ["init-if-needed"]}
+    counter_deps = tomllib.loads((counter / "programs" / "demo" / "Cargo.toml").read_text())["dependencies"]
+    assert counter_deps["anchor-lang"] == {"workspace": True}
+
+
+@pytest.mark.parametrize(
+    "template", ["counter", "token", "nft", "registry", "escrow", "staking", "multisig", "vesting", "voting"]
+)
+def test_init_if_needed_feature_matches_program_source(tmp_path: Path, template: str) -> None:
+    destination = render_template(
+        RenderOptions(template=template, destination=tmp_path / template, program_name="demo")
+    )
+    crate = destination / "programs" / "demo"
+
+    uses_init_if_needed = "init_if_needed" in (crate / "src" / "lib.rs").read_text()
+    anchor_lang = tomllib.loads((crate / "Cargo.toml").read_text())["dependencies"]["anchor-lang"]
+    assert ("init-if-needed" in anchor_lang.get("features", [])) == uses_init_if_needed
+
+
+def test_preview_template_lists_rendered_paths_without_writing(tmp_path: Path) -> None:
+    destination = tmp_path / "preview"
+    options = RenderOptions(template="escrow", destination=destination, program_name="my_escrow")
+
+    planned = preview_template(options)
+
+    assert not destination.exists()
+    paths = {item.path.as_posix() for item in planned}
+    assert "programs/my_escrow/src/lib.rs" in paths
+    assert "tests/my_escrow.ts" in paths
+    assert "target/deploy/my_escrow-keypair.json" in paths
+    renamed = next(item for item in planned if item.path.as_posix() == "programs/my_escrow/Cargo.toml")
+    assert renamed.source is not None
+    assert renamed.source.as_posix() == "programs/{{PROGRAM_NAME_SNAKE}}/Cargo.toml"
+
+    render_template(RenderOptions(template="escrow", destination=tmp_path / "real", program_name="my_escrow"))
+    written = {p.relative_to(tmp_path / "real").as_posix() for p in (tmp_path / "real").rglob("*") if p.is_file()}
+    assert paths == written
+
+
 def test_render_template_requires_empty_destination(tmp_path: Path) -> None:
     destination = tmp_path / "demo"
     destination.mkdir()
@@ -43,3 +224,598 @@ def test_render_template_requires_empty_destination(tmp_path: Path) -> None:
 
     with pytest.raises(TemplateExistsError):
         render_template(options)
+
+
+def _populated_project(tmp_path: Path) -> Path:
+    destination = tmp_path / "demo"
+    render_template(RenderOptions(template="counter", destination=destination, program_name="demo"))
+    (destination / "programs" / "demo" / "src" / "lib.rs").write_text("// hand edited\n")
+    (destination / "tests" / "demo.ts").unlink()
+    (destination / "notes.txt").write_text("keep me")
+    return destination
+
+
+def test_render_template_lists_conflicts_for_populated_destination(tmp_path: Path) -> None:
+    destination = _populated_project(tmp_path)
+
+    with pytest.raises(TemplateExistsError) as excinfo:
+        render_template(RenderOptions(template="counter", destination=destination, program_name="demo"))
+
+    assert "programs/demo/src/lib.rs" in excinfo.value.conflicts
+    assert "tests/demo.ts" not in excinfo.value.conflicts
+    assert "programs/demo/src/lib.rs" in str(excinfo.value)
+    assert "--merge" in str(excinfo.value)
+    assert (destination / "programs" / "demo" / "src" / "lib.rs").read_text() == "// hand edited\n"
+    assert not (destination / "tests" / "demo.ts").exists()
+
+
+def test_render_template_force_overwrites_conflicts(tmp_path: Path) -> None:
+    destination = _populated_project(tmp_path)
+    keypair = program_keypair_path(destination, "demo")
+    program_id = read_program_id(keypair)
+
+    render_template(
+        RenderOptions(template="counter", destination=destination, program_name="demo", overwrite=True)
+    )
+
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    assert f'declare_id!("{program_id}")' in lib_rs
+    assert (destination / "tests" / "demo.ts").exists()
+    assert (destination / "notes.txt").read_text() == "keep me"
+    assert read_program_id(keypair) == program_id
+
+
+def test_render_template_merge_only_adds_missing_files(tmp_path: Path) -> None:
+    destination = _populated_project(tmp_path)
+    program_id = read_program_id(program_keypair_path(destination, "demo"))
+
+    render_template(
+        RenderOptions(template="counter", destination=destination, program_name="demo", merge=True)
+    )
+
+    assert (destination / "programs" / "demo" / "src" / "lib.rs").read_text() == "// hand edited\n"
+    assert (destination / "notes.txt").read_text() == "keep me"
+    restored = (destination / "tests" / "demo.ts").read_text()
+    assert "{{" not in restored
+    assert read_program_id(program_keypair_path(destination, "demo")) == program_id
+
+
+def test_render_template_fills_current_directory_named_apart_from_program(
+    tmp_path: Path, monkeypatch
+) -> None:
+    repo_root = tmp_path / "my-repo"
+    repo_root.mkdir()
+    monkeypatch.chdir(repo_root)
+
+    output = render_template(RenderOptions(template="token", destination=Path("."), program_name="MyToken"))
+
+    assert output == repo_root.resolve()
+    # The directory is filled in place, so the working directory stays valid.
+    assert Path.cwd().exists() and (Path.cwd() / "Anchor.toml").exists()
+    cargo = tomllib.loads((repo_root / "programs" / "my_token" / "Cargo.toml").read_text())
+    assert cargo["package"]["name"] == "my_token"
+    assert not (repo_root / "programs" / "token").exists()
+    assert (repo_root / "tests" / "my_token.ts").exists()
+    assert not list(repo_root.rglob("*{{*"))
+    assert "my_repo" not in (repo_root / "Anchor.toml").read_text()
+
+
+def test_render_template_rejects_unreplaced_placeholders(tmp_path: Path) -> None:
+    # Without a solcoder.toml nothing declares UNMAPPED, so only the post-render scan sees it.
+    template = tmp_path / "template"
+    (template / "programs" / "{{PROGRAM_NAME_SNAKE}}" / "src").mkdir(parents=True)
+    (template / "programs" / "{{PROGRAM_NAME_SNAKE}}" / "src" / "lib.rs").write_text(
+        "use anchor_lang::prelude::*;\nconst LIMIT: usize = {{UNMAPPED}};\n"
+    )
+    destination = tmp_path / "demo"
+    options = RenderOptions(
+        template="custom", destination=destination, program_name="demo", template_path=template
+    )
+
+    with pytest.raises(UnrenderedPlaceholderError) as excinfo:
+        render_template(options)
+
+    assert excinfo.value.leftovers == ["programs/demo/src/lib.rs:2: const LIMIT: usize = {{UNMAPPED}};"]
+    assert "programs/demo/src/lib.rs:2" in str(excinfo.value)
+    assert not destination.exists()
+
+
+def test_render_template_adds_license_header_once(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    render_template(
+        RenderOptions(template="counter", destination=destination, program_name="demo", license="mit")
+    )
+
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    assert lib_rs.startswith("// SPDX-License-Identifier: MIT\nuse anchor_lang::prelude::*;")
+    assert lib_rs.count("SPDX-License-Identifier") == 1
+    suite = (destination / "tests" / "demo.ts").read_text()
+    assert suite.startswith("// SPDX-License-Identifier: MIT\n")
+    assert (destination / "LICENSE").read_text().startswith("MIT License")
+
+
+def test_render_template_license_none_adds_nothing(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    render_template(
+        RenderOptions(template="counter", destination=destination, program_name="demo", license="none")
+    )
+
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    assert "SPDX-License-Identifier" not in lib_rs
+    assert not (destination / "LICENSE").exists()
+
+
+def test_render_template_rejects_unknown_license(tmp_path: Path) -> None:
+    options = RenderOptions(template="counter", destination=tmp_path / "demo", license="WTFPL")
+
+    with pytest.raises(TemplateError, match="Unsupported license"):
+        render_template(options)
+    assert not (tmp_path / "demo").exists()
+
+
+def test_render_template_pins_requested_anchor_version(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    render_template(
+        RenderOptions(template="token", destination=destination, program_name="demo", anchor_version="v0.30.0")
+    )
+
+    dependencies = tomllib.loads((destination / "Cargo.toml").read_text())["workspace"]["dependencies"]
+    assert dependencies == {"anchor-lang": "0.30.0", "anchor-spl": "0.30.0"}
+    package = json.loads((destination / "package.json").read_text())
+    assert package["dependencies"]["@coral-xyz/anchor"] == "^0.30.0"
+
+
+def test_render_template_rejects_non_semver_anchor_version(tmp_path: Path) -> None:
+    options = RenderOptions(template="counter", destination=tmp_path / "demo", anchor_version="latest")
+
+    with pytest.raises(TemplateError, match="not a semver"):
+        render_template(options)
+    assert not (tmp_path / "demo").exists()
+
+
+def test_anchor_version_warning_flags_breaking_releases() -> None:
+    assert anchor_version_warning(None) is None
+    assert anchor_version_warning("0.30.0") is None
+    assert "`ctx.bumps` became a struct" in anchor_version_warning("0.28.0")
+    assert "Solana crates moved to 2.x" in anchor_version_warning("0.31.1")
+
+
+def test_counter_template_ships_anchor_test_suite(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    options = RenderOptions(
+        template="counter",
+        destination=destination,
+        program_name="demo_counter",
+        program_id="Demo111111111111111111111111111111111111111",
+    )
+
+    render_template(options)
+
+    test_ts = (destination / "tests" / "demo_counter.ts").read_text()
+    assert "../target/types/demo_counter" in test_ts
+    assert "Demo111111111111111111111111111111111111111" in test_ts
+    assert "Unauthorized" in test_ts
+    assert (destination / "package.json").exists()
+    assert (destination / "tsconfig.json").exists()
+    assert "ts-mocha" in (destination / "Anchor.toml").read_text()
+
+
+def test_counter_authority_transfer_is_timelocked(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    render_template(RenderOptions(template="counter", destination=destination, program_name="demo"))
+
+    lib_rs = (destination / "programs" / "demo" / "src" / "lib.rs").read_text()
+    for name in ("propose_authority", "accept_authority", "cancel_pending_authority"):
+        assert f"pub fn {name}(" in lib_rs
+    # An instant transfer would bypass the delay.
+    assert "pub fn transfer_authority(" not in lib_rs
+    assert "pub pending_authority: Option<Pubkey>," in lib_rs
+    assert "pub transfer_eligible_at: i64," in lib_rs
+    test_ts = (destination / "tests" / "demo.ts").read_text()
+    assert "TransferNotReady" in test_ts
+    assert "waitUntil(eligibleAt)" in test_ts
+
+
+def test_counter_preview_is_a_read_only_view(tmp_path: Path) -> None:
+    destination = tmp_path / "ctr"
+    render_template(RenderOptions(template="counter", destination=destination, program_name="ctr"))
+
+    lib_rs = (destination / "programs" / "ctr" / "src" / "lib.rs").read_text()
+    assert "pub fn preview(ctx: Context<ReadCounter>, delta: i64) -> Result<()>" in lib_rs
+    accounts = lib_rs[lib_rs.index("pub struct ReadCounter<'info>") :]
+    accounts = accounts[: accounts.index("\n}\n")]
+    assert "#[account" not in accounts
+    assert "Signer<" not in accounts
+    assert "emit!(CountPreviewed {" in lib_rs
+    cpi = (destination / "programs" / "ctr" / "cpi" / "src" / "lib.rs").read_text()
+    preview = cpi[cpi.index('"preview",') : cpi.index("pub fn", cpi.index('"preview",'))]
+    assert "vec![AccountMeta::new_readonly(*counter, false)]" in preview
+    assert ".simulate();" in (destination / "app" / "client.ts").read_text()
+
+
+def test_token_minting_is_gated_by_the_allowlist(tmp_path: Path) -> None:
+    destination = tmp_path / "tok"
+    render_template(RenderOptions(template="token", destination=destination, program_name="tok"))
+
+    lib_rs = (destination / "programs" / "tok" / "src" / "lib.rs").read_text()
+    assert "pub fn add_to_allowlist(ctx: Context<UpdateAllowlist>, wallet: Pubkey)" in lib_rs
+    assert "pub fn remove_from_allowlist(ctx: Context<UpdateAllowlist>, wallet: Pubkey)" in lib_rs
+    # Every minting path reads the PDA by its seeds, so callers cannot leave it out.
+    for accounts in ("MintTokens", "MintToAta", "Airdrop"):
+        body = lib_rs[lib_rs.index(f"pub struct {accounts}<'info>") :]
+        body = body[: body.index("\n}\n")]
+        assert '#[account(seeds = [b"allowlist", mint.key().as_ref()], bump)]' in body, accounts
+    assert lib_rs.count("Allowlist::check_recipient(") == 3
+    assert "self.wallets.is_empty() || self.wallets.contains(wallet)" in lib_rs
+    assert "RecipientNotAllowed" in lib_rs
+
+
+def test_token_mint_authority_is_the_config_pda(tmp_path: Path) -> None:
+    destination = tmp_path / "tok"
+    render_template(RenderOptions(template="token", destination=destination, program_name="tok"))
+
+    lib_rs = (destination / "programs" / "tok" / "src" / "lib.rs").read_text()
+    initialize = lib_rs[lib_rs.index("pub fn initialize(") :]
+    initialize = initialize[: initialize.index("\n    }\n")]
+    assert "&ctx.accounts.config.key(),\n            freeze_authority.as_ref()," in initialize
+    # Every mint goes through the PDA-signed helper, so the wallet never holds the authority.
+    assert lib_rs.count("token::mint_to(") == 1
+    assert lib_rs.count("mint_with_config(") == 4
+    helper = lib_rs[lib_rs.index("fn mint_with_config<'info>(") :]
+    assert "CpiContext::new_with_signer(" in helper[: helper.index("\n}\n")]
+    for accounts in ("MintTokens", "MintToAta", "Airdrop", "UpdateAllowlist", "SetMintAuthority"):
+        body = lib_rs[lib_rs.index(f"pub struct {accounts}<'info>") :]
+        body = body[: body.index("\n}\n")]
+        assert "has_one = authority @ TokenError::Unauthorized" in body, accounts
+
+
+def test_escrow_reclaim_expired_is_permissionless(tmp_path: Path) -> None:
+    destination = tmp_path / "esc"
+    render_template(RenderOptions(template="escrow", destination=destination, program_name="esc"))
+
+    lib_rs = (destination / "programs" / "esc" / "src" / "lib.rs").read_text()
+    assert "pub fn reclaim_expired(ctx: Context<ReclaimExpired>)" in lib_rs
+    accounts = lib_rs[lib_rs.index("pub struct ReclaimExpired<'info>") :]
+    accounts = accounts[: accounts.index("\n}\n")]
+    # The maker only receives funds; the caller can be any keeper.
+    assert "pub maker: SystemAccount<'info>," in accounts
+    assert "pub caller: Signer<'info>," in accounts
+    assert "emit!(EscrowExpired {" in lib_rs
+    assert "EscrowError::NotExpired" in lib_rs
+
+
+def test_escrow_deposit_saves_the_escrow_bookkeeping(tmp_path: Path) -> None:
+    destination = tmp_path / "esc"
+    render_template(RenderOptions(template="escrow", destination=destination, program_name="esc"))
+
+    lib_rs = (destination / "programs" / "esc" / "src" / "lib.rs").read_text()
+    accounts = lib_rs[lib_rs.index("pub struct Deposit<'info>") :]
+    accounts = accounts[: accounts.index("pub escrow: Account<'info, Escrow>,")]
+    # Anchor only writes back accounts marked `mut`; without it deposits are lost.
+    assert "mut," in accounts
+    assert "records each deposit on the escrow account" in (destination / "tests" / "esc.ts").read_text()
+
+
+@pytest.mark.parametrize("template", ["escrow", "token"])
+def test_program_directory_named_after_program(tmp_path: Path, template: str) -> None:
+    destination = tmp_path / "out"
+    options = RenderOptions(
+        template=template,
+        destination=destination,
+        program_name="my_escrow",
+        program_id="Demo111111111111111111111111111111111111111",
+    )
+
+    render_template(options)
+
+    assert [p.name for p in (destination / "programs").iterdir()] == ["my_escrow"]
+    assert not list(destination.rglob("counter*"))
+    assert not list(destination.rglob("*{{*"))
+    cargo = (destination / "programs" / "my_escrow" / "Cargo.toml").read_text()
+    assert 'name = "my_escrow"' in cargo
+
+
+@pytest.mark.parametrize(
+    "template", ["counter", "token", "nft", "registry", "escrow", "staking", "multisig", "vesting", "voting"]
+)
+def test_migration_script_targets_declared_program(tmp_path: Path, template: str) -> None:
+    destination = tmp_path / template
+    render_template(RenderOptions(template=template, destination=destination, program_name="my_app"))
+
+    deploy = (destination / "migrations" / "deploy.ts").read_text()
+    program_id = read_program_id(program_keypair_path(destination, "my_app"))
+    assert f'new anchor.web3.PublicKey("{program_id}")' in deploy
+    assert "module.exports = async function (provider" in deploy
+    if template == "counter":
+        assert 'import { myAppClient } from "../app/client";' in deploy
+
+
+@pytest.mark.parametrize("template", ["counter", "registry"])
+def test_client_wraps_every_instruction(tmp_path: Path, template: str) -> None:
+    destination = tmp_path / template
+    render_template(RenderOptions(template=template, destination=destination, program_name="my_app"))
+
+    client = (destination / "app" / "client.ts").read_text()
+    assert "export function myAppClient(" in client
+    assert 'from "../target/types/my_app"' in client
+    lib_rs = (destination / "programs" / "my_app" / "src" / "lib.rs").read_text()
+    program_mod = lib_rs.split("#[program]", 1)[1].split("\n}\n", 1)[0]
+    for instruction in re.findall(r"pub fn (\w+)", program_mod):
+        helper = to_camel_case(instruction)
+        assert f"    {helper}: " in client, helper
+        assert f".{helper}(" in client, helper
+    # anchor test compiles the client through the suite's import.
+    suite = (destination / "tests" / "my_app.ts").read_text()
+    assert re.search(r'import \{[^}]*\bmyAppClient\b[^}]*\} from "\.\./app/client";', suite)
+
+
+@pytest.mark.parametrize("template", ["counter", "registry"])
+def test_cpi_crate_builds_every_instruction(tmp_path: Path, template: str) -> None:
+    destination = tmp_path / template
+    render_template(RenderOptions(template=template, destination=destination, program_name="my_app"))
+
+    workspace = tomllib.loads((destination / "Cargo.toml").read_text())
+    assert "programs/my_app/cpi" in workspace["workspace"]["members"]
+    crate = destination / "programs" / "my_app" / "cpi"
+    manifest = tomllib.loads((crate / "Cargo.toml").read_text())
+    assert manifest["package"]["name"] == "my_app-cpi"
+    assert manifest["lib"]["name"] == "my_app_cpi"
+    cpi = (crate / "src" / "lib.rs").read_text()
+    program_id = read_program_id(program_keypair_path(destination, "my_app"))
+    assert f'pub const ID: Pubkey = pubkey!("{program_id}");' in cpi
+    lib_rs = (destination / "programs" / "my_app" / "src" / "lib.rs").read_text()
+    program_mod = lib_rs.split("#[program]", 1)[1].split("\n}\n", 1)[0]
+    for instruction in re.findall(r"pub fn (\w+)", program_mod):
+        assert re.search(rf"pub fn {instruction}\(", cpi), instruction
+        assert f'"{instruction}",' in cpi, instruction
+    # The generated crate ships its own unit test of an instruction builder.
+    assert "#[cfg(test)]" in cpi
+
+
+def test_registry_template_substitutes_max_value(tmp_path: Path) -> None:
+    destination = tmp_path / "reg"
+    options = RenderOptions(
+        template="registry",
+        destination=destination,
+        program_name="reg",
+        answers={"max_value": "1024"},
+    )
+
+    render_template(options)
+
+    lib_rs = (destination / "programs" / "reg" / "src" / "lib.rs").read_text()
+    assert "pub const MAX_VALUE: usize = 1024;" in lib_rs
+    assert '#[msg("Record value exceeds 1024 bytes.")]' in lib_rs
+    assert "{{REGISTRY_MAX_VALUE}}" not in lib_rs
+
+
+def test_registry_template_defaults_max_value(tmp_path: Path) -> None:
+    destination = tmp_path / "reg"
+    render_template(RenderOptions(template="registry", destination=destination, program_name="reg"))
+
+    lib_rs = (destination / "programs" / "reg" / "src" / "lib.rs").read_text()
+    assert "pub const MAX_VALUE: usize = 256;" in lib_rs
+
+
+def test_token_template_substitutes_decimals_and_initial_supply(tmp_path: Path) -> None:
+    destination = tmp_path / "tok"
+    render_template(
+        RenderOptions(
+            template="token",
+            destination=destination,
+            program_name="tok",
+            answers={"decimals": "9", "initial_supply": "1000000"},
+        )
+    )
+
+    lib_rs = (destination / "programs" / "tok" / "src" / "lib.rs").read_text()
+    assert "pub const TOKEN_DECIMALS: u8 = 9;" in lib_rs
+    assert "pub const INITIAL_SUPPLY_TOKENS: u64 = 1000000;" in lib_rs
+    deploy = (destination / "migrations" / "deploy.ts").read_text()
+    assert "const TOKEN_DECIMALS = 9;" in deploy
+    assert 'const INITIAL_SUPPLY_TOKENS = new anchor.BN("1000000");' in deploy
+    assert ".initialize(TOKEN_DECIMALS, null, null, UNCAPPED, supply)" in deploy
+
+
+@pytest.mark.parametrize(
+    "answers, message",
+    [
+        ({"decimals": "10"}, "between 0 and 9"),
+        ({"initial_supply": "1.5"}, "whole number of tokens"),
+        # 10^11 tokens at 9 decimals is 10^20 base units; u64::MAX is about 1.8 * 10^19.
+        ({"decimals": "9", "initial_supply": "100000000000"}, "more than a u64 holds"),
+    ],
+)
+def test_token_template_rejects_unrepresentable_supply(tmp_path: Path, answers: dict, message: str) -> None:
+    destination = tmp_path / "tok"
+    with pytest.raises(TemplateError, match=message):
+        render_template(
+            RenderOptions(template="token", destination=destination, program_name="tok", answers=answers)
+        )
+    assert not destination.exists()
+
+
+def test_registry_seed_constant_matches_client(tmp_path: Path) -> None:
+    destination = tmp_path / "reg"
+    render_template(RenderOptions(template="registry", destination=destination, program_name="reg"))
+
+    program = destination / "programs" / "reg" / "src"
+    rust = re.search(
+        r'pub const REGISTRY_SEED: &\[u8\] = b"([^"]*)";', (program / "constants.rs").read_text()
+    )
+    client = re.search(
+        r'export const REGISTRY_SEED = Buffer\.from\("([^"]*)"\);',
+        (destination / "app" / "client.ts").read_text(),
+    )
+    assert rust and client
+    assert rust.group(1).encode() == client.group(1).encode("utf-8")
+    # Every PDA derivation goes through the constant rather than its own literal.
+    lib_rs = (program / "lib.rs").read_text()
+    code = "\n".join(line for line in lib_rs.splitlines() if not line.lstrip().startswith("//"))
+    assert 'b"registry"' not in code
+    assert "seeds = [REGISTRY_SEED," in code
+    assert (destination / "app" / "client.ts").read_text().count('Buffer.from("registry")') == 1
+    assert 'Buffer.from("registry")' not in (destination / "tests" / "reg.ts").read_text()
+
+
+def test_registry_bounds_keys_before_deriving_record_pdas(tmp_path: Path) -> None:
+    destination = tmp_path / "reg"
+    render_template(RenderOptions(template="registry", destination=destination, program_name="reg"))
+
+    program = destination / "programs" / "reg" / "src"
+    assert "pub const MAX_SEED_KEY: usize = 32;" in (program / "constants.rs").read_text()
+    lib_rs = (program / "lib.rs").read_text()
+    seeds = re.findall(r"seeds = \[([^\]]*)\]", lib_rs)
+    assert seeds
+    # Each seeds constraint checks the key length before Anchor derives the PDA.
+    for seed in seeds:
+        assert seed.endswith("key_seed(&key)?"), seed
+    assert "key_seed(&entry.key)?" in lib_rs
+    assert "KeyTooLongForSeed" in (destination / "tests" / "reg.ts").read_text()
+
+
+def test_registry_sets_immutable_fields_only_on_first_write(tmp_path: Path) -> None:
+    destination = tmp_path / "reg"
+    render_template(RenderOptions(template="registry", destination=destination, program_name="reg"))
+
+    lib_rs = (destination / "programs" / "reg" / "src" / "lib.rs").read_text()
+    start = lib_rs.index("fn write_record(")
+    write_record = lib_rs[start : lib_rs.index("\n}\n", start)]
+    first_write, update = write_record.split("} else {", 1)
+    assert "if is_uninitialized(record) {" in first_write
+    assert "record.authority = authority;" in first_write
+    assert "record.created_at = now;" in first_write
+    assert "RegistryError::Unauthorized" in update.split("}", 1)[0]
+    assert "created_at" not in update
+    assert "if !is_uninitialized(record) {" in lib_rs
+    assert "rejects a reinitialization attempt" in (destination / "tests" / "reg.ts").read_text()
+
+
+@pytest.mark.parametrize("value", ["0", "10001", "big"])
+def test_registry_template_rejects_invalid_max_value(tmp_path: Path, value: str) -> None:
+    destination = tmp_path / "reg"
+    options = RenderOptions(
+        template="registry",
+        destination=destination,
+        program_name="reg",
+        answers={"max_value": value},
+    )
+
+    with pytest.raises(TemplateError):
+        render_template(options)
+    assert not destination.exists()
+
+
+@pytest.mark.parametrize(
+    "name, pascal, camel",
+    [
+        ("my-token", "MyToken", "myToken"),
+        ("my_token", "MyToken", "myToken"),
+        ("  My Token  ", "MyToken", "myToken"),
+        ("MY_TOKEN", "MyToken", "myToken"),
+        ("my--token__v2", "MyTokenV2", "myTokenV2"),
+        ("token2022", "Token2022", "token2022"),
+        ("sales_stats", "SalesStats", "salesStats"),
+        ("-_-", "Counter", "counter"),
+        ("MyToken", "MyToken", "myToken"),
+    ],
+)
+def test_program_name_case_conversion(name: str, pascal: str, camel: str) -> None:
+    assert to_pascal_case(name) == pascal
+    assert to_camel_case(name) == camel
+
+
+@pytest.mark.parametrize(
+    "name, snake",
+    [
+        ("my-token", "my_token"),
+        ("My Token", "my_token"),
+        ("sales-stats-v2", "sales_stats_v2"),
+        ("counter-2", "counter_2"),
+        ("moduleKit", "module_kit"),
+    ],
+)
+def test_normalise_program_name_yields_rust_identifiers(name: str, snake: str) -> None:
+    assert normalise_program_name(name) == snake
+
+
+@pytest.mark.parametrize("name", ["123", "2fast", "42-counter", "mod", "type", "Crate", "self"])
+def test_normalise_program_name_rejects_unusable_names(name: str) -> None:
+    with pytest.raises(InvalidProgramNameError):
+        normalise_program_name(name)
+
+
+def test_render_template_rejects_keyword_program_name(tmp_path: Path) -> None:
+    options = RenderOptions(template="counter", destination=tmp_path / "demo", program_name="type")
+
+    with pytest.raises(TemplateError, match="the Rust keyword `type`"):
+        render_template(options)
+    assert not (tmp_path / "demo").exists()
+
+
+def test_render_template_substitutes_case_placeholders(tmp_path: Path) -> None:
+    destination = tmp_path / "demo"
+    render_template(RenderOptions(template="counter", destination=destination, program_name="my-token"))
+
+    test_ts = (destination / "tests" / "my_token.ts").read_text()
+    assert "anchor.workspace.MyToken as Program<MyToken>" in test_ts
+    assert "# My Token Counter Template" in (destination / "README.md").read_text()
+
+
+def test_list_templates_reports_manifest_variables() -> None:
+    templates = {info.key: info for info in list_templates()}
+
+    for key in ("counter", "registry", "escrow", "token"):
+        assert templates[key].complete, key
+        assert "PROGRAM_NAME_SNAKE" in templates[key].required_variables
+    registry_vars = {variable.name: variable for variable in templates["registry"].variables}
+    assert registry_vars["REGISTRY_MAX_VALUE"].default == "256"
+    assert "REGISTRY_MAX_VALUE" not in templates["registry"].required_variables
+
+
+def test_list_templates_marks_blueprints_without_source_as_stubs(tmp_path: Path, monkeypatch) -> None:
+    (tmp_path / "nft2").mkdir()
+    (tmp_path / "nft2" / "wizard.json").write_text('{"questions": []}')
+    monkeypatch.setattr("solcoder.cli.blueprints.BLUEPRINTS_ROOT", tmp_path)
+    monkeypatch.setattr("solcoder.cli.blueprints.REGISTRY_PATH", tmp_path / "registry.json")
+
+    templates = {info.key: info for info in list_templates()}
+
+    assert templates["nft2"].complete is False
+    assert templates["nft2"].variables == []
+
+
+
+def test_render_template_writes_gitignore_for_deploy_keys(tmp_path: Path) -> None:
+    destination = render_template(RenderOptions(template="counter", destination=tmp_path / "demo"))
+
+    lines = (destination / ".gitignore").read_text().splitlines()
+    assert "**/*-keypair.json" in lines
+    assert set(GITIGNORE_ENTRIES) <= set(lines)
+    planned = preview_template(RenderOptions(template="counter", destination=tmp_path / "other"))
+    assert ".gitignore" in {item.path.as_posix() for item in planned}
+
+
+def test_ensure_gitignore_merges_missing_entries(tmp_path: Path) -> None:
+    gitignore = tmp_path / ".gitignore"
+    gitignore.write_text(".env\ntarget/")
+
+    ensure_gitignore(tmp_path)
+    ensure_gitignore(tmp_path)
+
+    lines = gitignore.read_text().splitlines()
+    assert lines[:2] == [".env", "target/"]
+    assert lines.count("target/") == 1
+    assert lines.count("**/*-keypair.json") == 1
+
+
+def test_registry_frozen_records_cannot_be_removed(tmp_path: Path) -> None:
+    destination = tmp_path / "reg"
+    render_template(RenderOptions(template="registry", destination=destination, program_name="reg"))
+
+    lib_rs = (destination / "programs" / "reg" / "src" / "lib.rs").read_text()
+    for handler in ("remove(ctx: Context<Remove>", "remove_global(ctx: Context<RemoveGlobal>"):
+        body = lib_rs[lib_rs.index(f"pub fn {handler}") :]
+        body = body[: body.index("\n    }\n")]
+        assert "require!(!ctx.accounts.record.frozen, RegistryError::RecordFrozen);" in body, handler
+    assert "does not let the authority remove a frozen record" in (destination / "tests" / "reg.ts").read_text()
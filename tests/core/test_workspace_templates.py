@@ -0,0 +1,169 @@
+import json
+import re
+import tomllib
+from pathlib import Path
+
+import pytest
+
+from solcoder.core.templates import RenderOptions, TemplateError, program_keypair_path, read_program_id
+from solcoder.core.workspace_templates import (
+    ERROR_CODE_RANGE,
+    error_code_offsets,
+    preview_workspace,
+    render_workspace,
+)
+
+
+def _programs(destination: Path) -> list[RenderOptions]:
+    return [
+        RenderOptions(template="counter", destination=destination, program_name="my_counter"),
+        RenderOptions(template="escrow", destination=destination, program_name="my_escrow"),
+    ]
+
+
+def test_render_workspace_lists_every_program(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+
+    render_workspace(_programs(destination), destination)
+
+    cargo = tomllib.loads((destination / "Cargo.toml").read_text())
+    assert cargo["workspace"]["members"] == [
+        "programs/my_counter",
+        "programs/my_counter/cpi",
+        "programs/my_escrow",
+    ]
+    # escrow needs anchor-spl, which the counter workspace manifest did not declare.
+    assert cargo["workspace"]["dependencies"]["anchor-spl"] == "0.30.1"
+
+    anchor = tomllib.loads((destination / "Anchor.toml").read_text())
+    assert anchor["workspace"]["members"] == ["programs/my_counter", "programs/my_escrow"]
+    ids = {}
+    for name in ("my_counter", "my_escrow"):
+        program_id = read_program_id(program_keypair_path(destination, name))
+        lib_rs = (destination / "programs" / name / "src" / "lib.rs").read_text()
+        assert re.search(r'declare_id!\("([^"]+)"\)', lib_rs).group(1) == program_id
+        assert anchor["programs"]["localnet"][name] == program_id
+        assert anchor["programs"]["devnet"][name] == program_id
+        assert (destination / "tests" / f"{name}.ts").exists()
+        ids[name] = program_id
+    assert ids["my_counter"] != ids["my_escrow"]
+
+    package = json.loads((destination / "package.json").read_text())
+    assert "@solana/spl-token" in package["dependencies"]
+
+    preview = preview_workspace(_programs(tmp_path / "again"), tmp_path / "again")
+    planned = {item.path.as_posix() for item in preview}
+    written = {p.relative_to(destination).as_posix() for p in destination.rglob("*") if p.is_file()}
+    assert planned == written
+
+
+def test_render_workspace_carries_validator_clones_of_later_programs(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    programs = [
+        RenderOptions(template="counter", destination=destination, program_name="my_counter"),
+        RenderOptions(template="token", destination=destination, program_name="my_token"),
+        RenderOptions(template="nft", destination=destination, program_name="my_nft"),
+    ]
+
+    render_workspace(programs, destination)
+
+    anchor = tomllib.loads((destination / "Anchor.toml").read_text())
+    # token and nft both clone Token Metadata; the workspace lists it once.
+    assert anchor["test"]["validator"]["clone"] == [
+        {"address": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"}
+    ]
+
+
+def test_render_workspace_rejects_duplicate_program_names(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    programs = [
+        RenderOptions(template="counter", destination=destination, program_name="shared"),
+        RenderOptions(template="escrow", destination=destination, program_name="Shared"),
+    ]
+
+    with pytest.raises(TemplateError, match="unique"):
+        render_workspace(programs, destination)
+    assert not destination.exists()
+
+
+def test_render_workspace_can_be_rerendered_with_overwrite(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    render_workspace(_programs(destination), destination)
+    first = {p.relative_to(destination): p.read_bytes() for p in destination.rglob("*") if p.is_file()}
+
+    render_workspace(_programs(destination), destination, overwrite=True)
+
+    second = {p.relative_to(destination): p.read_bytes() for p in destination.rglob("*") if p.is_file()}
+    # Program keypairs are reused, so every file, ids included, comes out the same.
+    assert second == first
+    tomllib.loads((destination / "Anchor.toml").read_text())
+
+
+def test_render_workspace_failure_keeps_an_existing_destination(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    destination.mkdir()
+    (destination / "notes.md").write_text("keep me\n")
+    programs = [
+        RenderOptions(template="counter", destination=destination, program_name="my_counter"),
+        RenderOptions(template="missing-blueprint", destination=destination, program_name="other"),
+    ]
+
+    with pytest.raises(TemplateError):
+        render_workspace(programs, destination, overwrite=True)
+
+    assert sorted(p.name for p in destination.iterdir()) == ["notes.md"]
+    assert (destination / "notes.md").read_text() == "keep me\n"
+
+
+def test_render_workspace_gives_each_program_its_own_client(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    programs = [
+        RenderOptions(template="counter", destination=destination, program_name="my_counter"),
+        RenderOptions(template="registry", destination=destination, program_name="my_registry"),
+    ]
+
+    render_workspace(programs, destination)
+
+    assert "myCounterClient" in (destination / "app" / "client.ts").read_text()
+    assert "myRegistryClient" in (destination / "app" / "my_registry_client.ts").read_text()
+    cargo = tomllib.loads((destination / "Cargo.toml").read_text())
+    assert cargo["workspace"]["members"] == [
+        "programs/my_counter",
+        "programs/my_counter/cpi",
+        "programs/my_registry",
+        "programs/my_registry/cpi",
+    ]
+    anchor = tomllib.loads((destination / "Anchor.toml").read_text())
+    assert anchor["workspace"]["members"] == ["programs/my_counter", "programs/my_registry"]
+    suite = (destination / "tests" / "my_registry.ts").read_text()
+    assert 'from "../app/my_registry_client";' in suite
+    preview = preview_workspace(programs, tmp_path / "again")
+    assert Path("app/my_registry_client.ts") in {item.path for item in preview}
+
+
+def test_render_workspace_namespaces_error_codes(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+    programs = _programs(destination)
+
+    render_workspace(programs, destination, namespace_errors=True)
+
+    ranges = []
+    for name, offset in error_code_offsets(programs).items():
+        lib_rs = (destination / "programs" / name / "src" / "lib.rs").read_text()
+        assert "#[error_code]" not in lib_rs
+        enum = re.search(rf"#\[error_code\(offset = {offset}\)\]\s*pub enum \w+ \{{(.*?)\n\}}", lib_rs, re.DOTALL)
+        assert enum, name
+        variants = len(re.findall(r"(?m)^\s+[A-Z]\w*,$", enum.group(1)))
+        assert 0 < variants <= ERROR_CODE_RANGE
+        ranges.append(range(offset, offset + variants))
+    assert ranges[0].start == 6000
+    assert ranges[0].stop <= ranges[1].start
+
+
+def test_render_workspace_keeps_default_error_codes(tmp_path: Path) -> None:
+    destination = tmp_path / "workspace"
+
+    render_workspace(_programs(destination), destination)
+
+    for name in ("my_counter", "my_escrow"):
+        assert "#[error_code]\n" in (destination / "programs" / name / "src" / "lib.rs").read_text()
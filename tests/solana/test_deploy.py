@@ -40,6 +40,19 @@ def test_ensure_program_keypair_updates_declare_id(tmp_path: Path) -> None:
     assert cfg_after["programs"]["devnet"]["demo"] == program_id
 
 
+def test_ensure_program_keypair_reuses_scaffolded_keypair(tmp_path: Path) -> None:
+    workspace = _make_workspace(tmp_path)
+    first_path, program_id = deploy.ensure_program_keypair(tmp_path / "other", "demo")
+    scaffolded = workspace / "target" / "deploy" / "demo-keypair.json"
+    scaffolded.parent.mkdir(parents=True)
+    scaffolded.write_text(first_path.read_text())
+
+    key_path, reused_id = deploy.ensure_program_keypair(workspace, "demo")
+
+    assert key_path == scaffolded
+    assert reused_id == program_id
+
+
 def test_run_anchor_deploy_parses_program_id(monkeypatch, tmp_path: Path) -> None:
     workspace = tmp_path / "workspace"
     workspace.mkdir()
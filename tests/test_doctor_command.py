@@ -0,0 +1,18 @@
+from typer.testing import CliRunner
+
+from solcoder.cli import app
+
+runner = CliRunner()
+
+
+def test_doctor_command_passes_bundled_templates() -> None:
+    result = runner.invoke(app, ["doctor"])
+    assert result.exit_code == 0, result.stdout
+    for key in ("counter", "registry", "escrow", "token"):
+        assert f"{key}: ok" in result.stdout
+
+
+def test_doctor_command_rejects_unknown_template() -> None:
+    result = runner.invoke(app, ["doctor", "nope"])
+    assert result.exit_code == 2
+    assert "Unknown template 'nope'" in result.stdout
@@ -0,0 +1,14 @@
+from typer.testing import CliRunner
+
+from solcoder.cli import app
+
+runner = CliRunner()
+
+
+def test_list_templates_command_prints_known_templates() -> None:
+    result = runner.invoke(app, ["list-templates"])
+    assert result.exit_code == 0
+    for key in ("counter", "registry", "escrow", "token"):
+        assert f"{key} (complete)" in result.stdout
+    assert "REGISTRY_MAX_VALUE (default: '256')" in result.stdout
+    assert "PROGRAM_NAME_SNAKE (required)" in result.stdout
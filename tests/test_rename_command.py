@@ -0,0 +1,25 @@
+from pathlib import Path
+
+from typer.testing import CliRunner
+
+from solcoder.cli import app
+from solcoder.core.templates import RenderOptions, render_template
+
+runner = CliRunner()
+
+
+def test_rename_command_moves_the_program(tmp_path: Path) -> None:
+    root = render_template(RenderOptions(template="counter", destination=tmp_path / "ws", program_name="demo"))
+
+    result = runner.invoke(app, ["rename", "--from", "demo", "--to", "tally", "--path", str(root)])
+
+    assert result.exit_code == 0, result.stdout
+    assert "Renamed program 'demo' to 'tally'" in result.stdout
+    assert (root / "programs" / "tally" / "src" / "lib.rs").exists()
+
+
+def test_rename_command_reports_a_missing_program(tmp_path: Path) -> None:
+    result = runner.invoke(app, ["rename", "--from", "demo", "--to", "tally", "--path", str(tmp_path)])
+
+    assert result.exit_code == 1
+    assert "No program 'demo'" in result.stdout
@@ -0,0 +1,30 @@
+from pathlib import Path
+
+from typer.testing import CliRunner
+
+from solcoder.cli import app
+from solcoder.core.templates import RenderOptions, program_keypair_path, read_program_id, render_template
+
+runner = CliRunner()
+
+
+def test_verify_id_consistency_passes_for_a_fresh_scaffold(tmp_path: Path) -> None:
+    root = render_template(RenderOptions(template="counter", destination=tmp_path / "ws", program_name="demo"))
+
+    result = runner.invoke(app, ["verify-id-consistency", "--path", str(root)])
+
+    assert result.exit_code == 0, result.stdout
+    assert "Program ids are consistent." in result.stdout
+
+
+def test_verify_id_consistency_reports_the_mismatched_file(tmp_path: Path) -> None:
+    root = render_template(RenderOptions(template="counter", destination=tmp_path / "ws", program_name="demo"))
+    program_id = read_program_id(program_keypair_path(root, "demo"))
+    other = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+    anchor_toml = root / "Anchor.toml"
+    anchor_toml.write_text(anchor_toml.read_text().replace(f'demo = "{program_id}"', f'demo = "{other}"', 1))
+
+    result = runner.invoke(app, ["verify-id-consistency", "--path", str(root)])
+
+    assert result.exit_code == 1
+    assert f"Anchor.toml [programs.localnet]: demo is {other}" in result.stdout